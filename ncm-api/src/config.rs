@@ -1,8 +1,19 @@
 pub static BASE_URL: &str = "https://music.163.com";
 
+/// 接口地址，请求失败时依次切换
+pub const API_HOST_LIST: [&str; 3] = [
+    "https://music.163.com",
+    "https://interface.music.163.com",
+    "https://interface3.music.163.com",
+];
+
 pub const DEFAULT_MAX_CONNECTIONS: usize = 32;
 
-pub const TIMEOUT: u64 = 100;
+/// 云盘上传服务器
+pub const CLOUD_UPLOAD_HOST: &str = "http://45.127.129.8";
+
+/// 云盘上传 bucket
+pub const CLOUD_UPLOAD_BUCKET: &str = "jd-musicrep-privatecloud-audio-public";
 
 pub const LINUX_USER_AGNET: &str =
     "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/60.0.3112.90 Safari/537.36";
@@ -38,5 +49,3 @@ pub const BASE_URL_LIST: [&str; 12] = [
     "https://music.163.com/wapi/feedback",
     "https://music.163.com/openapi/clientlog",
 ];
-
-pub const DEFAULT_RATE: u32 = 1900000;
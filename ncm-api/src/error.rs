@@ -0,0 +1,113 @@
+//
+// error.rs
+//
+use std::{fmt, io};
+
+pub type Result<T> = std::result::Result<T, NcmError>;
+
+/// ncm-api 错误类型
+#[derive(Debug)]
+pub enum NcmError {
+    /// 网络请求失败（连接失败、超时等）
+    Network(isahc::Error),
+    /// HTTP 状态码非 2xx
+    Http(u16),
+    /// 接口返回的 code 非 200
+    Api { code: i64, msg: String },
+    /// 需要登录，未登录或 cookie 已过期
+    AuthRequired,
+    /// 请求过于频繁，被服务器限制
+    RateLimited(i64),
+    /// 歌曲因版权或地区限制无法播放
+    Blocked(u64),
+    /// 歌曲没有歌词（纯音乐或暂无歌词）
+    LyricNotFound,
+    /// 返回数据格式与预期不符
+    Json(String),
+    /// 本地文件读写失败
+    Io(io::Error),
+    /// 其他错误
+    Other(String),
+}
+
+impl NcmError {
+    /// 根据接口返回的 code 生成错误
+    pub(crate) fn from_code(code: i64, msg: String) -> Self {
+        match code {
+            301 => NcmError::AuthRequired,
+            // 405: 操作频繁，-460/-462: 触发风控
+            405 | -460 | -462 => NcmError::RateLimited(code),
+            _ => NcmError::Api { code, msg },
+        }
+    }
+}
+
+impl fmt::Display for NcmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NcmError::Network(e) => write!(f, "网络连接失败: {}", e),
+            NcmError::Http(status) => write!(f, "服务器错误: HTTP {}", status),
+            NcmError::Api { code, msg } if msg.is_empty() => write!(f, "接口返回错误 ({})", code),
+            NcmError::Api { code, msg } => write!(f, "{} ({})", msg, code),
+            NcmError::AuthRequired => write!(f, "请先登录"),
+            NcmError::RateLimited(code) => write!(f, "请求过于频繁，请稍后再试 ({})", code),
+            NcmError::Blocked(song_id) => write!(f, "歌曲 {} 暂无版权或受地区限制", song_id),
+            NcmError::LyricNotFound => write!(f, "暂无歌词"),
+            NcmError::Json(msg) => write!(f, "数据解析失败: {}", msg),
+            NcmError::Io(e) => write!(f, "文件读写失败: {}", e),
+            NcmError::Other(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for NcmError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            NcmError::Network(e) => Some(e),
+            NcmError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<isahc::Error> for NcmError {
+    fn from(e: isahc::Error) -> Self {
+        NcmError::Network(e)
+    }
+}
+
+impl From<isahc::http::Error> for NcmError {
+    fn from(e: isahc::http::Error) -> Self {
+        NcmError::Other(e.to_string())
+    }
+}
+
+impl From<isahc::http::uri::InvalidUri> for NcmError {
+    fn from(e: isahc::http::uri::InvalidUri) -> Self {
+        NcmError::Other(e.to_string())
+    }
+}
+
+impl From<serde_json::Error> for NcmError {
+    fn from(e: serde_json::Error) -> Self {
+        NcmError::Json(e.to_string())
+    }
+}
+
+impl From<io::Error> for NcmError {
+    fn from(e: io::Error) -> Self {
+        NcmError::Io(e)
+    }
+}
+
+impl From<regex::Error> for NcmError {
+    fn from(e: regex::Error) -> Self {
+        NcmError::Other(e.to_string())
+    }
+}
+
+impl From<openssl::error::ErrorStack> for NcmError {
+    fn from(e: openssl::error::ErrorStack) -> Self {
+        NcmError::Other(e.to_string())
+    }
+}
@@ -3,25 +3,46 @@
 //
 mod config;
 mod encrypt;
+mod error;
+mod loudness_store;
+mod lyric;
+mod lyric_store;
+#[cfg(test)]
+mod mock_server;
 pub(crate) mod model;
+mod retry;
+mod transport;
+mod upload;
 
 use crate::config::*;
 use crate::encrypt::Crypto;
+pub use crate::error::{NcmError, Result};
+use crate::loudness_store::LoudnessStore;
+pub use crate::lyric::{attach_yrc, parse_yrc, Lrc, LrcLine, LyricLine, YrcLine, YrcWord};
+use crate::lyric_store::LyricStore;
 pub use crate::model::*;
-use anyhow::{anyhow, Result};
-use cookie_store;
+pub use crate::retry::RetryPolicy;
+use crate::retry::{is_retryable, Retry};
+pub use crate::transport::{
+    FixtureTransport, IsahcTransport, Transport, TransportFuture, TransportResponse,
+};
+pub use crate::upload::{ChunkedUploader, UploadProgress};
 use cookie_store::CookieStore;
 pub use isahc::cookies::{CookieBuilder, CookieJar};
 use isahc::{prelude::*, *};
 use lazy_static::lazy_static;
-use log::{error, trace};
+use log::{debug, error, trace, warn};
 use regex::Regex;
 use std::cell::RefCell;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
-use std::{collections::HashMap, fs, io, path::PathBuf, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    fs, io,
+    path::{Path, PathBuf},
+};
 use tokio::sync::Mutex;
 use urlqstring::QueryParams;
-use clap::{Command, Arg};
 
 lazy_static! {
     static ref _CSRF: Regex = Regex::new(r"_csrf=(?P<csrf>[^(;|$)]+)").unwrap();
@@ -29,22 +50,28 @@ lazy_static! {
 
 #[derive(Clone)]
 pub struct NcmApi {
-    client: HttpClient,
+    transport: Arc<dyn Transport>,
     csrf: Arc<Mutex<RefCell<String>>>,
 
     cookie_path: PathBuf,
-    lyrics_path: PathBuf,
-    cache_path: PathBuf,
+    lyric_store: LyricStore,
+    loudness_store: LoudnessStore,
 
     is_login: bool,
     login_info: Option<LoginInfo>,
-    rate: u32,
+    quality: SongQuality,
+
+    api_hosts: Vec<String>,
+    preferred_host: Arc<AtomicUsize>, // 最近一次请求成功的地址，clone 之间共享
+    retry_policy: RetryPolicy,
 
     user_favorite_songlist_name: Option<String>,
     user_favorite_songlist: Option<Vec<SongInfo>>,
+    user_liked_song_ids: HashSet<u64>,
 }
 
 #[allow(unused)]
+#[derive(Clone, Copy)]
 enum CryptoApi {
     Weapi,
     LinuxApi,
@@ -53,23 +80,32 @@ enum CryptoApi {
 
 impl NcmApi {
     pub fn new(cookie_path: PathBuf, lyrics_path: PathBuf, cache_path: PathBuf) -> Self {
-        let client = HttpClient::builder()
-            .timeout(Duration::from_secs(TIMEOUT))
-            .max_connections(DEFAULT_MAX_CONNECTIONS)
-            .cookies()
-            .build()
-            .expect("初始化网络请求失败!");
+        let transport = IsahcTransport::new(None, None).expect("初始化网络请求失败!");
+        Self::with_transport(Arc::new(transport), cookie_path, lyrics_path, cache_path)
+    }
+
+    /// 使用指定的 Transport 发送请求，如测试时使用 FixtureTransport
+    pub fn with_transport(
+        transport: Arc<dyn Transport>,
+        cookie_path: PathBuf,
+        lyrics_path: PathBuf,
+        cache_path: PathBuf,
+    ) -> Self {
         Self {
-            client,
+            transport,
             csrf: Arc::new(Mutex::new(RefCell::new(String::new()))),
             cookie_path,
-            lyrics_path,
-            cache_path,
+            lyric_store: LyricStore::new(lyrics_path, cache_path.clone()),
+            loudness_store: LoudnessStore::new(cache_path),
             is_login: false,
             login_info: None,
-            rate: DEFAULT_RATE,
+            quality: SongQuality::default(),
+            api_hosts: API_HOST_LIST.iter().map(|host| host.to_string()).collect(),
+            preferred_host: Arc::new(AtomicUsize::new(0)),
+            retry_policy: RetryPolicy::default(),
             user_favorite_songlist_name: None,
             user_favorite_songlist: None,
+            user_liked_song_ids: HashSet::new(),
         }
     }
 }
@@ -82,33 +118,14 @@ impl NcmApi {
         cache_path: PathBuf,
     ) -> Self {
         if let Some(cookie_jar) = Self::load_cookie_jar_from_file(cookie_path.clone()) {
-            Self {
-                client: Self::create_client_from_cookie_jar(cookie_jar),
-                csrf: Arc::new(Mutex::new(RefCell::new(String::new()))),
-                cookie_path,
-                lyrics_path,
-                cache_path,
-                is_login: false,
-                login_info: None,
-                rate: DEFAULT_RATE,
-                user_favorite_songlist_name: None,
-                user_favorite_songlist: None,
-            }
+            let transport =
+                IsahcTransport::new(Some(cookie_jar), None).expect("初始化网络请求失败!");
+            Self::with_transport(Arc::new(transport), cookie_path, lyrics_path, cache_path)
         } else {
             Self::new(cookie_path, lyrics_path, cache_path)
         }
     }
 
-    fn create_client_from_cookie_jar(cookie_jar: CookieJar) -> HttpClient {
-        HttpClient::builder()
-            .timeout(Duration::from_secs(TIMEOUT))
-            .max_connections(DEFAULT_MAX_CONNECTIONS)
-            .cookies()
-            .cookie_jar(cookie_jar)
-            .build()
-            .expect("初始化网络请求失败!")
-    }
-
     fn load_cookie_jar_from_file(cookie_store_path: PathBuf) -> Option<CookieJar> {
         use cookie_store::serde;
 
@@ -142,7 +159,7 @@ impl NcmApi {
     }
 
     pub fn cookie_jar(&self) -> Option<&CookieJar> {
-        self.client.cookie_jar()
+        self.transport.cookie_jar()
     }
 
     pub fn store_cookie(&self) {
@@ -189,7 +206,15 @@ impl NcmApi {
         let mut params = HashMap::new();
         params.insert("type", "1");
         let result = self
-            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .request(
+                Method::Post,
+                path,
+                params,
+                CryptoApi::Weapi,
+                "",
+                true,
+                Retry::Never,
+            )
             .await?;
         let unikey = to_unikey(result)?;
         Ok((
@@ -206,7 +231,15 @@ impl NcmApi {
         params.insert("type", "1");
         params.insert("key", &key);
         let result = self
-            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .request(
+                Method::Post,
+                path,
+                params,
+                CryptoApi::Weapi,
+                "",
+                true,
+                Retry::Never,
+            )
             .await?;
         to_message(result)
     }
@@ -222,19 +255,22 @@ impl NcmApi {
                 CryptoApi::Weapi,
                 "",
                 true,
+                Retry::Idempotent,
             )
             .await?;
         to_login_info(result)
     }
 
     /// 使用 cookie 登录时尝试检查登录状态
-    pub async fn check_cookie_login(&mut self) -> Result<bool> {
+    /// songlist_offset: 登录后加载的歌单在用户歌单列表中的位置，见 get_user_favorite_songlist
+    pub async fn check_cookie_login(&mut self, songlist_offset: u16) -> Result<bool> {
         match self.login_status().await {
             Ok(login_info) => {
                 self.login_info = Some(login_info);
                 self.is_login = true;
 
-                self.get_user_favorite_songlist().await?;
+                self.get_user_favorite_songlist(songlist_offset).await?;
+                self.refresh_user_liked_song_ids().await;
 
                 Ok(true)
             }
@@ -243,13 +279,15 @@ impl NcmApi {
     }
 
     /// 新账号已验证登录后，初始化
-    pub async fn init_after_new_login(&mut self) -> Result<()> {
+    /// songlist_offset: 登录后加载的歌单在用户歌单列表中的位置，见 get_user_favorite_songlist
+    pub async fn init_after_new_login(&mut self, songlist_offset: u16) -> Result<()> {
         self.store_cookie();
 
         self.login_info = Some(self.login_status().await?);
         self.is_login = true;
 
-        self.get_user_favorite_songlist().await?;
+        self.get_user_favorite_songlist(songlist_offset).await?;
+        self.refresh_user_liked_song_ids().await;
 
         Ok(())
     }
@@ -267,10 +305,11 @@ impl NcmApi {
         // ).await.expect("failed to logout");
 
         self.is_login = false;
+        self.user_liked_song_ids.clear();
     }
 }
 
-/// getter
+/// setter & getter
 impl NcmApi {
     /// 是否登录
     pub fn is_login(&self) -> bool {
@@ -289,27 +328,61 @@ impl NcmApi {
             self.user_favorite_songlist.clone(),
         )
     }
+
+    /// 音质设置
+    pub fn song_quality(&self) -> SongQuality {
+        self.quality
+    }
+
+    /// 设置音质，下一次获取歌曲 url 时生效
+    pub fn set_song_quality(&mut self, quality: SongQuality) {
+        self.quality = quality;
+    }
+
+    /// 请求超时与重试策略
+    pub fn set_retry_policy(&mut self, retry_policy: RetryPolicy) {
+        self.retry_policy = retry_policy;
+    }
+
+    /// 设置接口地址，请求失败时依次切换，为空时不修改
+    pub fn set_api_hosts(&mut self, api_hosts: Vec<String>) {
+        if !api_hosts.is_empty() {
+            self.api_hosts = api_hosts;
+            self.preferred_host.store(0, Ordering::Relaxed);
+        }
+    }
+
+    /// 用户喜欢的歌曲 id
+    pub fn user_liked_song_ids(&self) -> &HashSet<u64> {
+        &self.user_liked_song_ids
+    }
+
+    /// 是否为用户喜欢的歌曲
+    pub fn is_liked_song(&self, song_id: u64) -> bool {
+        self.user_liked_song_ids.contains(&song_id)
+    }
 }
 
 /// 音乐播放 API
 impl NcmApi {
-    /// 获取音乐（单曲） url
+    /// 获取音乐（单曲） url 及实际音质
     /// 使用 Eapi 获取音乐
-    pub async fn get_song_url(&self, id: u64) -> Result<String> {
-        let song_urls = self.get_song_urls(&[id]).await?;
+    pub async fn get_song_url(&self, id: u64) -> Result<SongUrl> {
+        let mut song_urls = self.get_song_urls(&[id]).await?;
 
         if !song_urls.is_empty() {
-            Ok(song_urls[0].url.clone())
+            Ok(song_urls.swap_remove(0))
         } else {
-            Err(anyhow!("failed to get song url"))
+            // 无可用 url 时为版权或地区限制
+            Err(NcmError::Blocked(id))
         }
     }
 
     /// 歌曲 URL
     /// ids: 歌曲列表
-    /// br: 歌曲码率
+    /// 按当前音质设置请求码率
     pub async fn get_song_urls(&self, ids: &[u64]) -> Result<Vec<SongUrl>> {
-        let br = self.rate.clone().to_string();
+        let br = self.quality.rate().to_string();
         // 使用 Eapi 获取音乐
         let path = "https://interface3.music.163.com/eapi/song/enhance/player/url";
         let mut params = HashMap::new();
@@ -317,175 +390,82 @@ impl NcmApi {
         params.insert("ids", ids.as_str());
         params.insert("br", br.as_str());
         let result = self
-            .request(Method::Post, path, params, CryptoApi::Eapi, "", true)
+            .request(
+                Method::Post,
+                path,
+                params,
+                CryptoApi::Eapi,
+                "",
+                true,
+                Retry::Idempotent,
+            )
             .await?;
         to_song_url(result)
     }
 
     /// 获取歌词
-    pub async fn song_lyric(&self, si: SongInfo) -> Result<Vec<(u64, (String, Option<String>))>> {
-        // 歌词文件位置
-        let mut lyric_path = self.lyrics_path.clone();
-        lyric_path.push(format!(
-            "{}-{}-{}.lrc",
-            si.name.replace('/', "／"),
-            si.singer,
-            si.album
-        ));
-        // 翻译歌词文件位置
-        let mut translation_lyric_path = self.cache_path.clone();
-        translation_lyric_path.push(format!("{}.tlrc", si.id));
-
-        // 替换歌词时间
-        let timestamp_re = Regex::new(r"\[\d+:\d+.\d+]")?;
-        // 修正不正常的时间戳 [00:11:22]
-        let re_abnormal_ts = Regex::new(r"^\[(\d+):(\d+):(\d+)]")?;
-        // 补全7位时间戳
-        let re_7bit_timestamp = Regex::new(r"\[(\d+):(\d+)]")?;
-
-        if !lyric_path.exists() {
-            // 创建歌词文件，访问网易云接口获取
-            if let Ok(lyric_origin) = self.get_song_lyric(si.id).await {
-                trace!("歌词: {:?}", lyric_origin);
-
-                let lyric_fixed = Lyrics {
-                    lyric: lyric_origin.lyric
-                        .into_iter()
-                        .map(|x| {
-                            let mut fixed = re_abnormal_ts.replace_all(&x, "[$1:$2.$3]").to_string();
-                            fixed = re_7bit_timestamp.replace_all(&fixed, "[$1:$2.000]").to_string();
-                            fixed
-                        })
-                        .collect(),
-                    tlyric: lyric_origin.tlyric
-                        .into_iter()
-                        .map(|x| {
-                            let mut fixed = re_abnormal_ts.replace_all(&x, "[$1:$2.$3]").to_string();
-                            fixed = re_7bit_timestamp.replace_all(&fixed, "[$1:$2.000]").to_string();
-                            fixed
-                        })
-                        .collect(),
-                };
+    /// 优先使用歌词目录中用户提供的歌词文件，其次是本地缓存，都没有时访问网易云接口获取
+    pub async fn song_lyric(&self, si: SongInfo) -> Result<Vec<LyricLine>> {
+        let lyrics = if let Some(lyrics) = self.lyric_store.user_lyrics(&si) {
+            trace!("user lyric: {:?}", lyrics);
+            lyrics
+        } else if let Some(lyrics) = self.lyric_store.load(si.id) {
+            trace!("read lyric: {:?}", lyrics);
+            lyrics
+        } else {
+            let lyrics = self.get_song_lyric(si.id).await?;
+            trace!("歌词: {:?}", lyrics);
 
-                // 修正并保存歌词文件
-                let lyric = lyric_fixed
-                    .lyric
-                    .clone()
-                    .join("\n");
-                fs::write(&lyric_path, lyric)?;
-                if !lyric_fixed.tlyric.is_empty() {
-                    // 保存翻译歌词文件
-                    let tlyric = lyric_fixed
-                        .tlyric
-                        .clone()
-                        .join("\n");
-                    fs::write(&translation_lyric_path, tlyric)?;
-                }
+            // 缓存失败不影响显示
+            if let Err(e) = self.lyric_store.save(si.id, &lyrics) {
+                warn!("保存歌词缓存失败: {}", e);
+            }
 
-                // 编码
-                let lyrics_with_timestamp = self.encode_lyric(&timestamp_re, &lyric_fixed);
+            lyrics
+        };
 
-                // 组织歌词+翻译
-                Ok(lyrics_with_timestamp)
-            } else {
-                anyhow::bail!("No lyrics found!")
-            }
-        } else {
-            // 歌词文件已存在缓存
-            let lyric_file = fs::read_to_string(&lyric_path)?;
-            let lyric: Vec<String> = lyric_file
-                .split('\n')
-                .collect::<Vec<&str>>()
-                .iter()
-                .map(|s| s.to_string())
-                .collect();
-            let mut tlyric = vec![];
-            if translation_lyric_path.exists() {
-                let tlyric_file = fs::read_to_string(&translation_lyric_path)?;
-                tlyric = tlyric_file
-                    .split('\n')
-                    .collect::<Vec<&str>>()
-                    .iter()
-                    .map(|s| s.to_string())
-                    .collect();
-            }
+        // 组织歌词+翻译+音译
+        Ok(Self::encode_lyric(&lyrics))
+    }
 
-            // 编码歌词和翻译
-            let lyrics = Lyrics { lyric, tlyric };
-            trace!("read lyric: {:?}", lyrics);
-            let lyrics_with_stamp = self.encode_lyric(&timestamp_re, &lyrics);
+    /// 删除歌曲的歌词缓存，下次获取歌词时重新请求
+    pub fn remove_lyric_cache(&self, music_id: u64) -> Result<()> {
+        self.lyric_store.remove(music_id)
+    }
 
-            // 组织歌词+翻译
-            Ok(lyrics_with_stamp)
-        }
+    /// 用户为歌曲设置的歌词时间偏移，单位: ms，正数表示歌词延后显示
+    pub fn lyric_offset(&self, music_id: u64) -> i64 {
+        self.lyric_store.offset(music_id)
     }
 
-    /// 编码歌词和翻译
-    fn encode_lyric(
-        &self,
-        timestamp_re: &Regex,
-        lyrics: &Lyrics,
-    ) -> Vec<(u64, (String, Option<String>))> {
-        let mut lyrics_with_timestamp: Vec<(u64, (String, Option<String>))> = Vec::new();
-
-        // 以倒序遍历歌词文件和翻译文件，避免 [00:00.000] 同时有多行歌前信息（作词/作曲/编曲/...）和第一句歌词造成的翻译匹配错误
-        let lyric_rev_iter = lyrics.lyric.iter().rev();
-        // 翻译部分的功能较复杂，不用迭代器实现
-        let t_lyric = &lyrics.tlyric;
-        let mut t_lyric_rev_pointer: usize;
-        let mut have_t_lyric = !t_lyric.is_empty();
-        t_lyric_rev_pointer = if have_t_lyric {
-            lyrics.tlyric.len() - 1
-        } else {
-            0
-        };
-        //
-        for lyric in lyric_rev_iter {
-            if lyric.len() >= 10 && timestamp_re.is_match(lyric) {
-                let time = (lyric[1..3].parse::<u64>().unwrap() * 60
-                    + lyric[4..6].parse::<u64>().unwrap())
-                    * 1000
-                    + lyric[7..9].parse::<u64>().unwrap_or(0) * 10;
-
-                let mut lyric_line = timestamp_re.replace_all(lyric, "").to_string();
-                lyric_line = lyric_line.trim_end_matches("\t").to_string(); // 部分句尾存在\t字符干扰渲染
-                lyric_line.push('\n');
-
-                // 翻译位置先填 None
-                lyrics_with_timestamp.push((time, (lyric_line, None)));
-            }
-            if have_t_lyric {
-                if let Some(t_lyric) = t_lyric.get(t_lyric_rev_pointer) {
-                    if t_lyric.len() >= 10
-                        && lyric.len() >= 10
-                        && t_lyric.starts_with(&lyric[0..10])
-                    {
-                        let mut t_lyric_line = timestamp_re.replace_all(t_lyric, "").to_string();
-                        t_lyric_line = t_lyric_line.trim_end_matches("\t").to_string(); // 部分句尾存在\t字符干扰渲染
-                        t_lyric_line.push('\n');
-
-                        // 更新对应的翻译
-                        if let Some(last) = lyrics_with_timestamp.as_mut_slice().last_mut() {
-                            *last = (last.0.clone(), (last.1 .0.clone(), Some(t_lyric_line)));
-                        }
+    /// 保存歌曲的歌词时间偏移
+    pub fn set_lyric_offset(&self, music_id: u64, offset: i64) -> Result<()> {
+        self.lyric_store.set_offset(music_id, offset)
+    }
 
-                        // 只有当翻译匹配到时间戳相同的歌词时指针才移动
-                        if t_lyric_rev_pointer > 0 {
-                            t_lyric_rev_pointer -= 1;
-                        } else {
-                            // t_lyric_rev_pointer == 0
-                            // 翻译已经遍历到头
-                            have_t_lyric = false;
-                        }
-                    }
-                }
-            }
-        }
+    /// 歌曲的响度（播放时分析），未分析过时返回 None
+    pub fn song_loudness(&self, music_id: u64) -> Option<Loudness> {
+        self.loudness_store.track(music_id)
+    }
+
+    /// 由已分析过的歌曲估算的专辑响度
+    pub fn album_loudness(&self, album_id: u64) -> Option<Loudness> {
+        self.loudness_store.album(album_id)
+    }
+
+    /// 保存歌曲的响度
+    pub fn set_song_loudness(&self, si: &SongInfo, loudness: Loudness) -> Result<()> {
+        self.loudness_store.save(si.id, si.album_id, loudness)
+    }
 
-        // 倒转恢复顺序
-        lyrics_with_timestamp.reverse();
+    /// 解析歌词、翻译、音译和逐字歌词，按时间戳匹配
+    fn encode_lyric(lyrics: &Lyrics) -> Vec<LyricLine> {
+        let lyric = Lrc::parse(&lyrics.lyric.join("\n"));
+        let translation = Lrc::parse(&lyrics.tlyric.join("\n"));
+        let romanization = Lrc::parse(&lyrics.romalrc.join("\n"));
+        let yrc = parse_yrc(&lyrics.yrc.join("\n"));
 
-        lyrics_with_timestamp
+        attach_yrc(lyric.merge_layers(&translation, &romanization), &yrc)
     }
 
     /// 查询歌词
@@ -498,14 +478,25 @@ impl NcmApi {
         params.insert("id", &id[..]);
         params.insert("lv", "-1");
         params.insert("tv", "-1");
+        params.insert("kv", "-1");
+        params.insert("yv", "-1");
+        params.insert("rv", "-1");
         params.insert("csrf_token", &csrf_token);
 
         // let result = self
-        //     .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+        //     .request(Method::Post, path, params, CryptoApi::Weapi, "", true, Retry::Idempotent)
         //     .await?;
 
         let result = self
-            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .request(
+                Method::Post,
+                path,
+                params,
+                CryptoApi::Weapi,
+                "",
+                true,
+                Retry::Idempotent,
+            )
             .await?;
 
         to_lyric(result)
@@ -514,25 +505,12 @@ impl NcmApi {
 
 /// 音乐信息获取 API
 impl NcmApi {
-    pub async fn get_user_favorite_songlist(&mut self) -> Result<()> {
-        // 解析命令行参数
-        let matches = Command::new("xxx")
-            .arg(Arg::new("playList")
-                .long("playList")
-                .value_parser(clap::value_parser!(i32))  // 使用 value_parser 来解析 i32 类型
-                .required(true)
-                .help("The play list identifier"))
-            .get_matches();
-
-        // 获取传入的 playList 参数并转换为 u16
-        let play_list = *matches.get_one::<i32>("playList").unwrap() as u16;
-
+    /// 获取登录用户的歌单并保存
+    /// songlist_offset: 歌单在用户歌单列表中的位置，0 为“我喜欢的音乐”
+    pub async fn get_user_favorite_songlist(&mut self, songlist_offset: u16) -> Result<()> {
         match &self.login_info {
             Some(login_info) => {
-                let user_id = login_info.uid.clone();  // 获取登录用户的 id
-
-                // 调用 user_song_list 方法时使用 u16 类型的参数
-                match self.user_song_list(user_id, play_list, 1).await {
+                match self.user_song_list(login_info.uid, songlist_offset, 1).await {
                     Ok(user_songlists) => {
                         if !user_songlists.is_empty() {
                             self.user_favorite_songlist_name = Some(user_songlists[0].name.clone());
@@ -541,13 +519,13 @@ impl NcmApi {
 
                             Ok(())
                         } else {
-                            Err(anyhow!("user has no songlist."))
+                            Err(NcmError::Other(String::from("user has no songlist.")))
                         }
                     }
                     Err(err) => Err(err),
                 }
             }
-            None => Err(anyhow!("you have to login first.")),
+            None => Err(NcmError::AuthRequired),
         }
     }
 
@@ -565,165 +543,895 @@ impl NcmApi {
         params.insert("offset", offset.as_str());
         params.insert("limit", limit.as_str());
         let result = self
-            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .request(
+                Method::Post,
+                path,
+                params,
+                CryptoApi::Weapi,
+                "",
+                true,
+                Retry::Idempotent,
+            )
             .await?;
         to_song_list(result, Parse::Usl)
     }
-}
 
-/// 待重构
-impl NcmApi {
-    /// 用户音乐id列表
-    /// uid: 用户id
-    pub async fn user_song_id_list(&self, uid: u64) -> Result<Vec<u64>> {
-        let path = "/weapi/song/like/get";
+    /// 专辑详情
+    /// album_id: 专辑 id
+    pub async fn album_detail(&self, album_id: u64) -> Result<AlbumDetail> {
+        let path = format!("/weapi/v1/album/{}", album_id);
+        let result = self
+            .request(
+                Method::Post,
+                &path,
+                HashMap::new(),
+                CryptoApi::Weapi,
+                "",
+                true,
+                Retry::Idempotent,
+            )
+            .await?;
+        to_album_detail(&serde_json::from_str(&result)?)
+    }
+
+    /// 专辑动态信息（收藏数、评论数、是否已收藏）
+    /// album_id: 专辑 id
+    pub async fn album_detail_dynamic(&self, album_id: u64) -> Result<AlbumDetailDynamic> {
+        let path = "/weapi/album/detail/dynamic";
         let mut params = HashMap::new();
-        let uid = uid.to_string();
-        params.insert("uid", uid.as_str());
+        let album_id = album_id.to_string();
+        params.insert("id", album_id.as_str());
         let result = self
-            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .request(
+                Method::Post,
+                path,
+                params,
+                CryptoApi::Weapi,
+                "",
+                true,
+                Retry::Idempotent,
+            )
             .await?;
-        to_song_id_list(result)
+        to_album_detail_dynamic(result)
     }
 
-    /// 用户收藏专辑列表
+    /// 歌手详情
+    /// singer_id: 歌手 id
+    pub async fn singer_detail(&self, singer_id: u64) -> Result<SingerDetail> {
+        let path = "/weapi/artist/head/info/get";
+        let mut params = HashMap::new();
+        let singer_id = singer_id.to_string();
+        params.insert("id", singer_id.as_str());
+        let result = self
+            .request(
+                Method::Post,
+                path,
+                params,
+                CryptoApi::Weapi,
+                "",
+                true,
+                Retry::Idempotent,
+            )
+            .await?;
+        to_singer_detail(result)
+    }
+
+    /// 歌手热门单曲
+    /// singer_id: 歌手 id
+    pub async fn singer_songs(&self, singer_id: u64) -> Result<Vec<SongInfo>> {
+        let path = format!("/weapi/v1/artist/{}", singer_id);
+        let result = self
+            .request(
+                Method::Post,
+                &path,
+                HashMap::new(),
+                CryptoApi::Weapi,
+                "",
+                true,
+                Retry::Idempotent,
+            )
+            .await?;
+        to_song_info(result, Parse::Singer)
+    }
+
+    /// 歌手全部单曲（按热度排序）
+    /// singer_id: 歌手 id
     /// offset: 列表起点号
     /// limit: 列表长度
-    pub async fn album_sublist(&self, offset: u16, limit: u16) -> Result<Vec<SongList>> {
-        let path = "/weapi/album/sublist";
+    pub async fn singer_all_songs(
+        &self,
+        singer_id: u64,
+        offset: u16,
+        limit: u16,
+    ) -> Result<Vec<SongInfo>> {
+        let path = "/weapi/v1/artist/songs";
         let mut params = HashMap::new();
+        let singer_id = singer_id.to_string();
         let offset = offset.to_string();
         let limit = limit.to_string();
-        let total = true.to_string();
-        params.insert("total", total.as_str());
+        params.insert("id", singer_id.as_str());
+        params.insert("private_cloud", "true");
+        params.insert("work_type", "1");
+        params.insert("order", "hot");
         params.insert("offset", offset.as_str());
         params.insert("limit", limit.as_str());
         let result = self
-            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .request(
+                Method::Post,
+                path,
+                params,
+                CryptoApi::Weapi,
+                "",
+                true,
+                Retry::Idempotent,
+            )
             .await?;
-        to_song_list(result, Parse::LikeAlbum)
+        to_song_info(result, Parse::SingerSongs)
     }
 
-    /// 歌单详情
-    /// songlist_id: 歌单 id
-    pub async fn song_list_detail(&self, songlist_id: u64) -> Result<PlayListDetail> {
-        let csrf_token = self.csrf.lock().await.borrow().to_owned();
-        let path = "/weapi/v6/playlist/detail";
+    /// 歌手专辑
+    /// singer_id: 歌手 id
+    /// offset: 列表起点号
+    /// limit: 列表长度
+    pub async fn singer_albums(
+        &self,
+        singer_id: u64,
+        offset: u16,
+        limit: u16,
+    ) -> Result<Vec<SongList>> {
+        let path = format!("/weapi/artist/albums/{}", singer_id);
         let mut params = HashMap::new();
-        let songlist_id = songlist_id.to_string();
-        params.insert("id", songlist_id.as_str());
-        params.insert("offset", "0");
+        let offset = offset.to_string();
+        let limit = limit.to_string();
+        params.insert("offset", offset.as_str());
+        params.insert("limit", limit.as_str());
         params.insert("total", "true");
-        params.insert("limit", "1000");
-        params.insert("n", "1000");
-        params.insert("csrf_token", &csrf_token);
         let result = self
-            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .request(
+                Method::Post,
+                &path,
+                params,
+                CryptoApi::Weapi,
+                "",
+                true,
+                Retry::Idempotent,
+            )
             .await?;
-        to_mix_detail(&serde_json::from_str(&result)?)
+        to_song_list(result, Parse::SingerAlbum)
     }
 
-    /// 歌曲详情
-    /// ids: 歌曲 id 列表
-    pub async fn songs_detail(&self, ids: &[u64]) -> Result<Vec<SongInfo>> {
-        let path = "/weapi/v3/song/detail";
-        let mut params = HashMap::new();
-        let c = ids
-            .iter()
-            .map(|i| format!("{{\\\"id\\\":\\\"{}\\\"}}", i))
-            .collect::<Vec<String>>()
-            .join(",");
-        let c = format!("[{}]", c);
-        params.insert("c", &c[..]);
+    /// 官方榜单列表
+    pub async fn toplist(&self) -> Result<Vec<TopList>> {
+        let path = "/weapi/toplist";
         let result = self
-            .request(Method::Post, path, params, CryptoApi::Weapi, "", true)
+            .request(
+                Method::Post,
+                path,
+                HashMap::new(),
+                CryptoApi::Weapi,
+                "",
+                true,
+                Retry::Idempotent,
+            )
             .await?;
-        to_song_info(result, Parse::Usl)
+        to_toplist(result)
     }
 
-    /// 从网络下载图片
-    /// url: 网址
-    /// path: 本地保存路径(包含文件名)
-    /// width: 宽度
-    /// high: 高度
-    #[allow(unused)]
-    pub async fn download_img<I>(&self, url: I, path: PathBuf, width: u16, high: u16) -> Result<()>
-    where
-        I: Into<String>,
-    {
-        if !path.exists() {
-            let url = url.into();
-            let image_url = format!("{}?param={}y{}", url, width, high);
-
-            let mut response = self.client.get_async(image_url).await?;
-            if response.status().is_success() {
-                let mut buf = vec![];
-                response.copy_to(&mut buf).await?;
-                fs::write(&path, buf)?;
-            }
-        }
-        Ok(())
+    /// 榜单详情（榜单即歌单）
+    /// toplist_id: 榜单 id
+    pub async fn toplist_detail(&self, toplist_id: u64) -> Result<PlayListDetail> {
+        self.song_list_detail(toplist_id).await
     }
 
-    /// 从网络下载音乐
-    /// url: 网址
-    /// path: 本地保存路径(包含文件名)
-    #[allow(unused)]
-    pub async fn download_song<I>(&self, url: I, path: PathBuf) -> Result<()>
-    where
-        I: Into<String>,
-    {
-        if !path.exists() {
-            let mut response = self.client.get_async(url.into()).await?;
-            if response.status().is_success() {
-                let mut buf = vec![];
-                response.copy_to(&mut buf).await?;
-                fs::write(&path, buf)?;
-            }
-        }
-        Ok(())
+    /// 首页轮播
+    pub async fn banners(&self) -> Result<Vec<BannersInfo>> {
+        let path = "/weapi/v2/banner/get";
+        let mut params = HashMap::new();
+        params.insert("clientType", "pc");
+        let result = self
+            .request(
+                Method::Post,
+                path,
+                params,
+                CryptoApi::Weapi,
+                "",
+                true,
+                Retry::Idempotent,
+            )
+            .await?;
+        to_banners_info(result)
     }
 }
 
-/// 底层网络接口封装
+/// 推荐与私人 FM API
 impl NcmApi {
-    /// 设置使用代理
-    /// proxy: 代理地址，支持以下协议
-    ///   - http: Proxy. Default when no scheme is specified.
-    ///   - https: HTTPS Proxy. (Added in 7.52.0 for OpenSSL, GnuTLS and NSS)
-    ///   - socks4: SOCKS4 Proxy.
-    ///   - socks4a: SOCKS4a Proxy. Proxy resolves URL hostname.
-    ///   - socks5: SOCKS5 Proxy.
-    ///   - socks5h: SOCKS5 Proxy. Proxy resolves URL hostname.
-    pub fn set_proxy(&mut self, proxy: &str) -> Result<()> {
-        if let Some(cookie_jar) = self.client.cookie_jar() {
-            let client = HttpClient::builder()
-                .timeout(Duration::from_secs(TIMEOUT))
-                .proxy(Some(proxy.parse()?))
-                .cookies()
-                .cookie_jar(cookie_jar.to_owned())
-                .build()
-                .expect("初始化网络请求失败!");
-            self.client = client;
-        } else {
-            let client = HttpClient::builder()
-                .timeout(Duration::from_secs(TIMEOUT))
-                .proxy(Some(proxy.parse()?))
-                .cookies()
-                .build()
-                .expect("初始化网络请求失败!");
-            self.client = client;
+    /// 每日推荐歌曲，需要登录
+    pub async fn recommend_songs(&self) -> Result<Vec<SongInfo>> {
+        if !self.is_login {
+            return Err(NcmError::AuthRequired);
         }
-        Ok(())
-    }
 
-    /// 发送请求
-    /// method: 请求方法
+        let path = "/weapi/v2/discovery/recommend/songs";
+        let mut params = HashMap::new();
+        params.insert("total", "true");
+        let result = self
+            .request(
+                Method::Post,
+                path,
+                params,
+                CryptoApi::Weapi,
+                "",
+                true,
+                Retry::Idempotent,
+            )
+            .await?;
+        to_song_info(result, Parse::Rmds)
+    }
+
+    /// 每日推荐歌单，需要登录
+    pub async fn recommend_songlists(&self) -> Result<Vec<SongList>> {
+        if !self.is_login {
+            return Err(NcmError::AuthRequired);
+        }
+
+        let path = "/weapi/v1/discovery/recommend/resource";
+        let result = self
+            .request(
+                Method::Post,
+                path,
+                HashMap::new(),
+                CryptoApi::Weapi,
+                "",
+                true,
+                Retry::Idempotent,
+            )
+            .await?;
+        to_song_list(result, Parse::Rmd)
+    }
+
+    /// 私人 FM，每次返回少量歌曲，需要登录
+    pub async fn personal_fm(&self) -> Result<Vec<SongInfo>> {
+        if !self.is_login {
+            return Err(NcmError::AuthRequired);
+        }
+
+        let path = "/weapi/v1/radio/get";
+        let result = self
+            .request(
+                Method::Post,
+                path,
+                HashMap::new(),
+                CryptoApi::Weapi,
+                "",
+                true,
+                Retry::Idempotent,
+            )
+            .await?;
+        to_song_info(result, Parse::Rmd)
+    }
+
+    /// 心动模式（智能播放）歌曲，需要登录
+    /// song_id: 起始歌曲 id
+    /// songlist_id: 歌单 id，一般为“我喜欢的音乐”
+    pub async fn intelligence_songs(
+        &self,
+        song_id: u64,
+        songlist_id: u64,
+    ) -> Result<Vec<SongInfo>> {
+        if !self.is_login {
+            return Err(NcmError::AuthRequired);
+        }
+
+        let path = "/weapi/playmode/intelligence/list";
+        let mut params = HashMap::new();
+        let song_id = song_id.to_string();
+        let songlist_id = songlist_id.to_string();
+        params.insert("songId", song_id.as_str());
+        params.insert("type", "fromPlayOne");
+        params.insert("playlistId", songlist_id.as_str());
+        params.insert("startMusicId", song_id.as_str());
+        params.insert("count", "1");
+        let result = self
+            .request(
+                Method::Post,
+                path,
+                params,
+                CryptoApi::Weapi,
+                "",
+                true,
+                Retry::Idempotent,
+            )
+            .await?;
+        to_song_info(result, Parse::Intelligence)
+    }
+
+    /// 私人 FM 不喜欢（垃圾桶），之后不再推荐该歌曲
+    /// song_id: 歌曲 id
+    pub async fn fm_trash(&self, song_id: u64) -> Result<()> {
+        if !self.is_login {
+            return Err(NcmError::AuthRequired);
+        }
+
+        let path = "/weapi/radio/trash/add";
+        let mut params = HashMap::new();
+        let song_id = song_id.to_string();
+        params.insert("songId", song_id.as_str());
+        params.insert("alg", "RT");
+        params.insert("time", "25");
+        let result = self
+            .request(
+                Method::Post,
+                path,
+                params,
+                CryptoApi::Weapi,
+                "",
+                true,
+                Retry::Never,
+            )
+            .await?;
+        check_msg(to_msg(result)?)
+    }
+
+    /// “我喜欢的音乐”歌单 id，即用户歌单列表中的第一个歌单
+    pub async fn user_liked_songlist_id(&self) -> Result<u64> {
+        match &self.login_info {
+            Some(login_info) => self
+                .user_song_list(login_info.uid, 0, 1)
+                .await?
+                .first()
+                .map(|songlist| songlist.id)
+                .ok_or_else(|| NcmError::Other(String::from("user has no songlist."))),
+            None => Err(NcmError::AuthRequired),
+        }
+    }
+}
+
+/// 搜索 API
+impl NcmApi {
+    /// 搜索
+    /// keywords: 关键词
+    /// search_type: 单曲(1)，专辑(10)，歌手(100)，歌单(1000)
+    /// offset: 起始点
+    /// limit: 数量
+    async fn search(
+        &self,
+        keywords: &str,
+        search_type: u32,
+        offset: u16,
+        limit: u16,
+    ) -> Result<String> {
+        let path = "/weapi/search/get";
+        let mut params = HashMap::new();
+        let search_type = search_type.to_string();
+        let offset = offset.to_string();
+        let limit = limit.to_string();
+        params.insert("s", keywords);
+        params.insert("type", search_type.as_str());
+        params.insert("offset", offset.as_str());
+        params.insert("limit", limit.as_str());
+        self.request(
+            Method::Post,
+            path,
+            params,
+            CryptoApi::Weapi,
+            "",
+            true,
+            Retry::Idempotent,
+        )
+        .await
+    }
+
+    /// 搜索单曲
+    pub async fn search_song(
+        &self,
+        keywords: &str,
+        offset: u16,
+        limit: u16,
+    ) -> Result<Vec<SongInfo>> {
+        let result = self.search(keywords, 1, offset, limit).await?;
+        to_song_info(result, Parse::Search)
+    }
+
+    /// 搜索专辑
+    pub async fn search_album(
+        &self,
+        keywords: &str,
+        offset: u16,
+        limit: u16,
+    ) -> Result<Vec<SongList>> {
+        let result = self.search(keywords, 10, offset, limit).await?;
+        to_song_list(result, Parse::SearchAlbum)
+    }
+
+    /// 搜索歌手
+    pub async fn search_singer(
+        &self,
+        keywords: &str,
+        offset: u16,
+        limit: u16,
+    ) -> Result<Vec<SingerInfo>> {
+        let result = self.search(keywords, 100, offset, limit).await?;
+        to_singer_info(result)
+    }
+
+    /// 搜索歌单
+    pub async fn search_songlist(
+        &self,
+        keywords: &str,
+        offset: u16,
+        limit: u16,
+    ) -> Result<Vec<SongList>> {
+        let result = self.search(keywords, 1000, offset, limit).await?;
+        to_song_list(result, Parse::Search)
+    }
+}
+
+/// 喜欢与收藏 API
+impl NcmApi {
+    /// 重新获取用户喜欢的歌曲 id，失败时保留原有记录
+    pub async fn refresh_user_liked_song_ids(&mut self) {
+        if let Some(login_info) = &self.login_info {
+            match self.user_song_id_list(login_info.uid).await {
+                Ok(ids) => self.user_liked_song_ids = ids.into_iter().collect(),
+                Err(err) => error!("{:?}", err),
+            }
+        }
+    }
+
+    /// 喜欢/取消喜欢歌曲，成功后同步更新本地记录
+    /// song_id: 歌曲 id
+    /// like: true 为喜欢，false 为取消喜欢
+    pub async fn like_song(&mut self, song_id: u64, like: bool) -> Result<()> {
+        if !self.is_login {
+            return Err(NcmError::AuthRequired);
+        }
+
+        let path = "/weapi/radio/like";
+        let mut params = HashMap::new();
+        let song_id_str = song_id.to_string();
+        let like_str = like.to_string();
+        params.insert("alg", "itembased");
+        params.insert("trackId", song_id_str.as_str());
+        params.insert("like", like_str.as_str());
+        params.insert("time", "3");
+        let result = self
+            .request(
+                Method::Post,
+                path,
+                params,
+                CryptoApi::Weapi,
+                "",
+                true,
+                Retry::Never,
+            )
+            .await?;
+        check_msg(to_msg(result)?)?;
+
+        if like {
+            self.user_liked_song_ids.insert(song_id);
+        } else {
+            self.user_liked_song_ids.remove(&song_id);
+        }
+
+        Ok(())
+    }
+
+    /// 收藏/取消收藏歌单
+    /// songlist_id: 歌单 id
+    /// subscribe: true 为收藏，false 为取消收藏
+    pub async fn subscribe_playlist(&self, songlist_id: u64, subscribe: bool) -> Result<()> {
+        let path = if subscribe {
+            "/weapi/playlist/subscribe"
+        } else {
+            "/weapi/playlist/unsubscribe"
+        };
+        let mut params = HashMap::new();
+        let songlist_id = songlist_id.to_string();
+        params.insert("id", songlist_id.as_str());
+        let result = self
+            .request(
+                Method::Post,
+                path,
+                params,
+                CryptoApi::Weapi,
+                "",
+                true,
+                Retry::Never,
+            )
+            .await?;
+        check_msg(to_msg(result)?)
+    }
+
+    /// 收藏/取消收藏专辑
+    /// album_id: 专辑 id
+    /// subscribe: true 为收藏，false 为取消收藏
+    pub async fn subscribe_album(&self, album_id: u64, subscribe: bool) -> Result<()> {
+        let path = if subscribe {
+            "/weapi/album/sub"
+        } else {
+            "/weapi/album/unsub"
+        };
+        let mut params = HashMap::new();
+        let album_id = album_id.to_string();
+        params.insert("id", album_id.as_str());
+        let result = self
+            .request(
+                Method::Post,
+                path,
+                params,
+                CryptoApi::Weapi,
+                "",
+                true,
+                Retry::Never,
+            )
+            .await?;
+        check_msg(to_msg(result)?)
+    }
+}
+
+/// 云盘 API
+impl NcmApi {
+    /// 云盘歌曲
+    /// offset: 列表起点号
+    /// limit: 列表长度
+    pub async fn user_cloud(&self, offset: u16, limit: u16) -> Result<Vec<SongInfo>> {
+        if !self.is_login {
+            return Err(NcmError::AuthRequired);
+        }
+
+        let path = "/weapi/v1/cloud/get";
+        let mut params = HashMap::new();
+        let offset = offset.to_string();
+        let limit = limit.to_string();
+        params.insert("offset", offset.as_str());
+        params.insert("limit", limit.as_str());
+        let result = self
+            .request(
+                Method::Post,
+                path,
+                params,
+                CryptoApi::Weapi,
+                "",
+                true,
+                Retry::Idempotent,
+            )
+            .await?;
+        to_song_info(result, Parse::Ucd)
+    }
+
+    /// 删除云盘歌曲
+    /// song_id: 云盘歌曲 id
+    pub async fn cloud_delete(&self, song_id: u64) -> Result<()> {
+        let path = "/weapi/cloud/del";
+        let mut params = HashMap::new();
+        let song_ids = serde_json::to_string(&[song_id])?;
+        params.insert("songIds", song_ids.as_str());
+        let result = self
+            .request(
+                Method::Post,
+                path,
+                params,
+                CryptoApi::Weapi,
+                "",
+                true,
+                Retry::Never,
+            )
+            .await?;
+        check_msg(to_msg(result)?)
+    }
+
+    /// 上传本地音频文件到云盘
+    /// file_path: 本地文件路径
+    /// on_progress: 上传进度回调，云端已有相同文件时直接回调完成
+    pub async fn cloud_upload<F>(&self, file_path: &Path, mut on_progress: F) -> Result<()>
+    where
+        F: FnMut(UploadProgress),
+    {
+        if !self.is_login {
+            return Err(NcmError::AuthRequired);
+        }
+
+        // 分块计算 md5，上传时再按分片读取，不把整个文件读入内存
+        let mut file = fs::File::open(file_path)?;
+        let mut hasher = openssl::hash::Hasher::new(openssl::hash::MessageDigest::md5())?;
+        let size = io::copy(&mut file, &mut hasher)?;
+        let md5 = hex::encode(hasher.finish()?);
+        let file_name = file_path
+            .file_name()
+            .and_then(|file_name| file_name.to_str())
+            .ok_or_else(|| NcmError::Other(format!("invalid file name: {:?}", file_path)))?;
+        let song_name = file_path
+            .file_stem()
+            .and_then(|file_stem| file_stem.to_str())
+            .unwrap_or(file_name);
+        let ext = file_path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("mp3")
+            .to_ascii_lowercase();
+        let length = size.to_string();
+
+        // 检查云端是否已有相同文件
+        let path = "/weapi/cloud/upload/check";
+        let mut params = HashMap::new();
+        params.insert("bitrate", "999000");
+        params.insert("ext", "");
+        params.insert("length", length.as_str());
+        params.insert("md5", md5.as_str());
+        params.insert("songId", "0");
+        params.insert("version", "1");
+        let result = self
+            .request(
+                Method::Post,
+                path,
+                params,
+                CryptoApi::Weapi,
+                "",
+                true,
+                Retry::Never,
+            )
+            .await?;
+        let upload_check = to_cloud_upload_check(result)?;
+
+        // 申请上传凭证
+        let path = "/weapi/nos/token/alloc";
+        let mut params = HashMap::new();
+        params.insert("bucket", CLOUD_UPLOAD_BUCKET);
+        params.insert("ext", ext.as_str());
+        params.insert("filename", song_name);
+        params.insert("local", "false");
+        params.insert("nos_product", "3");
+        params.insert("type", "audio");
+        params.insert("md5", md5.as_str());
+        let result = self
+            .request(
+                Method::Post,
+                path,
+                params,
+                CryptoApi::Weapi,
+                "",
+                true,
+                Retry::Never,
+            )
+            .await?;
+        let nos_token = to_nos_token(result)?;
+
+        if upload_check.need_upload {
+            ChunkedUploader::new(Arc::clone(&self.transport), CLOUD_UPLOAD_HOST)
+                .upload(
+                    CLOUD_UPLOAD_BUCKET,
+                    &nos_token.object_key,
+                    &nos_token.token,
+                    &mut file,
+                    &mut on_progress,
+                )
+                .await?;
+        } else {
+            on_progress(UploadProgress {
+                uploaded: size,
+                total: size,
+            });
+        }
+
+        // 提交歌曲信息
+        let path = "/weapi/upload/cloud/info/v2";
+        let mut params = HashMap::new();
+        let resource_id = nos_token.resource_id.to_string();
+        params.insert("md5", md5.as_str());
+        params.insert("songid", upload_check.song_id.as_str());
+        params.insert("filename", file_name);
+        params.insert("song", song_name);
+        params.insert("album", "未知专辑");
+        params.insert("artist", "未知艺术家");
+        params.insert("bitrate", "999000");
+        params.insert("resourceId", resource_id.as_str());
+        let result = self
+            .request(
+                Method::Post,
+                path,
+                params,
+                CryptoApi::Weapi,
+                "",
+                true,
+                Retry::Never,
+            )
+            .await?;
+        let song_id = to_cloud_upload_info(result)?;
+
+        // 发布到云盘
+        let path = "/weapi/cloud/pub/v2";
+        let mut params = HashMap::new();
+        params.insert("songid", song_id.as_str());
+        let result = self
+            .request(
+                Method::Post,
+                path,
+                params,
+                CryptoApi::Weapi,
+                "",
+                true,
+                Retry::Never,
+            )
+            .await?;
+        check_msg(to_msg(result)?)
+    }
+}
+
+/// 待重构
+impl NcmApi {
+    /// 用户音乐id列表
+    /// uid: 用户id
+    pub async fn user_song_id_list(&self, uid: u64) -> Result<Vec<u64>> {
+        let path = "/weapi/song/like/get";
+        let mut params = HashMap::new();
+        let uid = uid.to_string();
+        params.insert("uid", uid.as_str());
+        let result = self
+            .request(
+                Method::Post,
+                path,
+                params,
+                CryptoApi::Weapi,
+                "",
+                true,
+                Retry::Idempotent,
+            )
+            .await?;
+        to_song_id_list(result)
+    }
+
+    /// 用户收藏专辑列表
+    /// offset: 列表起点号
+    /// limit: 列表长度
+    pub async fn album_sublist(&self, offset: u16, limit: u16) -> Result<Vec<SongList>> {
+        let path = "/weapi/album/sublist";
+        let mut params = HashMap::new();
+        let offset = offset.to_string();
+        let limit = limit.to_string();
+        let total = true.to_string();
+        params.insert("total", total.as_str());
+        params.insert("offset", offset.as_str());
+        params.insert("limit", limit.as_str());
+        let result = self
+            .request(
+                Method::Post,
+                path,
+                params,
+                CryptoApi::Weapi,
+                "",
+                true,
+                Retry::Idempotent,
+            )
+            .await?;
+        to_song_list(result, Parse::LikeAlbum)
+    }
+
+    /// 歌单详情
+    /// songlist_id: 歌单 id
+    pub async fn song_list_detail(&self, songlist_id: u64) -> Result<PlayListDetail> {
+        let csrf_token = self.csrf.lock().await.borrow().to_owned();
+        let path = "/weapi/v6/playlist/detail";
+        let mut params = HashMap::new();
+        let songlist_id = songlist_id.to_string();
+        params.insert("id", songlist_id.as_str());
+        params.insert("offset", "0");
+        params.insert("total", "true");
+        params.insert("limit", "1000");
+        params.insert("n", "1000");
+        params.insert("csrf_token", &csrf_token);
+        let result = self
+            .request(
+                Method::Post,
+                path,
+                params,
+                CryptoApi::Weapi,
+                "",
+                true,
+                Retry::Idempotent,
+            )
+            .await?;
+        to_mix_detail(&serde_json::from_str(&result)?)
+    }
+
+    /// 歌曲详情
+    /// ids: 歌曲 id 列表
+    pub async fn songs_detail(&self, ids: &[u64]) -> Result<Vec<SongInfo>> {
+        let path = "/weapi/v3/song/detail";
+        let mut params = HashMap::new();
+        let c = ids
+            .iter()
+            .map(|i| format!("{{\\\"id\\\":\\\"{}\\\"}}", i))
+            .collect::<Vec<String>>()
+            .join(",");
+        let c = format!("[{}]", c);
+        params.insert("c", &c[..]);
+        let result = self
+            .request(
+                Method::Post,
+                path,
+                params,
+                CryptoApi::Weapi,
+                "",
+                true,
+                Retry::Idempotent,
+            )
+            .await?;
+        to_song_info(result, Parse::Usl)
+    }
+
+    /// 从网络下载图片
+    /// url: 网址
+    /// path: 本地保存路径(包含文件名)
+    /// width: 宽度
+    /// high: 高度
+    #[allow(unused)]
+    pub async fn download_img<I>(&self, url: I, path: PathBuf, width: u16, high: u16) -> Result<()>
+    where
+        I: Into<String>,
+    {
+        if !path.exists() {
+            let url = url.into();
+            let image_url = format!("{}?param={}y{}", url, width, high);
+
+            let response = self
+                .transport
+                .send(Request::get(image_url).body(Vec::new())?)
+                .await?;
+            if response.is_success() {
+                fs::write(&path, response.body)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 从网络下载音乐
+    /// url: 网址
+    /// path: 本地保存路径(包含文件名)
+    #[allow(unused)]
+    pub async fn download_song<I>(&self, url: I, path: PathBuf) -> Result<()>
+    where
+        I: Into<String>,
+    {
+        if !path.exists() {
+            let response = self
+                .transport
+                .send(Request::get(url.into()).body(Vec::new())?)
+                .await?;
+            if response.is_success() {
+                fs::write(&path, response.body)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 底层网络接口封装
+impl NcmApi {
+    /// 设置使用代理
+    /// proxy: 代理地址，支持以下协议
+    ///   - http: Proxy. Default when no scheme is specified.
+    ///   - https: HTTPS Proxy. (Added in 7.52.0 for OpenSSL, GnuTLS and NSS)
+    ///   - socks4: SOCKS4 Proxy.
+    ///   - socks4a: SOCKS4a Proxy. Proxy resolves URL hostname.
+    ///   - socks5: SOCKS5 Proxy.
+    ///   - socks5h: SOCKS5 Proxy. Proxy resolves URL hostname.
+    pub fn set_proxy(&mut self, proxy: &str) -> Result<()> {
+        let transport = IsahcTransport::new(self.cookie_jar().cloned(), Some(proxy))?;
+        self.transport = Arc::new(transport);
+        Ok(())
+    }
+
+    /// 发送请求
+    /// retry 为 Retry::Idempotent 时，网络错误、超时和服务端错误按 retry_policy 重试，每次重试切换到下一个接口地址
+    /// 会产生副作用的接口使用 Retry::Never，只请求一次
+    /// method: 请求方法
     /// path: 请求路径
     /// params: 请求参数
     /// cryptoapi: 请求加密方式
     /// ua: 要使用的 USER_AGENT_LIST
     /// append_csrf: 是否在路径中添加 csrf
+    /// retry: 失败后是否可以重试
+    #[allow(clippy::too_many_arguments)]
     async fn request(
         &self,
         method: Method,
@@ -732,6 +1440,7 @@ impl NcmApi {
         cryptoapi: CryptoApi,
         ua: &str,
         append_csrf: bool,
+        retry: Retry,
     ) -> Result<String> {
         let mut csrf = self.csrf.lock().await.borrow().to_owned();
         if csrf.is_empty() {
@@ -744,9 +1453,83 @@ impl NcmApi {
                 }
             }
         }
-        let mut url = format!("{}{}?csrf_token={}", BASE_URL, path, csrf);
+
+        let max_retries = match retry {
+            Retry::Idempotent => self.retry_policy.max_retries,
+            Retry::Never => 0,
+        };
+        // 完整 url 形式的路径（eapi）只有一个地址可用
+        let host_count = if path.starts_with("http") {
+            1
+        } else {
+            self.api_hosts.len()
+        };
+        let preferred_host = self.preferred_host.load(Ordering::Relaxed) % host_count;
+
+        let mut retries = 0;
+        loop {
+            let host_index = (preferred_host + retries as usize) % host_count;
+            let host = self.api_hosts[host_index].as_str();
+            let result = self
+                .request_once(
+                    method,
+                    host,
+                    path,
+                    params.clone(),
+                    cryptoapi,
+                    ua,
+                    append_csrf,
+                    &csrf,
+                )
+                .await;
+
+            match result {
+                Ok(text) => {
+                    if host_index != preferred_host {
+                        debug!("request {} succeeded on fallback host {}", path, host);
+                        self.preferred_host.store(host_index, Ordering::Relaxed);
+                    }
+                    return Ok(text);
+                }
+                Err(e) if retries < max_retries && is_retryable(&e) => {
+                    let backoff = self.retry_policy.backoff(retries);
+                    warn!(
+                        "request {} on {} failed (attempt {}/{}): {}, retry in {:?}",
+                        path,
+                        host,
+                        retries + 1,
+                        max_retries + 1,
+                        e,
+                        backoff
+                    );
+                    tokio::time::sleep(backoff).await;
+                    retries += 1;
+                }
+                Err(e) => {
+                    error!("request {} on {} failed: {}", path, host, e);
+                    return Err(e);
+                }
+            }
+        }
+    }
+
+    /// 向指定地址发送一次请求
+    /// host: 接口地址，path 为完整 url 时不使用
+    #[allow(clippy::too_many_arguments)]
+    async fn request_once(
+        &self,
+        method: Method,
+        host: &str,
+        path: &str,
+        params: HashMap<&str, &str>,
+        cryptoapi: CryptoApi,
+        ua: &str,
+        append_csrf: bool,
+        csrf: &str,
+    ) -> Result<String> {
+        let mut url = format!("{}{}?csrf_token={}", host, path, csrf);
         if !append_csrf {
-            url = format!("{}{}", BASE_URL, path);
+            url = format!("{}{}", host, path);
         }
         match method {
             Method::Post => {
@@ -766,12 +1549,12 @@ impl NcmApi {
                     }
                     CryptoApi::Weapi => {
                         let mut params = params;
-                        params.insert("csrf_token", &csrf);
+                        params.insert("csrf_token", csrf);
                         Crypto::weapi(&QueryParams::from_map(params).json())
                     }
                     CryptoApi::Eapi => {
                         let mut params = params;
-                        params.insert("csrf_token", &csrf);
+                        params.insert("csrf_token", csrf);
                         url = path.to_string();
                         Crypto::eapi(
                             "/api/song/enhance/player/url",
@@ -781,32 +1564,44 @@ impl NcmApi {
                 };
 
                 let request = Request::post(&url)
+                    .timeout(self.retry_policy.timeout)
                     .header("Cookie", "os=pc; appver=2.7.1.198277")
                     .header("Accept", "*/*")
                     .header("Accept-Language", "en-US,en;q=0.5")
                     .header("Connection", "keep-alive")
                     .header("Content-Type", "application/x-www-form-urlencoded")
-                    .header("Host", "music.163.com")
                     .header("Referer", "https://music.163.com")
                     .header("User-Agent", user_agent)
-                    .body(body)?;
-                let mut response = self
-                    .client
-                    .send_async(request)
-                    .await
-                    .map_err(|_| anyhow!("none"))?;
-                response.text().await.map_err(|_| anyhow!("none"))
+                    .body(body.into_bytes())?;
+                let response = self.transport.send(request).await?;
+                Self::response_text(response)
+            }
+            Method::Get => {
+                let request = Request::get(&url)
+                    .timeout(self.retry_policy.timeout)
+                    .body(Vec::new())?;
+                let response = self.transport.send(request).await?;
+                Self::response_text(response)
             }
-            Method::Get => self
-                .client
-                .get_async(&url)
-                .await
-                .map_err(|_| anyhow!("none"))?
-                .text()
-                .await
-                .map_err(|_| anyhow!("none")),
         }
     }
+
+    /// 读取响应内容，HTTP 状态码非 2xx 时返回错误
+    fn response_text(response: TransportResponse) -> Result<String> {
+        if !response.is_success() {
+            return Err(NcmError::Http(response.status));
+        }
+        String::from_utf8(response.body).map_err(|e| NcmError::Json(e.to_string()))
+    }
+}
+
+/// 接口返回非 200 时转换为错误
+fn check_msg(msg: Msg) -> Result<()> {
+    if msg.code == 200 {
+        Ok(())
+    } else {
+        Err(NcmError::from_code(msg.code as i64, msg.msg))
+    }
 }
 
 fn choose_user_agent(ua: &str) -> &str {
@@ -824,12 +1619,410 @@ fn choose_user_agent(ua: &str) -> &str {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use crate::mock_server::{unreachable_url, MockResponse, MockServer};
+    use std::env;
+    use std::time::Duration;
+
+    const OK_BODY: &str = r#"{"code":200}"#;
+
+    fn test_api(api_hosts: Vec<String>, max_retries: u32) -> NcmApi {
+        let mut api = NcmApi::new(env::temp_dir(), env::temp_dir(), env::temp_dir());
+        api.set_api_hosts(api_hosts);
+        api.set_retry_policy(RetryPolicy {
+            timeout: Duration::from_millis(300),
+            max_retries,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_millis(40),
+        });
+        api
+    }
 
-    // use super::*;
+    async fn post(api: &NcmApi, path: &str) -> Result<String> {
+        api.request(
+            Method::Post,
+            path,
+            HashMap::new(),
+            CryptoApi::Weapi,
+            "",
+            true,
+            Retry::Idempotent,
+        )
+        .await
+    }
 
-    // #[async_std::test]
-    // async fn test() {
-    //     let api = NcmApi::new();
-    //     assert!(api.banners().await.is_ok());
-    // }
+    fn reply(status: u16) -> MockResponse {
+        MockResponse::Reply(status, OK_BODY.to_string())
+    }
+
+    #[tokio::test]
+    async fn retry_server_error_then_succeed() {
+        let server = MockServer::start(vec![reply(502), reply(200)]);
+        let api = test_api(vec![server.url()], 2);
+
+        assert_eq!(post(&api, "/weapi/test").await.unwrap(), OK_BODY);
+        assert_eq!(
+            server.requests(),
+            vec!["/weapi/test?csrf_token=", "/weapi/test?csrf_token="]
+        );
+    }
+
+    #[tokio::test]
+    async fn give_up_after_max_retries() {
+        let server = MockServer::start(vec![reply(500), reply(500), reply(500)]);
+        let api = test_api(vec![server.url()], 2);
+
+        assert!(matches!(
+            post(&api, "/weapi/test").await,
+            Err(NcmError::Http(500))
+        ));
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn no_retry_for_client_error() {
+        let server = MockServer::start(vec![reply(404), reply(200)]);
+        let api = test_api(vec![server.url()], 2);
+
+        assert!(matches!(
+            post(&api, "/weapi/test").await,
+            Err(NcmError::Http(404))
+        ));
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn no_retry_for_side_effecting_call() {
+        let server = MockServer::start(vec![reply(500), reply(200)]);
+        let mut api = test_api(vec![server.url()], 2);
+        api.is_login = true;
+
+        assert!(matches!(
+            api.like_song(186016, true).await,
+            Err(NcmError::Http(500))
+        ));
+        assert_eq!(server.requests(), vec!["/weapi/radio/like?csrf_token="]);
+        assert!(!api.is_liked_song(186016));
+    }
+
+    #[tokio::test]
+    async fn retry_after_timeout() {
+        let server = MockServer::start(vec![
+            MockResponse::Delay(Duration::from_secs(1), 200, OK_BODY.to_string()),
+            reply(200),
+        ]);
+        let api = test_api(vec![server.url()], 1);
+
+        assert_eq!(post(&api, "/weapi/test").await.unwrap(), OK_BODY);
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn failover_to_next_host_and_keep_it() {
+        let server = MockServer::start(vec![reply(200), reply(200)]);
+        let api = test_api(vec![unreachable_url(), server.url()], 1);
+
+        assert_eq!(post(&api, "/weapi/test").await.unwrap(), OK_BODY);
+        // 之后的请求直接使用可用的地址，不再尝试无法连接的地址
+        assert_eq!(post(&api, "/weapi/test").await.unwrap(), OK_BODY);
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    fn fixture_api(transport: FixtureTransport, name: &str) -> (NcmApi, Arc<FixtureTransport>) {
+        // 每个测试使用单独的目录，避免读取到其他测试缓存的歌词
+        let dir = env::temp_dir().join(format!("ncm-api-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        let transport = Arc::new(transport);
+        let api = NcmApi::with_transport(transport.clone(), dir.clone(), dir.clone(), dir);
+        (api, transport)
+    }
+
+    /// 只填写 id 和歌名的歌曲，其余字段按需覆盖
+    fn song(id: u64, name: &str) -> SongInfo {
+        SongInfo {
+            id,
+            name: name.to_string(),
+            singer: String::new(),
+            singers: Vec::new(),
+            album: String::new(),
+            album_id: 0,
+            pic_url: String::new(),
+            duration: 0,
+            song_url: String::new(),
+            copyright: SongCopyright::Free,
+        }
+    }
+
+    #[tokio::test]
+    async fn login_with_qrcode() {
+        let transport = FixtureTransport::new()
+            .with_fixture(
+                "/weapi/login/qrcode/unikey",
+                include_str!("../fixtures/login_qrcode_unikey.json"),
+            )
+            .with_fixture(
+                "/weapi/login/qrcode/client/login",
+                include_str!("../fixtures/login_qrcode_waiting.json"),
+            )
+            .with_fixture(
+                "/weapi/login/qrcode/client/login",
+                include_str!("../fixtures/login_qrcode_authorized.json"),
+            );
+        let (api, _) = fixture_api(transport, "login-qrcode");
+
+        let (qr_url, unikey) = api.login_qr_create().await.unwrap();
+        assert_eq!(unikey, "9e4f6a4c-6a3b-4f53-8d1e-2c0f7b4e1a55");
+        assert_eq!(
+            qr_url,
+            "https://music.163.com/login?codekey=9e4f6a4c-6a3b-4f53-8d1e-2c0f7b4e1a55"
+        );
+
+        let msg = api.login_qr_check(unikey.clone()).await.unwrap();
+        assert_eq!((msg.code, msg.msg.as_str()), (801, "等待扫码"));
+        let msg = api.login_qr_check(unikey).await.unwrap();
+        assert_eq!((msg.code, msg.msg.as_str()), (803, "授权登陆成功"));
+    }
+
+    #[tokio::test]
+    async fn cookie_login_loads_favorite_songlist() {
+        let transport = FixtureTransport::new()
+            .with_fixture(
+                "/api/nuser/account/get",
+                include_str!("../fixtures/account_get.json"),
+            )
+            .with_fixture(
+                "/weapi/user/playlist",
+                include_str!("../fixtures/user_playlist.json"),
+            )
+            .with_fixture(
+                "/weapi/v6/playlist/detail",
+                include_str!("../fixtures/playlist_detail.json"),
+            )
+            .with_fixture(
+                "/weapi/song/like/get",
+                include_str!("../fixtures/song_like_get.json"),
+            );
+        let (mut api, transport) = fixture_api(transport, "cookie-login");
+
+        assert!(api.check_cookie_login(0).await.unwrap());
+        assert!(api.is_login());
+        let login_info = api.login_info().unwrap();
+        assert_eq!((login_info.uid, login_info.vip_type), (32953014, 11));
+        assert_eq!(login_info.nickname, "云村村民");
+
+        let (name, songs) = api.user_favorite_songlist();
+        assert_eq!(name.unwrap(), "云村村民喜欢的音乐");
+        assert_eq!(songs.unwrap().len(), 2);
+        assert!(api.is_liked_song(186016));
+        assert!(!api.is_liked_song(1));
+
+        assert_eq!(
+            transport.requests(),
+            vec![
+                "/api/nuser/account/get",
+                "/weapi/user/playlist",
+                "/weapi/v6/playlist/detail",
+                "/weapi/song/like/get",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn cookie_login_expired() {
+        let transport = FixtureTransport::new().with_fixture(
+            "/api/nuser/account/get",
+            include_str!("../fixtures/account_get_logged_out.json"),
+        );
+        let (mut api, transport) = fixture_api(transport, "cookie-expired");
+
+        assert!(!api.check_cookie_login(0).await.unwrap());
+        assert!(!api.is_login());
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn playlist_detail() {
+        let transport = FixtureTransport::new().with_fixture(
+            "/weapi/v6/playlist/detail",
+            include_str!("../fixtures/playlist_detail.json"),
+        );
+        let (api, _) = fixture_api(transport, "playlist-detail");
+
+        let detail = api.song_list_detail(705123491).await.unwrap();
+        assert_eq!(detail.id, 705123491);
+        assert_eq!(detail.description, "");
+        assert_eq!(detail.track_update_time, 1712047231000);
+
+        let song = &detail.songs[0];
+        assert_eq!((song.id, song.name.as_str()), (186016, "晴天"));
+        assert_eq!((song.album_id, song.album.as_str()), (18905, "叶惠美"));
+        assert_eq!(song.duration, 269000);
+        assert_eq!(song.copyright, SongCopyright::VipOnlyHighRate);
+
+        let song = &detail.songs[1];
+        assert_eq!(song.singer, "Sia/Sean Paul");
+        assert_eq!(song.singers.len(), 2);
+        assert_eq!(song.copyright, SongCopyright::Unavailable);
+    }
+
+    #[tokio::test]
+    async fn song_url() {
+        let transport = FixtureTransport::new().with_fixture(
+            "/eapi/song/enhance/player/url",
+            include_str!("../fixtures/song_url.json"),
+        );
+        let (api, _) = fixture_api(transport, "song-url");
+
+        let song_url = api.get_song_url(186016).await.unwrap();
+        assert_eq!(song_url.id, 186016);
+        assert!(song_url.url.ends_with(".flac"));
+        assert_eq!(song_url.rate, 1411000);
+        assert_eq!(song_url.size, 47480133);
+        assert_eq!(song_url.codec, "flac");
+    }
+
+    #[tokio::test]
+    async fn song_url_unavailable() {
+        let transport = FixtureTransport::new().with_fixture(
+            "/eapi/song/enhance/player/url",
+            include_str!("../fixtures/song_url_unavailable.json"),
+        );
+        let (api, _) = fixture_api(transport, "song-url-unavailable");
+
+        assert!(matches!(
+            api.get_song_url(411214279).await,
+            Err(NcmError::Blocked(411214279))
+        ));
+    }
+
+    #[tokio::test]
+    async fn song_lyric_with_translation_and_words_is_cached() {
+        let transport = FixtureTransport::new().with_fixture(
+            "/weapi/song/lyric",
+            include_str!("../fixtures/song_lyric.json"),
+        );
+        let (api, transport) = fixture_api(transport, "song-lyric");
+        let song_info = song(411214279, "Cheap Thrills");
+
+        let line = |time: u64, text: &str, translation: Option<&str>| LyricLine {
+            time,
+            text: text.to_string(),
+            translation: translation.map(str::to_string),
+            romanization: None,
+            words: Vec::new(),
+        };
+        let mut expected = vec![
+            line(0, " 作词 : Sia/Greg Kurstin", None),
+            line(1000, " 作曲 : Sia/Greg Kurstin", None),
+            line(
+                9520,
+                "Come on, come on, turn the radio on",
+                Some("来吧 来吧 把收音机打开"),
+            ),
+            line(
+                13240,
+                "It's Friday night and I won't be long",
+                Some("周五的夜晚 我不会待太久"),
+            ),
+        ];
+        // 逐字歌词与 LRC 歌词时间相同，内容一致
+        expected[2].words = parse_yrc(
+            "[9520,3720](9520,400,0)Come (9920,300,0)on, (10220,400,0)come (10620,300,0)on, (10920,2000,0)turn the radio on",
+        )
+        .swap_remove(0)
+        .words;
+        assert_eq!(api.song_lyric(song_info.clone()).await.unwrap(), expected);
+        // 第二次读取本地缓存的歌词文件
+        assert_eq!(api.song_lyric(song_info.clone()).await.unwrap(), expected);
+        assert_eq!(transport.requests(), vec!["/weapi/song/lyric"]);
+
+        // 删除缓存后重新请求，歌词时间偏移不受影响
+        api.set_lyric_offset(song_info.id, 300).unwrap();
+        api.remove_lyric_cache(song_info.id).unwrap();
+        assert_eq!(api.lyric_offset(song_info.id), 300);
+        assert_eq!(api.song_lyric(song_info).await.unwrap(), expected);
+        assert_eq!(
+            transport.requests(),
+            vec!["/weapi/song/lyric", "/weapi/song/lyric"]
+        );
+    }
+
+    #[tokio::test]
+    async fn song_lyric_keeps_error_type() {
+        let transport = FixtureTransport::new()
+            .with_fixture(
+                "/weapi/song/lyric",
+                r#"{"sgc":false,"sfy":false,"qfy":false,"nolyric":true,"code":200}"#,
+            )
+            .with_fixture("/weapi/song/lyric", r#"{"code":-460,"msg":"Cheating"}"#);
+        let (api, _) = fixture_api(transport, "song-lyric-error");
+        let song_info = song(1, "纯音乐");
+
+        assert!(matches!(
+            api.song_lyric(song_info.clone()).await,
+            Err(NcmError::LyricNotFound)
+        ));
+        assert!(matches!(
+            api.song_lyric(song_info).await,
+            Err(NcmError::RateLimited(-460))
+        ));
+    }
+
+    #[tokio::test]
+    async fn user_lyric_file_takes_precedence() {
+        let transport = FixtureTransport::new().with_fixture(
+            "/weapi/song/lyric",
+            include_str!("../fixtures/song_lyric.json"),
+        );
+        let (api, transport) = fixture_api(transport, "user-lyric");
+        let song_info = SongInfo {
+            singer: String::from("周杰伦"),
+            ..song(186016, "晴天")
+        };
+        // fixture_api 中歌词目录与缓存目录相同
+        let lyrics_dir = env::temp_dir().join(format!("ncm-api-user-lyric-{}", std::process::id()));
+        fs::write(
+            lyrics_dir.join("周杰伦 - 晴天.lrc"),
+            "[00:29.36]故事的小黄花\n[00:32.79]从出生那年就飘着",
+        )
+        .unwrap();
+
+        let lyrics = api.song_lyric(song_info).await.unwrap();
+        let texts: Vec<&str> = lyrics.iter().map(|line| line.text.as_str()).collect();
+        assert_eq!(texts, vec!["故事的小黄花", "从出生那年就飘着"]);
+        assert!(transport.requests().is_empty());
+    }
+
+    #[test]
+    fn song_loudness_is_cached_with_album_estimate() {
+        let (api, _) = fixture_api(FixtureTransport::new(), "loudness");
+        let song_info = |id: u64, album_id: u64| SongInfo {
+            album_id,
+            ..song(id, "")
+        };
+        assert_eq!(api.song_loudness(1), None);
+        assert_eq!(api.album_loudness(18905), None);
+
+        let quiet = Loudness {
+            gain: 0.0,
+            peak: 0.5,
+        };
+        let loud = Loudness {
+            gain: -10.0,
+            peak: 1.0,
+        };
+        api.set_song_loudness(&song_info(1, 18905), quiet).unwrap();
+        api.set_song_loudness(&song_info(2, 18905), loud).unwrap();
+        api.set_song_loudness(&song_info(3, 0), loud).unwrap();
+        assert_eq!(api.song_loudness(1), Some(quiet));
+        assert_eq!(api.song_loudness(3), Some(loud));
+
+        // 能量平均：(1 + 10) / 2 = 5.5 → -7.40 dB
+        let album = api.album_loudness(18905).unwrap();
+        assert!((album.gain + 10.0 * 5.5_f64.log10()).abs() < 1e-9);
+        assert_eq!(album.peak, 1.0);
+        assert_eq!(api.album_loudness(0), None);
+    }
 }
@@ -0,0 +1,94 @@
+//
+// 歌曲响度缓存
+//
+use crate::error::Result;
+use crate::model::Loudness;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// 缓存文件中的一首歌曲
+#[derive(PartialEq, Serialize, Deserialize)]
+struct StoredLoudness {
+    album_id: u64,
+    #[serde(flatten)]
+    loudness: Loudness,
+}
+
+/// 按歌曲 id 缓存播放时分析得到的响度，保存在 cache_dir/loudness.json
+/// 文件在创建时读入内存，之后只在响度变化时写回，clone 出的实例共享同一份数据
+#[derive(Clone)]
+pub struct LoudnessStore {
+    path: PathBuf,
+    all: Arc<Mutex<BTreeMap<u64, StoredLoudness>>>,
+}
+
+impl LoudnessStore {
+    pub fn new(cache_dir: PathBuf) -> Self {
+        let path = cache_dir.join("loudness.json");
+        let all = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Self {
+            path,
+            all: Arc::new(Mutex::new(all)),
+        }
+    }
+
+    /// 歌曲的响度，未分析过时返回 None
+    pub fn track(&self, id: u64) -> Option<Loudness> {
+        self.all
+            .lock()
+            .unwrap()
+            .get(&id)
+            .map(|stored| stored.loudness)
+    }
+
+    /// 由专辑中已分析过的歌曲估算专辑的响度：响度按能量平均，峰值取最大值
+    pub fn album(&self, album_id: u64) -> Option<Loudness> {
+        if album_id == 0 {
+            return None;
+        }
+
+        let (count, power, peak) = self
+            .all
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|stored| stored.album_id == album_id)
+            .fold((0, 0.0, 0.0_f64), |(count, power, peak), stored| {
+                (
+                    count + 1,
+                    power + 10_f64.powf(-stored.loudness.gain / 10.0),
+                    peak.max(stored.loudness.peak),
+                )
+            });
+        if count == 0 {
+            return None;
+        }
+
+        Some(Loudness {
+            gain: -10.0 * (power / count as f64).log10(),
+            peak,
+        })
+    }
+
+    /// 保存歌曲的响度，与已保存的相同时不写文件
+    pub fn save(&self, id: u64, album_id: u64, loudness: Loudness) -> Result<()> {
+        let mut all = self.all.lock().unwrap();
+        let stored = StoredLoudness { album_id, loudness };
+        if all.get(&id) == Some(&stored) {
+            return Ok(());
+        }
+        all.insert(id, stored);
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&self.path, serde_json::to_string(&*all)?)?;
+
+        Ok(())
+    }
+}
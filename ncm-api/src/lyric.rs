@@ -0,0 +1,710 @@
+//
+// LRC / YRC 歌词解析
+//
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// 逐字歌词与 LRC 歌词时间相差超过该值时不匹配，单位: ms
+const YRC_MATCH_TOLERANCE: u64 = 1000;
+
+/// 一行歌词
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LyricLine {
+    /// 开始时间，单位: ms
+    pub time: u64,
+    /// 原文
+    pub text: String,
+    /// 翻译
+    pub translation: Option<String>,
+    /// 音译（罗马音）
+    pub romanization: Option<String>,
+    /// 逐字时间，无逐字歌词时为空
+    pub words: Vec<YrcWord>,
+}
+
+impl LyricLine {
+    /// 播放到 position（ms）时本行已唱过的字符数，用于逐字高亮
+    /// 正在唱的字按时间比例计算，无逐字歌词时返回 None
+    pub fn sung_chars(&self, position: u64) -> Option<usize> {
+        if self.words.is_empty() {
+            return None;
+        }
+
+        let mut sung = 0;
+        for word in self.words.iter() {
+            let chars = word.text.chars().count();
+            if position >= word.time + word.duration {
+                sung += chars;
+            } else {
+                if position > word.time {
+                    sung += (chars as u64 * (position - word.time) / word.duration) as usize;
+                }
+                break;
+            }
+        }
+
+        Some(sung.min(self.text.chars().count()))
+    }
+}
+
+/// 一行 LRC 歌词
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LrcLine {
+    /// 开始时间（已应用 offset），单位: ms
+    pub time: u64,
+    /// 歌词内容
+    pub text: String,
+}
+
+/// 解析后的 LRC 歌词
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Lrc {
+    /// 标签，如 ti（标题）、ar（歌手）、al（专辑）、by（歌词作者），键为小写
+    pub tags: HashMap<String, String>,
+    /// [offset:] 标签，单位: ms，正数表示歌词提前显示
+    pub offset: i64,
+    /// 按时间排序的歌词行
+    pub lines: Vec<LrcLine>,
+}
+
+impl Lrc {
+    /// 解析 LRC 歌词
+    /// 支持以下格式，无法识别的行会被忽略
+    ///   - 时间戳 `[mm:ss]` `[mm:ss.x]` `[mm:ss.xx]` `[mm:ss.xxx]`，分钟可超过两位
+    ///   - 不规范的时间戳 `[mm:ss:xx]`
+    ///   - 一行多个时间戳 `[00:01.00][00:31.00]歌词`
+    ///   - 标签 `[ti:标题]` `[offset:500]` 等
+    pub fn parse(text: &str) -> Self {
+        let mut lrc = Lrc::default();
+
+        for line in text.lines() {
+            let mut rest = line.trim_start_matches('\u{feff}').trim_start();
+            let mut times = Vec::new();
+
+            while let Some(stripped) = rest.strip_prefix('[') {
+                let Some(end) = stripped.find(']') else {
+                    break;
+                };
+                let tag = &stripped[..end];
+
+                if let Some(time) = parse_timestamp(tag) {
+                    times.push(time);
+                } else if times.is_empty() {
+                    if let Some((key, value)) = parse_tag(tag) {
+                        if key == "offset" {
+                            lrc.offset = value.parse().unwrap_or(0);
+                        }
+                        lrc.tags.insert(key, value.to_string());
+                    }
+                } else {
+                    // 时间戳之后的 [] 属于歌词内容
+                    break;
+                }
+                rest = &stripped[end + 1..];
+            }
+
+            // 部分歌词句尾存在 \t 等空白字符，会干扰渲染
+            let text = rest.trim_end();
+            for time in times {
+                lrc.lines.push(LrcLine {
+                    time,
+                    text: text.to_string(),
+                });
+            }
+        }
+
+        // 一行多个时间戳时歌词不再按顺序排列，稳定排序保留同一时间多行歌词的原有顺序
+        lrc.lines.sort_by_key(|line| line.time);
+        for line in lrc.lines.iter_mut() {
+            line.time = (line.time as i64 - lrc.offset).max(0) as u64;
+        }
+
+        lrc
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// 按时间戳匹配歌词与翻译、音译
+    pub fn merge_layers(&self, translation: &Lrc, romanization: &Lrc) -> Vec<LyricLine> {
+        let translations = self.match_layer(translation);
+        let romanizations = self.match_layer(romanization);
+
+        self.lines
+            .iter()
+            .zip(translations)
+            .zip(romanizations)
+            .map(|((line, translation), romanization)| LyricLine {
+                time: line.time,
+                text: line.text.clone(),
+                translation,
+                romanization,
+                words: Vec::new(),
+            })
+            .collect()
+    }
+
+    /// 为每行歌词匹配 layer 中时间相同的行
+    /// 同一时间有多行歌词时（如歌曲信息与第一句歌词同在 [00:00.00]），从最后一行开始依次匹配
+    fn match_layer(&self, layer: &Lrc) -> Vec<Option<String>> {
+        let mut layer_texts: HashMap<u64, Vec<&str>> = HashMap::new();
+        for line in layer.lines.iter() {
+            if !line.text.trim().is_empty() {
+                layer_texts.entry(line.time).or_default().push(&line.text);
+            }
+        }
+
+        let mut matched = vec![None; self.lines.len()];
+        for (line, text) in self.lines.iter().zip(matched.iter_mut()).rev() {
+            if let Some(texts) = layer_texts.get_mut(&line.time) {
+                *text = texts.pop().map(str::to_string);
+            }
+        }
+
+        matched
+    }
+}
+
+/// 逐字歌词中的一个字（词）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct YrcWord {
+    /// 开始时间，单位: ms
+    pub time: u64,
+    /// 持续时间，单位: ms
+    pub duration: u64,
+    pub text: String,
+}
+
+/// 一行逐字歌词
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct YrcLine {
+    /// 开始时间，单位: ms
+    pub time: u64,
+    /// 持续时间，单位: ms
+    pub duration: u64,
+    pub words: Vec<YrcWord>,
+}
+
+impl YrcLine {
+    /// 整行歌词内容
+    pub fn text(&self) -> String {
+        self.words.iter().map(|word| word.text.as_str()).collect()
+    }
+}
+
+/// 解析逐字歌词，无法识别的行会被忽略
+/// 支持以下格式:
+///   - yrc: `[行开始,行时长](字开始,字时长,0)字...`，字开始为绝对时间
+///   - klyric: `[行开始,行时长](0,字时长)字...`，字按顺序紧接上一个字
+///   - 歌曲信息: `{"t":0,"c":[{"tx":"作词: "},{"tx":"周杰伦"}]}`
+pub fn parse_yrc(text: &str) -> Vec<YrcLine> {
+    let mut lines: Vec<YrcLine> = text
+        .lines()
+        .filter_map(|line| {
+            // 保留行尾空格，英文逐字歌词中单词后的空格属于该单词
+            let line = line.trim_start_matches('\u{feff}').trim_start();
+            if line.starts_with('{') {
+                parse_yrc_info_line(line)
+            } else {
+                parse_yrc_line(line)
+            }
+        })
+        .collect();
+    lines.sort_by_key(|line| line.time);
+
+    lines
+}
+
+/// 为每行歌词匹配时间最接近的逐字歌词，匹配到时以逐字歌词的内容为准
+/// 只有逐字歌词时直接使用逐字歌词
+pub fn attach_yrc(mut lines: Vec<LyricLine>, yrc: &[YrcLine]) -> Vec<LyricLine> {
+    if lines.is_empty() {
+        return yrc
+            .iter()
+            .map(|yrc_line| LyricLine {
+                time: yrc_line.time,
+                text: yrc_line.text().trim_end().to_string(),
+                translation: None,
+                romanization: None,
+                words: yrc_line.words.clone(),
+            })
+            .collect();
+    }
+
+    let mut used = vec![false; yrc.len()];
+    for line in lines.iter_mut() {
+        let nearest = yrc
+            .iter()
+            .enumerate()
+            .filter(|(index, yrc_line)| {
+                !used[*index] && yrc_line.time.abs_diff(line.time) <= YRC_MATCH_TOLERANCE
+            })
+            .min_by_key(|(_, yrc_line)| yrc_line.time.abs_diff(line.time));
+
+        if let Some((index, yrc_line)) = nearest {
+            used[index] = true;
+            line.text = yrc_line.text().trim_end().to_string();
+            line.words = yrc_line.words.clone();
+        }
+    }
+
+    lines
+}
+
+/// 解析时间戳 `mm:ss.xxx`，返回 ms
+fn parse_timestamp(tag: &str) -> Option<u64> {
+    let (minutes, rest) = tag.split_once(':')?;
+    let (seconds, fraction) = match rest.split_once(['.', ':']) {
+        Some((seconds, fraction)) => (seconds, Some(fraction)),
+        None => (rest, None),
+    };
+
+    let minutes = parse_digits(minutes)?;
+    let seconds = parse_digits(seconds)?;
+    let millis = match fraction {
+        // 只取前三位，不足三位时补齐，如 .5 为 500ms，.05 为 50ms
+        Some(fraction) => {
+            // 先确认全部为 ASCII 数字，再按字节截取
+            if fraction.is_empty() || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            let digits = &fraction[..fraction.len().min(3)];
+            parse_digits(digits)? * 10u64.pow(3 - digits.len() as u32)
+        }
+        None => 0,
+    };
+
+    Some((minutes * 60 + seconds) * 1000 + millis)
+}
+
+/// 解析标签 `key:value`，键只能包含字母
+fn parse_tag(tag: &str) -> Option<(String, &str)> {
+    let (key, value) = tag.split_once(':')?;
+    let key = key.trim();
+    if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    Some((key.to_ascii_lowercase(), value.trim()))
+}
+
+/// 非空且只包含数字
+fn parse_digits(s: &str) -> Option<u64> {
+    if s.is_empty() || !s.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+/// 解析逗号分隔的时间，如 `16210,3460` 或 `16210,670,0`
+fn parse_times(s: &str) -> Option<Vec<u64>> {
+    s.split(',').map(|time| parse_digits(time.trim())).collect()
+}
+
+fn parse_yrc_line(line: &str) -> Option<YrcLine> {
+    let (line_times, mut rest) = line.strip_prefix('[')?.split_once(']')?;
+    let line_times = parse_times(line_times)?;
+    let (time, duration) = (*line_times.first()?, *line_times.get(1)?);
+
+    let mut words = Vec::new();
+    // klyric 中字的开始时间为上一个字的结束时间
+    let mut next_word_time = time;
+    while let Some((word_times, text, remain)) = next_yrc_word(rest) {
+        let (word_time, word_duration) = match word_times.as_slice() {
+            [word_time, word_duration, _] => (*word_time, *word_duration),
+            [_, word_duration] => (next_word_time, *word_duration),
+            _ => return None,
+        };
+        next_word_time = word_time + word_duration;
+
+        words.push(YrcWord {
+            time: word_time,
+            duration: word_duration,
+            text: text.to_string(),
+        });
+        rest = remain;
+    }
+
+    Some(YrcLine {
+        time,
+        duration,
+        words,
+    })
+}
+
+/// 读取 `(时间)字` ，返回 (时间, 字, 剩余部分)
+/// 字本身可能包含括号，只有括号内为时间时才视为下一个字的开始
+fn next_yrc_word(s: &str) -> Option<(Vec<u64>, &str, &str)> {
+    let (word_times, rest) = s.strip_prefix('(')?.split_once(')')?;
+    let word_times = parse_times(word_times)?;
+
+    let text_end = rest
+        .match_indices('(')
+        .map(|(index, _)| index)
+        .find(|index| {
+            rest[index + 1..]
+                .split_once(')')
+                .is_some_and(|(times, _)| parse_times(times).is_some())
+        })
+        .unwrap_or(rest.len());
+
+    Some((word_times, &rest[..text_end], &rest[text_end..]))
+}
+
+fn parse_yrc_info_line(line: &str) -> Option<YrcLine> {
+    let value = serde_json::from_str::<Value>(line).ok()?;
+    let time = value.get("t")?.as_u64()?;
+    let text: String = value
+        .get("c")?
+        .as_array()?
+        .iter()
+        .filter_map(|c| c.get("tx").and_then(Value::as_str))
+        .collect();
+
+    Some(YrcLine {
+        time,
+        duration: 0,
+        words: vec![YrcWord {
+            time,
+            duration: 0,
+            text,
+        }],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(time: u64, text: &str) -> LrcLine {
+        LrcLine {
+            time,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn timestamp_precision() {
+        assert_eq!(parse_timestamp("01:02"), Some(62_000));
+        assert_eq!(parse_timestamp("01:02.5"), Some(62_500));
+        assert_eq!(parse_timestamp("01:02.05"), Some(62_050));
+        assert_eq!(parse_timestamp("01:02.005"), Some(62_005));
+        assert_eq!(parse_timestamp("01:02.0054"), Some(62_005));
+        assert_eq!(parse_timestamp("1:02.50"), Some(62_500));
+        assert_eq!(parse_timestamp("123:04.56"), Some(7_384_560));
+        // 不规范的时间戳
+        assert_eq!(parse_timestamp("00:11:22"), Some(11_220));
+    }
+
+    #[test]
+    fn invalid_timestamp() {
+        assert_eq!(parse_timestamp("ti:晴天"), None);
+        assert_eq!(parse_timestamp("00:-1.00"), None);
+        assert_eq!(parse_timestamp(":01.00"), None);
+        assert_eq!(parse_timestamp("00:01."), None);
+        assert_eq!(parse_timestamp("00:01.a0"), None);
+        assert_eq!(parse_timestamp("0001"), None);
+        assert_eq!(parse_timestamp("00:00.12中"), None);
+        assert_eq!(parse_timestamp("00:00.中"), None);
+    }
+
+    #[test]
+    fn parse_lines() {
+        let lrc = Lrc::parse("[00:29.73]故事的小黄花\n[00:33.29]从出生那年就飘着\t\r\n");
+
+        assert_eq!(
+            lrc.lines,
+            vec![
+                line(29_730, "故事的小黄花"),
+                line(33_290, "从出生那年就飘着")
+            ]
+        );
+        assert!(lrc.tags.is_empty());
+        assert_eq!(lrc.offset, 0);
+    }
+
+    #[test]
+    fn keep_empty_and_bracketed_text() {
+        let lrc = Lrc::parse("[00:01.00]\n[00:02.00][Chorus] la la\n[00:03.00] 作词 : 周杰伦");
+
+        assert_eq!(
+            lrc.lines,
+            vec![
+                line(1_000, ""),
+                line(2_000, "[Chorus] la la"),
+                line(3_000, " 作词 : 周杰伦"),
+            ]
+        );
+    }
+
+    #[test]
+    fn multiple_timestamps_per_line() {
+        let lrc = Lrc::parse("[00:01.00][00:31.00]副歌\n[00:11.00]主歌\n[00:41.00][00:21.00]桥段");
+
+        assert_eq!(
+            lrc.lines,
+            vec![
+                line(1_000, "副歌"),
+                line(11_000, "主歌"),
+                line(21_000, "桥段"),
+                line(31_000, "副歌"),
+                line(41_000, "桥段"),
+            ]
+        );
+    }
+
+    #[test]
+    fn same_timestamp_keeps_order() {
+        let lrc = Lrc::parse("[00:00.00]作词 : 周杰伦\n[00:00.00]作曲 : 周杰伦\n[00:00.00]第一句");
+
+        let texts: Vec<&str> = lrc.lines.iter().map(|line| line.text.as_str()).collect();
+        assert_eq!(texts, vec!["作词 : 周杰伦", "作曲 : 周杰伦", "第一句"]);
+    }
+
+    #[test]
+    fn parse_tags() {
+        let lrc =
+            Lrc::parse("\u{feff}[ti:晴天]\n[AR: 周杰伦 ]\n[al:叶惠美]\n[by:]\n[00:01.00]歌词");
+
+        assert_eq!(lrc.tags.get("ti").unwrap(), "晴天");
+        assert_eq!(lrc.tags.get("ar").unwrap(), "周杰伦");
+        assert_eq!(lrc.tags.get("al").unwrap(), "叶惠美");
+        assert_eq!(lrc.tags.get("by").unwrap(), "");
+        assert_eq!(lrc.lines, vec![line(1_000, "歌词")]);
+    }
+
+    #[test]
+    fn apply_offset() {
+        // 正数提前显示
+        let lrc = Lrc::parse("[offset:+500]\n[00:00.20]第一句\n[00:02.00]第二句");
+        assert_eq!(lrc.offset, 500);
+        assert_eq!(lrc.lines, vec![line(0, "第一句"), line(1_500, "第二句")]);
+
+        // 负数延后显示，offset 标签可以出现在任意位置
+        let lrc = Lrc::parse("[00:02.00]第二句\n[offset:-250]");
+        assert_eq!(lrc.offset, -250);
+        assert_eq!(lrc.lines, vec![line(2_250, "第二句")]);
+
+        let lrc = Lrc::parse("[offset:abc]\n[00:02.00]第二句");
+        assert_eq!(lrc.offset, 0);
+    }
+
+    #[test]
+    fn ignore_invalid_lines() {
+        let lrc = Lrc::parse("没有时间戳\n\n[00:01.00\n[x1:00]\n[00:00.12中]x\n[00:02.00]有效");
+
+        assert_eq!(lrc.lines, vec![line(2_000, "有效")]);
+    }
+
+    fn lyric_line(time: u64, text: &str, translation: Option<&str>) -> LyricLine {
+        LyricLine {
+            time,
+            text: text.to_string(),
+            translation: translation.map(str::to_string),
+            romanization: None,
+            words: Vec::new(),
+        }
+    }
+
+    fn word(time: u64, duration: u64, text: &str) -> YrcWord {
+        YrcWord {
+            time,
+            duration,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn merge_translation_by_timestamp() {
+        let lyric = Lrc::parse(
+            "[00:00.00]作词 : Sia\n[00:00.00]Come on\n[00:05.00]turn the radio on\n[00:09.00]Oh\n[00:12.00]",
+        );
+        let translation =
+            Lrc::parse("[by:云村翻译组]\n[00:00.00]来吧\n[00:05.000]把收音机打开\n[00:12.00]");
+
+        assert_eq!(
+            lyric.merge_layers(&translation, &Lrc::default()),
+            vec![
+                lyric_line(0, "作词 : Sia", None),
+                lyric_line(0, "Come on", Some("来吧")),
+                lyric_line(5_000, "turn the radio on", Some("把收音机打开")),
+                lyric_line(9_000, "Oh", None),
+                lyric_line(12_000, "", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn merge_empty_translation() {
+        let lyric = Lrc::parse("[00:01.00]歌词");
+
+        assert_eq!(
+            lyric.merge_layers(&Lrc::default(), &Lrc::default()),
+            vec![lyric_line(1_000, "歌词", None)]
+        );
+    }
+
+    #[test]
+    fn merge_translation_and_romanization() {
+        let lyric = Lrc::parse("[00:01.00]君の名は\n[00:03.00]夢の中\n[00:05.00]Interlude");
+        let translation = Lrc::parse("[00:01.00]你的名字\n[00:03.00]在梦里");
+        let romanization = Lrc::parse("[00:01.000]ki mi no na wa\n[00:03.000]yu me no na ka");
+
+        let lines = lyric.merge_layers(&translation, &romanization);
+        let layers: Vec<(Option<&str>, Option<&str>)> = lines
+            .iter()
+            .map(|line| (line.translation.as_deref(), line.romanization.as_deref()))
+            .collect();
+        assert_eq!(
+            layers,
+            vec![
+                (Some("你的名字"), Some("ki mi no na wa")),
+                (Some("在梦里"), Some("yu me no na ka")),
+                (None, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_yrc_words() {
+        let lines = parse_yrc("[16210,3460](16210,670,0)还(16880,410,0)没 (17290,0,0)\n");
+
+        assert_eq!(
+            lines,
+            vec![YrcLine {
+                time: 16_210,
+                duration: 3_460,
+                words: vec![
+                    YrcWord {
+                        time: 16_210,
+                        duration: 670,
+                        text: String::from("还"),
+                    },
+                    YrcWord {
+                        time: 16_880,
+                        duration: 410,
+                        text: String::from("没 "),
+                    },
+                    YrcWord {
+                        time: 17_290,
+                        duration: 0,
+                        text: String::new(),
+                    },
+                ],
+            }]
+        );
+        assert_eq!(lines[0].text(), "还没 ");
+    }
+
+    #[test]
+    fn parse_yrc_word_with_parentheses() {
+        let lines = parse_yrc("[1000,2000](1000,500,0)(Oh) (1500,500,0)yeah(");
+
+        let texts: Vec<&str> = lines[0]
+            .words
+            .iter()
+            .map(|word| word.text.as_str())
+            .collect();
+        assert_eq!(texts, vec!["(Oh) ", "yeah("]);
+    }
+
+    #[test]
+    fn parse_klyric_words() {
+        let lines = parse_yrc("[1000,900](0,300)Cheap (0,200)Thrills(0,400)");
+
+        let words: Vec<(u64, u64)> = lines[0]
+            .words
+            .iter()
+            .map(|word| (word.time, word.duration))
+            .collect();
+        assert_eq!(words, vec![(1_000, 300), (1_300, 200), (1_500, 400)]);
+        assert_eq!(lines[0].text(), "Cheap Thrills");
+    }
+
+    #[test]
+    fn parse_yrc_info_and_sort() {
+        let lines = parse_yrc(
+            "[5000,1000](5000,1000,0)第一句\n{\"t\":0,\"c\":[{\"tx\":\"作词: \"},{\"tx\":\"周杰伦\",\"li\":\"\"}]}\n无效\n[a,b](1,1,0)无效",
+        );
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            (lines[0].time, lines[0].text()),
+            (0, String::from("作词: 周杰伦"))
+        );
+        assert_eq!(
+            (lines[1].time, lines[1].text()),
+            (5_000, String::from("第一句"))
+        );
+    }
+
+    #[test]
+    fn attach_yrc_to_nearest_line() {
+        let lines = vec![
+            lyric_line(0, "作词 : 周杰伦", None),
+            lyric_line(29_730, "故事的小黄花", Some("translation")),
+            lyric_line(33_290, "从出生那年就飘着", None),
+            lyric_line(60_000, "没有逐字歌词", None),
+        ];
+        let yrc = parse_yrc(
+            "{\"t\":0,\"c\":[{\"tx\":\"作词: \"},{\"tx\":\"周杰伦\"}]}\n[29500,3600](29500,600,0)故事(30100,300,0)的(30400,1200,0)小黄花 \n[33290,2000](33290,2000,0)从出生那年就飘着",
+        );
+
+        let lines = attach_yrc(lines, &yrc);
+        assert_eq!(lines[0].text, "作词: 周杰伦");
+        assert_eq!(lines[1].text, "故事的小黄花");
+        assert_eq!(lines[1].translation.as_deref(), Some("translation"));
+        assert_eq!(
+            lines[1].words,
+            vec![
+                word(29_500, 600, "故事"),
+                word(30_100, 300, "的"),
+                word(30_400, 1_200, "小黄花 "),
+            ]
+        );
+        assert_eq!(lines[2].words.len(), 1);
+        assert!(lines[3].words.is_empty());
+        assert_eq!(lines[3].text, "没有逐字歌词");
+    }
+
+    #[test]
+    fn attach_yrc_without_lrc() {
+        let yrc = parse_yrc("[1000,500](1000,500,0)only yrc ");
+
+        let lines = attach_yrc(Vec::new(), &yrc);
+        assert_eq!(lines.len(), 1);
+        assert_eq!((lines[0].time, lines[0].text.as_str()), (1_000, "only yrc"));
+        assert_eq!(lines[0].words.len(), 1);
+    }
+
+    #[test]
+    fn attach_yrc_each_line_once() {
+        let lines = vec![lyric_line(1_000, "a", None), lyric_line(1_000, "b", None)];
+        let yrc = parse_yrc("[1000,500](1000,500,0)a");
+
+        let lines = attach_yrc(lines, &yrc);
+        assert_eq!(lines[0].words.len(), 1);
+        assert!(lines[1].words.is_empty());
+    }
+
+    #[test]
+    fn sung_chars_progress() {
+        let mut line = lyric_line(1_000, "故事的小黄花", None);
+        assert_eq!(line.sung_chars(1_500), None);
+
+        line.words = vec![
+            word(1_000, 400, "故事"),
+            word(1_400, 200, "的"),
+            word(1_600, 600, "小黄花"),
+        ];
+        assert_eq!(line.sung_chars(0), Some(0));
+        assert_eq!(line.sung_chars(1_000), Some(0));
+        assert_eq!(line.sung_chars(1_200), Some(1));
+        assert_eq!(line.sung_chars(1_400), Some(2));
+        assert_eq!(line.sung_chars(1_600), Some(3));
+        assert_eq!(line.sung_chars(1_800), Some(4));
+        assert_eq!(line.sung_chars(2_200), Some(6));
+        assert_eq!(line.sung_chars(10_000), Some(6));
+    }
+}
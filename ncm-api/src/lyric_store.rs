@@ -0,0 +1,131 @@
+//
+// 歌词本地存储
+//
+use crate::error::Result;
+use crate::model::{Lyrics, SongInfo};
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// 缓存格式版本，格式变化时加一，旧版本的缓存会被忽略并重新获取
+const LYRIC_STORE_VERSION: u32 = 1;
+
+/// 缓存文件内容
+#[derive(Serialize, Deserialize)]
+struct StoredLyrics {
+    version: u32,
+    id: u64,
+    lyrics: Lyrics,
+}
+
+/// 按歌曲 id 存储歌词
+/// 接口获取的歌词（含翻译、音译、逐字歌词）缓存在 cache_dir/lyrics/{id}.json
+/// 用户放入 user_dir 的歌词文件优先于缓存，文件名为 "{id}.lrc" 或 "{歌手} - {歌名}.lrc"
+/// 用户调整的歌词时间偏移保存在 user_dir/offsets.json，不随缓存删除
+#[derive(Clone)]
+pub struct LyricStore {
+    user_dir: PathBuf,
+    cache_dir: PathBuf,
+}
+
+impl LyricStore {
+    pub fn new(user_dir: PathBuf, cache_dir: PathBuf) -> Self {
+        Self {
+            user_dir,
+            cache_dir: cache_dir.join("lyrics"),
+        }
+    }
+
+    /// 用户提供的歌词文件，只包含原文
+    pub fn user_lyrics(&self, si: &SongInfo) -> Option<Lyrics> {
+        let file_names = [
+            format!("{}.lrc", si.id),
+            format!(
+                "{} - {}.lrc",
+                si.singer.replace('/', "／"),
+                si.name.replace('/', "／")
+            ),
+        ];
+
+        file_names.iter().find_map(|file_name| {
+            let content = fs::read_to_string(self.user_dir.join(file_name)).ok()?;
+            Some(Lyrics {
+                lyric: content.lines().map(str::to_string).collect(),
+                tlyric: Vec::new(),
+                yrc: Vec::new(),
+                romalrc: Vec::new(),
+            })
+        })
+    }
+
+    /// 读取缓存，缓存不存在、版本不符或已损坏时返回 None
+    pub fn load(&self, id: u64) -> Option<Lyrics> {
+        let content = fs::read_to_string(self.cache_file(id)).ok()?;
+        match serde_json::from_str::<StoredLyrics>(&content) {
+            Ok(stored) if stored.version == LYRIC_STORE_VERSION && stored.id == id => {
+                Some(stored.lyrics)
+            }
+            Ok(_) => None,
+            Err(e) => {
+                warn!("歌词缓存已损坏 {}: {}", id, e);
+                None
+            }
+        }
+    }
+
+    /// 写入缓存
+    pub fn save(&self, id: u64, lyrics: &Lyrics) -> Result<()> {
+        fs::create_dir_all(&self.cache_dir)?;
+        let stored = StoredLyrics {
+            version: LYRIC_STORE_VERSION,
+            id,
+            lyrics: lyrics.clone(),
+        };
+        fs::write(self.cache_file(id), serde_json::to_string(&stored)?)?;
+
+        Ok(())
+    }
+
+    /// 删除缓存，下次获取歌词时重新请求
+    pub fn remove(&self, id: u64) -> Result<()> {
+        match fs::remove_file(self.cache_file(id)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// 歌词时间偏移，单位: ms，正数表示歌词延后显示
+    pub fn offset(&self, id: u64) -> i64 {
+        self.load_offsets().get(&id).copied().unwrap_or(0)
+    }
+
+    /// 保存歌词时间偏移，为 0 时删除记录
+    pub fn set_offset(&self, id: u64, offset: i64) -> Result<()> {
+        let mut offsets = self.load_offsets();
+        if offset == 0 {
+            offsets.remove(&id);
+        } else {
+            offsets.insert(id, offset);
+        }
+        fs::write(self.offsets_file(), serde_json::to_string_pretty(&offsets)?)?;
+
+        Ok(())
+    }
+
+    fn load_offsets(&self) -> BTreeMap<u64, i64> {
+        fs::read_to_string(self.offsets_file())
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn offsets_file(&self) -> PathBuf {
+        self.user_dir.join("offsets.json")
+    }
+
+    fn cache_file(&self, id: u64) -> PathBuf {
+        self.cache_dir.join(format!("{}.json", id))
+    }
+}
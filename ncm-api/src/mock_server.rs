@@ -0,0 +1,137 @@
+//
+// 测试用的本地 HTTP 服务器
+//
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// 模拟服务器对一个请求的响应
+pub enum MockResponse {
+    /// 返回状态码和响应体
+    Reply(u16, String),
+    /// 等待一段时间后再返回，用于测试超时
+    Delay(Duration, u16, String),
+}
+
+/// 模拟服务器收到的请求
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockRequest {
+    pub path: String, // 路径（含查询参数）
+    pub body_len: usize,
+}
+
+pub struct MockServer {
+    url: String,
+    requests: Arc<Mutex<Vec<MockRequest>>>,
+}
+
+impl MockServer {
+    /// 依次使用 responses 响应每个请求（每个请求一个连接），用完后不再接受连接
+    pub fn start(responses: Vec<MockResponse>) -> Self {
+        let count = responses.len();
+        let mut responses = responses.into_iter();
+        Self::with_handler(count, move |_| responses.next().unwrap())
+    }
+
+    /// 接受 count 个连接，按收到的顺序调用 handler 生成响应
+    /// 每个连接在单独的线程中响应，延迟的响应不会阻塞后续请求
+    pub fn with_handler<F>(count: usize, mut handler: F) -> Self
+    where
+        F: FnMut(&MockRequest) -> MockResponse + Send + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let requests_2 = Arc::clone(&requests);
+        thread::spawn(move || {
+            for stream in listener.incoming().take(count) {
+                let mut stream = match stream {
+                    Ok(stream) => stream,
+                    Err(_) => return,
+                };
+                let request = match read_request(&stream) {
+                    Some(request) => request,
+                    None => continue,
+                };
+                let response = handler(&request);
+                requests_2.lock().unwrap().push(request);
+
+                thread::spawn(move || {
+                    let (status, body) = match response {
+                        MockResponse::Reply(status, body) => (status, body),
+                        MockResponse::Delay(delay, status, body) => {
+                            thread::sleep(delay);
+                            (status, body)
+                        }
+                    };
+                    // 客户端可能已超时断开，忽略写入错误
+                    let _ = write!(
+                        stream,
+                        "HTTP/1.1 {} Mock\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        status,
+                        body.len(),
+                        body
+                    );
+                });
+            }
+        });
+
+        Self { url, requests }
+    }
+
+    pub fn url(&self) -> String {
+        self.url.clone()
+    }
+
+    /// 已收到的请求路径（含查询参数）
+    pub fn requests(&self) -> Vec<String> {
+        self.received()
+            .into_iter()
+            .map(|request| request.path)
+            .collect()
+    }
+
+    /// 已收到的请求
+    pub fn received(&self) -> Vec<MockRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+/// 读取请求行、请求头和请求体，连接异常时返回 None
+fn read_request(stream: &TcpStream) -> Option<MockRequest> {
+    let mut reader = BufReader::new(stream.try_clone().ok()?);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).ok()?;
+    let path = request_line.split_whitespace().nth(1)?.to_string();
+
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header).ok()?;
+        if header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().ok()?;
+            }
+        }
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).ok()?;
+
+    Some(MockRequest {
+        path,
+        body_len: content_length,
+    })
+}
+
+/// 无法连接的地址：绑定随机端口后立即释放
+pub fn unreachable_url() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    format!("http://{}", listener.local_addr().unwrap())
+}
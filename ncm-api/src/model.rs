@@ -3,7 +3,7 @@
 // Copyright (C) 2019 gmg137 <gmg137@live.com>
 // Distributed under terms of the GPLv3 license.
 //
-use anyhow::{anyhow, Context, Ok, Result};
+use crate::error::{NcmError, Result};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::fmt;
@@ -52,7 +52,7 @@ impl<'a> DeVal<'a> for &'a Vec<Value> {
     fn dval(v: &'a Value) -> Result<Self> {
         match v {
             Value::Array(v) => Ok(v),
-            _ => Err(anyhow!("json not a array")),
+            _ => Err(NcmError::Json(String::from("json not a array"))),
         }
     }
 }
@@ -67,19 +67,41 @@ fn get_val_chain<'a, T>(v: &'a Value, names: &[&str]) -> Result<T>
 where
     T: DeVal<'a>,
 {
-    let v = names.iter().fold(Ok(v), |v, n| {
-        v?.get(n)
-            .ok_or_else(|| anyhow!("key '{}' not found, in chain {:?}", n, names))
+    let v = names.iter().try_fold(v, |v, n| {
+        v.get(n)
+            .ok_or_else(|| NcmError::Json(format!("key '{}' not found, in chain {:?}", n, names)))
     })?;
-    Ok(T::dval(v)?)
+    T::dval(v)
+}
+
+/// 为 json 解析错误添加出错位置
+fn with_location(e: NcmError, file: &str, line: u32) -> NcmError {
+    match e {
+        NcmError::Json(msg) => NcmError::Json(format!("{}, at {}:{}", msg, file, line)),
+        e => e,
+    }
+}
+
+/// 接口返回的 code 非 200 时，根据 code 和 msg/message 生成错误
+fn api_error(value: &Value) -> NcmError {
+    let code = value
+        .get("code")
+        .and_then(Value::as_i64)
+        .unwrap_or_default();
+    let msg = value
+        .get("msg")
+        .or_else(|| value.get("message"))
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    NcmError::from_code(code, msg.to_string())
 }
 
 macro_rules! get_val {
     (@as $t:ty, $v:expr, $($n:expr),+) => {
-        get_val_chain::<$t>($v, &[$($n),+]).context(format!("at {}:{}", file!(), line!()))
+        get_val_chain::<$t>($v, &[$($n),+]).map_err(|e| with_location(e, file!(), line!()))
     };
     ($v:expr, $($n:expr),+) => {
-        get_val_chain($v, &[$($n),+]).context(format!("at {}:{}", file!(), line!()))
+        get_val_chain($v, &[$($n),+]).map_err(|e| with_location(e, file!(), line!()))
     };
 }
 
@@ -90,6 +112,12 @@ pub struct Lyrics {
     pub lyric: Vec<String>,
     /// 歌词翻译
     pub tlyric: Vec<String>,
+    /// 逐字歌词（yrc 或 klyric）
+    #[serde(default)]
+    pub yrc: Vec<String>,
+    /// 音译歌词（罗马音）
+    #[serde(default)]
+    pub romalrc: Vec<String>,
 }
 
 #[allow(unused)]
@@ -97,6 +125,10 @@ pub fn to_lyric(json: String) -> Result<Lyrics> {
     let value = &serde_json::from_str::<Value>(&json)?;
     let code: i64 = get_val!(value, "code")?;
     if code == 200 {
+        // 纯音乐、暂无歌词的歌曲没有 lrc 字段
+        if value.get("lrc").and_then(|lrc| lrc.get("lyric")).is_none() {
+            return Err(NcmError::LyricNotFound);
+        }
         let mut lyric: Vec<String> = Vec::new();
         let lrc: String = get_val!(value, "lrc", "lyric")?;
         lyric = lrc
@@ -115,9 +147,31 @@ pub fn to_lyric(json: String) -> Result<Lyrics> {
             .map(|s| (*s).to_string())
             .filter(|s| !s.is_empty())
             .collect::<Vec<String>>();
-        return Ok(Lyrics { lyric, tlyric });
+        // 逐字歌词优先使用 yrc，其次 klyric，大部分歌曲都没有
+        let mut lrc: String = get_val!(value, "yrc", "lyric").unwrap_or_default();
+        if lrc.is_empty() {
+            lrc = get_val!(value, "klyric", "lyric").unwrap_or_default();
+        }
+        let yrc = lrc
+            .split('\n')
+            .map(|s| s.to_string())
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<String>>();
+        // 音译歌词，仅部分日语、粤语等歌曲有
+        let lrc: String = get_val!(value, "romalrc", "lyric").unwrap_or_default();
+        let romalrc = lrc
+            .split('\n')
+            .map(|s| s.to_string())
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<String>>();
+        return Ok(Lyrics {
+            lyric,
+            tlyric,
+            yrc,
+            romalrc,
+        });
     }
-    Err(anyhow!("none"))
+    Err(api_error(value))
 }
 
 /// 歌手信息
@@ -151,7 +205,69 @@ pub fn to_singer_info(json: String) -> Result<Vec<SingerInfo>> {
         }
         return Ok(vec);
     }
-    Err(anyhow!("none"))
+    Err(api_error(value))
+}
+
+/// 解析歌曲中的歌手列表（"ar" 或 "artists" 字段）
+fn to_singers(array: &[Value]) -> Vec<SingerInfo> {
+    array
+        .iter()
+        .map(|v| SingerInfo {
+            id: get_val!(v, "id").unwrap_or_default(),
+            name: get_val!(v, "name").unwrap_or_else(|_| "unknown".to_owned()),
+            pic_url: String::new(),
+        })
+        .collect()
+}
+
+/// 以 "/" 连接歌手名
+fn join_singer_names(singers: &[SingerInfo]) -> String {
+    if singers.is_empty() {
+        return "unknown".to_owned();
+    }
+    singers
+        .iter()
+        .map(|singer| singer.name.as_str())
+        .collect::<Vec<&str>>()
+        .join("/")
+}
+
+/// 歌手详情
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SingerDetail {
+    /// 歌手 id
+    pub id: u64,
+    /// 歌手姓名
+    pub name: String,
+    /// 歌手照片
+    pub pic_url: String,
+    /// 歌手简介
+    pub brief_desc: String,
+    /// 专辑数
+    pub album_size: u64,
+    /// 单曲数
+    pub music_size: u64,
+    /// MV 数
+    pub mv_size: u64,
+}
+
+#[allow(unused)]
+pub fn to_singer_detail(json: String) -> Result<SingerDetail> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i64 = get_val!(value, "code")?;
+    if code == 200 {
+        let artist: &Value = get_val!(value, "data", "artist")?;
+        return Ok(SingerDetail {
+            id: get_val!(artist, "id")?,
+            name: get_val!(artist, "name")?,
+            pic_url: get_val!(artist, "cover").unwrap_or_default(),
+            brief_desc: get_val!(artist, "briefDesc").unwrap_or_default(),
+            album_size: get_val!(artist, "albumSize").unwrap_or_default(),
+            music_size: get_val!(artist, "musicSize").unwrap_or_default(),
+            mv_size: get_val!(artist, "mvSize").unwrap_or_default(),
+        });
+    }
+    Err(api_error(value))
 }
 
 /// 歌曲 URL
@@ -161,8 +277,87 @@ pub struct SongUrl {
     pub id: u64,
     /// 歌曲 URL
     pub url: String,
-    /// 码率
+    /// 码率，单位: bps
     pub rate: u32,
+    /// 文件大小，单位: byte
+    pub size: u64,
+    /// 编码格式（如 mp3、flac）
+    pub codec: String,
+}
+
+/// 音质
+/// 实际获取到的音质可能低于所选音质（受版权与会员限制），以 SongUrl 为准
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SongQuality {
+    /// 标准
+    Standard,
+    /// 较高
+    Higher,
+    /// 极高
+    Exhigh,
+    /// 无损
+    #[default]
+    Lossless,
+    /// Hi-Res
+    Hires,
+}
+
+impl SongQuality {
+    /// 获取歌曲 url 时请求的码率
+    pub fn rate(&self) -> u32 {
+        match self {
+            SongQuality::Standard => 128000,
+            SongQuality::Higher => 192000,
+            SongQuality::Exhigh => 320000,
+            SongQuality::Lossless => 999000,
+            SongQuality::Hires => 1999000,
+        }
+    }
+}
+
+impl fmt::Display for SongQuality {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SongQuality::Standard => write!(f, "标准"),
+            SongQuality::Higher => write!(f, "较高"),
+            SongQuality::Exhigh => write!(f, "极高"),
+            SongQuality::Lossless => write!(f, "无损"),
+            SongQuality::Hires => write!(f, "Hi-Res"),
+        }
+    }
+}
+
+/// 响度均衡模式
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NormalizeMode {
+    /// 不调整
+    #[default]
+    Off,
+    /// 按单曲响度调整
+    Track,
+    /// 按专辑响度调整，保留专辑内歌曲的响度差异
+    Album,
+}
+
+impl fmt::Display for NormalizeMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NormalizeMode::Off => write!(f, "关闭"),
+            NormalizeMode::Track => write!(f, "单曲"),
+            NormalizeMode::Album => write!(f, "专辑"),
+        }
+    }
+}
+
+/// 响度分析结果（ReplayGain）
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct Loudness {
+    /// 调整到参考响度所需的增益，单位: dB
+    pub gain: f64,
+    /// 峰值，1.0 为满幅
+    pub peak: f64,
 }
 
 #[allow(unused)]
@@ -179,12 +374,14 @@ pub fn to_song_url(json: String) -> Result<Vec<SongUrl>> {
                     id: get_val!(v, "id")?,
                     url,
                     rate: get_val!(v, "br")?,
+                    size: get_val!(v, "size").unwrap_or_default(),
+                    codec: get_val!(v, "type").unwrap_or_default(),
                 });
             }
         }
         return Ok(vec);
     }
-    Err(anyhow!("none"))
+    Err(api_error(value))
 }
 
 #[derive(Debug, PartialEq, Eq, Deserialize, Serialize, Clone)]
@@ -236,8 +433,11 @@ pub struct SongInfo {
     pub id: u64,
     /// 歌名
     pub name: String,
-    /// 歌手
+    /// 歌手，多位歌手时以 "/" 连接
     pub singer: String,
+    /// 歌手列表（含歌手 id），云盘、电台等来源为空
+    #[serde(default)]
+    pub singers: Vec<SingerInfo>,
     /// 专辑
     pub album: String,
     /// 专辑 ID
@@ -275,15 +475,13 @@ pub fn to_song_info(json: String, parse: Parse) -> Result<Vec<SongInfo>> {
                     array = get_val!(value, "playlist", "tracks")?;
                 }
                 for v in array.iter() {
-                    let ar: &Vec<Value> = get_val!(v, "ar")?;
+                    let singers = to_singers(get_val!(@as &Vec<Value>, v, "ar")?);
 
                     vec.push(SongInfo {
                         id: get_val!(v, "id")?,
                         name: get_val!(v, "name")?,
-                        singer: get_val!(@as &Vec<Value>, v, "ar")?
-                            .first()
-                            .map(|v: &Value| get_val!(v, "name").unwrap_or_else(|_| unk.clone()))
-                            .unwrap_or_else(|| unk.clone()),
+                        singer: join_singer_names(&singers),
+                        singers,
                         album: get_val!(v, "al", "name").unwrap_or_else(|_| unk.clone()),
                         album_id: get_val!(v, "al", "id")?,
                         pic_url: get_val!(v, "al", "picUrl").unwrap_or_default(),
@@ -301,6 +499,7 @@ pub fn to_song_info(json: String, parse: Parse) -> Result<Vec<SongInfo>> {
                         id: get_val!(v, "songId")?,
                         name: get_val!(v, "songName")?,
                         singer: get_val!(v, "artist").unwrap_or_else(|_| unk.clone()),
+                        singers: Vec::new(),
                         album: get_val!(v, "album").unwrap_or_else(|_| unk.clone()),
                         album_id: 0,
                         pic_url: String::new(),
@@ -313,13 +512,12 @@ pub fn to_song_info(json: String, parse: Parse) -> Result<Vec<SongInfo>> {
             Parse::Rmd => {
                 let array: &Vec<Value> = get_val!(value, "data")?;
                 for v in array.iter() {
+                    let singers = to_singers(get_val!(@as &Vec<Value>, v, "artists")?);
                     vec.push(SongInfo {
                         id: get_val!(v, "id")?,
                         name: get_val!(v, "name")?,
-                        singer: get_val!(@as &Vec<Value>, v, "artists")?
-                            .first()
-                            .map(|v: &Value| get_val!(v, "name").unwrap_or_else(|_| unk.clone()))
-                            .unwrap_or_else(|| unk.clone()),
+                        singer: join_singer_names(&singers),
+                        singers,
                         album: get_val!(v, "album", "name").unwrap_or_else(|_| unk.clone()),
                         album_id: get_val!(v, "album", "id")?,
                         pic_url: get_val!(v, "album", "picUrl").unwrap_or_default(),
@@ -332,13 +530,12 @@ pub fn to_song_info(json: String, parse: Parse) -> Result<Vec<SongInfo>> {
             Parse::Rmds => {
                 let array: &Vec<Value> = get_val!(value, "data", "dailySongs")?;
                 for v in array.iter() {
+                    let singers = to_singers(get_val!(@as &Vec<Value>, v, "artists")?);
                     vec.push(SongInfo {
                         id: get_val!(v, "id")?,
                         name: get_val!(v, "name")?,
-                        singer: get_val!(@as &Vec<Value>, v, "artists")?
-                            .first()
-                            .map(|v: &Value| get_val!(v, "name").unwrap_or_else(|_| unk.clone()))
-                            .unwrap_or_else(|| unk.clone()),
+                        singer: join_singer_names(&singers),
+                        singers,
                         album: get_val!(v, "album", "name").unwrap_or_else(|_| unk.clone()),
                         album_id: get_val!(v, "album", "id")?,
                         pic_url: get_val!(v, "album", "picUrl").unwrap_or_default(),
@@ -351,13 +548,12 @@ pub fn to_song_info(json: String, parse: Parse) -> Result<Vec<SongInfo>> {
             Parse::Search => {
                 let array: &Vec<Value> = get_val!(value, "result", "songs")?;
                 for v in array.iter() {
+                    let singers = to_singers(get_val!(@as &Vec<Value>, v, "artists")?);
                     vec.push(SongInfo {
                         id: get_val!(v, "id")?,
                         name: get_val!(v, "name")?,
-                        singer: get_val!(@as &Vec<Value>, v, "artists")?
-                            .first()
-                            .map(|v: &Value| get_val!(v, "name").unwrap_or_else(|_| unk.clone()))
-                            .unwrap_or_else(|| unk.clone()),
+                        singer: join_singer_names(&singers),
+                        singers,
                         album: get_val!(v, "album", "name").unwrap_or_else(|_| unk.clone()),
                         album_id: get_val!(v, "album", "id")?,
                         pic_url: get_val!(v, "album", "picUrl").unwrap_or_default(),
@@ -371,13 +567,12 @@ pub fn to_song_info(json: String, parse: Parse) -> Result<Vec<SongInfo>> {
                 let array: &Vec<Value> = get_val!(value, "songs")?;
                 for v in array.iter() {
                     let duration: u32 = get_val!(v, "dt")?;
+                    let singers = to_singers(get_val!(@as &Vec<Value>, v, "ar")?);
                     vec.push(SongInfo {
                         id: get_val!(v, "id")?,
                         name: get_val!(v, "name")?,
-                        singer: get_val!(@as &Vec<Value>, v, "ar")?
-                            .first()
-                            .map(|v: &Value| get_val!(v, "name").unwrap_or_else(|_| unk.clone()))
-                            .unwrap_or_else(|| unk.clone()),
+                        singer: join_singer_names(&singers),
+                        singers,
                         album: get_val!(value, "album", "name").unwrap_or_else(|_| unk.clone()),
                         album_id: get_val!(value, "album", "id")?,
                         pic_url: get_val!(value, "album", "picUrl").unwrap_or_default(),
@@ -390,10 +585,12 @@ pub fn to_song_info(json: String, parse: Parse) -> Result<Vec<SongInfo>> {
             Parse::Singer => {
                 let array: &Vec<Value> = get_val!(value, "hotSongs")?;
                 for v in array.iter() {
+                    let singers = to_singers(get_val!(@as &Vec<Value>, v, "ar")?);
                     vec.push(SongInfo {
                         id: get_val!(v, "id")?,
                         name: get_val!(v, "name")?,
-                        singer: get_val!(value, "artist", "name")?,
+                        singer: join_singer_names(&singers),
+                        singers,
                         album: get_val!(v, "al", "name").unwrap_or_else(|_| unk.clone()),
                         album_id: get_val!(v, "al", "id")?,
                         pic_url: String::new(),
@@ -406,13 +603,12 @@ pub fn to_song_info(json: String, parse: Parse) -> Result<Vec<SongInfo>> {
             Parse::SingerSongs => {
                 let array: &Vec<Value> = get_val!(value, "songs")?;
                 for v in array.iter() {
+                    let singers = to_singers(get_val!(@as &Vec<Value>, v, "ar")?);
                     vec.push(SongInfo {
                         id: get_val!(v, "id")?,
                         name: get_val!(v, "name")?,
-                        singer: get_val!(@as &Vec<Value>, v, "ar")?
-                            .first()
-                            .map(|v: &Value| get_val!(v, "name").unwrap_or_else(|_| unk.clone()))
-                            .unwrap_or_else(|| unk.clone()),
+                        singer: join_singer_names(&singers),
+                        singers,
                         album: get_val!(v, "al", "name").unwrap_or_else(|_| unk.clone()),
                         album_id: get_val!(v, "al", "id")?,
                         pic_url: String::new(),
@@ -430,6 +626,7 @@ pub fn to_song_info(json: String, parse: Parse) -> Result<Vec<SongInfo>> {
                         id: get_val!(v, "mainTrackId")?,
                         name: get_val!(v, "name")?,
                         singer: format!("第 {} 期", num),
+                        singers: Vec::new(),
                         album: get_val!(@as u64, v, "createTime")?.to_string(),
                         album_id: 0,
                         pic_url: get_val!(v, "coverUrl")?,
@@ -443,13 +640,12 @@ pub fn to_song_info(json: String, parse: Parse) -> Result<Vec<SongInfo>> {
             Parse::Intelligence => {
                 let array: &Vec<Value> = get_val!(value, "data")?;
                 for v in array.iter() {
+                    let singers = to_singers(get_val!(@as &Vec<Value>, v, "songInfo", "ar")?);
                     vec.push(SongInfo {
                         id: get_val!(v, "id")?,
                         name: get_val!(v, "songInfo", "name")?,
-                        singer: get_val!(@as &Vec<Value>, v, "songInfo", "ar")?
-                            .first()
-                            .map(|v: &Value| get_val!(v, "name").unwrap_or_else(|_| unk.clone()))
-                            .unwrap_or_else(|| unk.clone()),
+                        singer: join_singer_names(&singers),
+                        singers,
                         album: get_val!(v, "songInfo", "al", "name")
                             .unwrap_or_else(|_| unk.clone()),
                         album_id: get_val!(v, "songInfo", "al", "id")?,
@@ -464,7 +660,7 @@ pub fn to_song_info(json: String, parse: Parse) -> Result<Vec<SongInfo>> {
         }
         return Ok(vec);
     }
-    Err(anyhow!("none"))
+    Err(api_error(value))
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -495,15 +691,13 @@ pub fn to_mix_detail(json: &Value) -> Result<PlayListDetail> {
         }
         let array_privilege: &Vec<Value> = get_val!(value, "privileges")?;
         for (v, p) in array.iter().zip(array_privilege.iter()) {
-            let ar: &Vec<Value> = get_val!(v, "ar")?;
+            let singers = to_singers(get_val!(@as &Vec<Value>, v, "ar")?);
 
             songs.push(SongInfo {
                 id: get_val!(v, "id")?,
                 name: get_val!(v, "name")?,
-                singer: get_val!(@as &Vec<Value>, v, "ar")?
-                    .first()
-                    .map(|v: &Value| get_val!(v, "name").unwrap_or_else(|_| unk.clone()))
-                    .unwrap_or_else(|| unk.clone()),
+                singer: join_singer_names(&singers),
+                singers,
                 album: get_val!(v, "al", "name").unwrap_or_else(|_| unk.clone()),
                 album_id: get_val!(v, "al", "id")?,
                 pic_url: get_val!(v, "al", "picUrl").unwrap_or_default(),
@@ -524,7 +718,7 @@ pub fn to_mix_detail(json: &Value) -> Result<PlayListDetail> {
             songs,
         });
     }
-    Err(anyhow!("none"))
+    Err(api_error(value))
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -555,13 +749,12 @@ pub fn to_album_detail(json: &Value) -> Result<AlbumDetail> {
         let mut songs: Vec<SongInfo> = Vec::new();
         let array: &Vec<Value> = get_val!(value, "songs")?;
         for v in array.iter() {
+            let singers = to_singers(get_val!(@as &Vec<Value>, v, "ar")?);
             songs.push(SongInfo {
                 id: get_val!(v, "id")?,
                 name: get_val!(v, "name")?,
-                singer: get_val!(@as &Vec<Value>, v, "ar")?
-                    .first()
-                    .map(|v: &Value| get_val!(v, "name").unwrap_or_else(|_| unk.clone()))
-                    .unwrap_or_else(|| unk.clone()),
+                singer: join_singer_names(&singers),
+                singers,
                 album: name.clone(),
                 album_id: id,
                 pic_url: pic_url.clone(),
@@ -585,7 +778,7 @@ pub fn to_album_detail(json: &Value) -> Result<AlbumDetail> {
             songs,
         });
     }
-    Err(anyhow!("none"))
+    Err(api_error(value))
 }
 
 /// 歌单信息
@@ -599,13 +792,19 @@ pub struct SongList {
     pub cover_img_url: String,
     /// 歌单作者
     pub author: String,
+    /// 是否为收藏的歌单（非用户创建）
+    pub subscribed: bool,
 }
 
 /// parse: 解析方式
 #[allow(unused)]
 pub fn to_song_list(json: String, parse: Parse) -> Result<Vec<SongList>> {
     let value = serde_json::from_str::<Value>(&json)?;
-    if value.get("code").ok_or_else(|| anyhow!("none"))?.eq(&200) {
+    if value
+        .get("code")
+        .ok_or_else(|| NcmError::Json(String::from("key 'code' not found")))?
+        .eq(&200)
+    {
         let mut vec: Vec<SongList> = Vec::new();
         match parse {
             Parse::Usl => {
@@ -616,6 +815,7 @@ pub fn to_song_list(json: String, parse: Parse) -> Result<Vec<SongList>> {
                         name: get_val!(v, "name")?,
                         cover_img_url: get_val!(v, "coverImgUrl")?,
                         author: get_val!(v, "creator", "nickname")?,
+                        subscribed: get_val!(v, "subscribed").unwrap_or(false),
                     });
                 }
             }
@@ -627,6 +827,7 @@ pub fn to_song_list(json: String, parse: Parse) -> Result<Vec<SongList>> {
                         name: get_val!(v, "name")?,
                         cover_img_url: get_val!(v, "picUrl").unwrap_or_default(),
                         author: get_val!(v, "creator", "nickname")?,
+                        subscribed: false,
                     });
                 }
             }
@@ -638,6 +839,7 @@ pub fn to_song_list(json: String, parse: Parse) -> Result<Vec<SongList>> {
                         name: get_val!(v, "name")?,
                         cover_img_url: get_val!(v, "picUrl")?,
                         author: get_val!(v, "artist", "name")?,
+                        subscribed: false,
                     });
                 }
             }
@@ -649,6 +851,7 @@ pub fn to_song_list(json: String, parse: Parse) -> Result<Vec<SongList>> {
                         name: get_val!(v, "name")?,
                         cover_img_url: get_val!(v, "coverImgUrl")?,
                         author: get_val!(v, "creator", "nickname")?,
+                        subscribed: false,
                     });
                 }
             }
@@ -660,6 +863,7 @@ pub fn to_song_list(json: String, parse: Parse) -> Result<Vec<SongList>> {
                         name: get_val!(v, "name")?,
                         cover_img_url: get_val!(v, "coverImgUrl")?,
                         author: get_val!(v, "creator", "nickname")?,
+                        subscribed: false,
                     });
                 }
             }
@@ -671,6 +875,19 @@ pub fn to_song_list(json: String, parse: Parse) -> Result<Vec<SongList>> {
                         name: get_val!(v, "name")?,
                         cover_img_url: get_val!(v, "picUrl")?,
                         author: get_val!(v, "artist", "name")?,
+                        subscribed: false,
+                    });
+                }
+            }
+            Parse::SingerAlbum => {
+                let array: &Vec<Value> = get_val!(&value, "hotAlbums")?;
+                for v in array.iter() {
+                    vec.push(SongList {
+                        id: get_val!(v, "id")?,
+                        name: get_val!(v, "name")?,
+                        cover_img_url: get_val!(v, "picUrl")?,
+                        author: get_val!(v, "artist", "name")?,
+                        subscribed: false,
                     });
                 }
             }
@@ -684,6 +901,7 @@ pub fn to_song_list(json: String, parse: Parse) -> Result<Vec<SongList>> {
                         author: get_val!(@as &Vec<Value>, v, "artists")?
                             .first()
                             .map_or(Ok(String::new()), |v: &Value| get_val!(v, "name"))?,
+                        subscribed: true,
                     });
                 }
             }
@@ -695,6 +913,7 @@ pub fn to_song_list(json: String, parse: Parse) -> Result<Vec<SongList>> {
                         name: get_val!(v, "name")?,
                         cover_img_url: get_val!(v, "picUrl")?,
                         author: get_val!(v, "dj", "nickname")?,
+                        subscribed: false,
                     });
                 }
             }
@@ -702,7 +921,7 @@ pub fn to_song_list(json: String, parse: Parse) -> Result<Vec<SongList>> {
         }
         return Ok(vec);
     }
-    Err(anyhow!("none"))
+    Err(api_error(&value))
 }
 
 #[allow(unused)]
@@ -713,7 +932,7 @@ pub fn to_song_id_list(json: String) -> Result<Vec<u64>> {
         let id_array: &Vec<Value> = get_val!(value, "ids")?;
         return id_array.iter().map(u64::dval).collect();
     }
-    Err(anyhow!("none"))
+    Err(api_error(value))
 }
 
 /// 消息
@@ -733,7 +952,7 @@ pub fn to_msg(json: String) -> Result<Msg> {
             msg: "".to_owned(),
         });
     }
-    let msg = get_val!(value, "msg")?;
+    let msg = get_val!(value, "msg").unwrap_or_default();
     Ok(Msg { code, msg })
 }
 
@@ -748,7 +967,7 @@ pub fn to_message(json: String) -> Result<Msg> {
         });
     }
 
-    let msg = get_val!(value, "message")?;
+    let msg = get_val!(value, "message").unwrap_or_default();
     Ok(Msg { code, msg })
 }
 
@@ -806,6 +1025,9 @@ pub struct BannersInfo {
     pub target_id: u64,
     /// 内容类型
     pub target_type: TargetType,
+    /// 类型标题（如“新歌首发”“独家”）
+    #[serde(default)]
+    pub type_title: String,
 }
 
 #[allow(unused)]
@@ -820,12 +1042,13 @@ pub fn to_banners_info(json: String) -> Result<Vec<BannersInfo>> {
                 pic: get_val!(v, "imageUrl")?,
                 target_id: get_val!(v, "targetId")?,
                 target_type: TargetType::from(get_val!(@as i32, v, "targetType")?),
+                type_title: get_val!(v, "typeTitle").unwrap_or_default(),
             };
             vec.push(bi);
         }
         return Ok(vec);
     }
-    Err(anyhow!("none"))
+    Err(api_error(value))
 }
 
 #[allow(unused)]
@@ -839,7 +1062,7 @@ pub fn to_captcha(json: String) -> Result<()> {
     if data {
         return Ok(());
     }
-    Err(anyhow!("get captcha err!"))
+    Err(api_error(value))
 }
 
 #[allow(unused)]
@@ -851,7 +1074,7 @@ pub fn to_unikey(json: String) -> Result<String> {
         let unikey: String = get_val!(value, "unikey")?;
         return Ok(unikey);
     }
-    Err(anyhow!("get unikey err!"))
+    Err(api_error(value))
 }
 
 /// 轮播信息
@@ -888,7 +1111,65 @@ pub fn to_toplist(json: String) -> Result<Vec<TopList>> {
         }
         return Ok(toplist);
     }
-    Err(anyhow!("get toplist err!"))
+    Err(api_error(value))
+}
+
+/// 云盘上传检查结果
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CloudUploadCheck {
+    /// 是否需要上传文件（云端已有相同文件时无需上传）
+    pub need_upload: bool,
+    /// 云盘歌曲 id
+    pub song_id: String,
+}
+
+#[allow(unused)]
+pub fn to_cloud_upload_check(json: String) -> Result<CloudUploadCheck> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i32 = get_val!(value, "code")?;
+    if code == 200 {
+        return Ok(CloudUploadCheck {
+            need_upload: get_val!(value, "needUpload")?,
+            song_id: get_val!(value, "songId")?,
+        });
+    }
+    Err(api_error(value))
+}
+
+/// NOS 上传凭证
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct NosToken {
+    /// 对象名
+    pub object_key: String,
+    /// 上传 token
+    pub token: String,
+    /// 资源 id
+    pub resource_id: u64,
+}
+
+#[allow(unused)]
+pub fn to_nos_token(json: String) -> Result<NosToken> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i32 = get_val!(value, "code")?;
+    if code == 200 {
+        return Ok(NosToken {
+            object_key: get_val!(value, "result", "objectKey")?,
+            token: get_val!(value, "result", "token")?,
+            resource_id: get_val!(value, "result", "resourceId")?,
+        });
+    }
+    Err(api_error(value))
+}
+
+/// 上传完成后提交歌曲信息，返回云盘歌曲 id
+#[allow(unused)]
+pub fn to_cloud_upload_info(json: String) -> Result<String> {
+    let value = &serde_json::from_str::<Value>(&json)?;
+    let code: i32 = get_val!(value, "code")?;
+    if code == 200 {
+        return get_val!(value, "songId");
+    }
+    Err(api_error(value))
 }
 
 /// 歌单详情动态
@@ -912,7 +1193,7 @@ pub fn to_songlist_detail_dynamic(json: String) -> Result<PlayListDetailDynamic>
             comment_count: get_val!(value, "commentCount")?,
         });
     }
-    Err(anyhow!("get songlist detail dynamic err!"))
+    Err(api_error(value))
 }
 
 /// 专辑详情动态
@@ -935,12 +1216,12 @@ pub fn to_album_detail_dynamic(json: String) -> Result<AlbumDetailDynamic> {
             comment_count: get_val!(value, "commentCount")?,
         });
     }
-    Err(anyhow!("get album detail dynamic err!"))
+    Err(api_error(value))
 }
 
 /// 请求方式
 #[allow(unused)]
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub enum Method {
     Post,
     Get,
@@ -957,6 +1238,8 @@ pub enum Method {
 /// LikeAlbum: 收藏的专辑
 /// TOP: 热门
 /// Singer: 歌手热门单曲
+/// SingerSongs: 歌手全部单曲
+/// SingerAlbum: 歌手专辑
 /// Dj: 电台
 /// Intelligence: 心动/智能
 #[allow(unused)]
@@ -974,6 +1257,7 @@ pub enum Parse {
     Top,
     Singer,
     SingerSongs,
+    SingerAlbum,
     Radio,
     Intelligence,
 }
@@ -0,0 +1,85 @@
+//
+// 请求超时与重试策略
+//
+use crate::error::NcmError;
+use std::time::Duration;
+
+/// 请求超时与重试策略
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// 单次请求超时
+    pub timeout: Duration,
+    /// 失败后的最大重试次数，不含首次请求
+    pub max_retries: u32,
+    /// 第一次重试前的等待时间，之后每次翻倍
+    pub initial_backoff: Duration,
+    /// 重试前等待时间的上限
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(10),
+            max_retries: 2,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(2),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// 第 retry 次重试（从 0 开始）前的等待时间
+    pub fn backoff(&self, retry: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.max_backoff)
+    }
+}
+
+/// 请求失败后是否可以重试，由调用方按接口是否有副作用指定
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Retry {
+    /// 只读接口，重复请求没有副作用，按 RetryPolicy 重试
+    Idempotent,
+    /// 会产生副作用的接口（喜欢、收藏、删除、登录等），只请求一次
+    Never,
+}
+
+/// 是否值得重试：网络错误（含超时）、服务端 5xx 与 429
+/// 接口返回的业务错误（未登录、无版权等）重试也不会成功
+pub(crate) fn is_retryable(e: &NcmError) -> bool {
+    match e {
+        NcmError::Network(_) => true,
+        NcmError::Http(status) => *status >= 500 || *status == 429,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_until_max() {
+        let policy = RetryPolicy {
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(500),
+            ..RetryPolicy::default()
+        };
+
+        let backoffs: Vec<u128> = (0..5)
+            .map(|retry| policy.backoff(retry).as_millis())
+            .collect();
+        assert_eq!(backoffs, vec![100, 200, 400, 500, 500]);
+    }
+
+    #[test]
+    fn retry_only_transient_errors() {
+        assert!(is_retryable(&NcmError::Http(502)));
+        assert!(is_retryable(&NcmError::Http(429)));
+        assert!(!is_retryable(&NcmError::Http(404)));
+        assert!(!is_retryable(&NcmError::AuthRequired));
+        assert!(!is_retryable(&NcmError::Json(String::new())));
+    }
+}
@@ -0,0 +1,156 @@
+//
+// 网络请求传输层
+//
+use crate::config::DEFAULT_MAX_CONNECTIONS;
+use crate::error::{NcmError, Result};
+use crate::retry::RetryPolicy;
+use isahc::cookies::CookieJar;
+use isahc::http::Request;
+use isahc::{prelude::*, HttpClient};
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Mutex;
+
+pub type TransportFuture<'a> = Pin<Box<dyn Future<Output = Result<TransportResponse>> + Send + 'a>>;
+
+/// 完整读取后的响应
+#[derive(Debug, Clone)]
+pub struct TransportResponse {
+    /// HTTP 状态码
+    pub status: u16,
+    /// 响应内容
+    pub body: Vec<u8>,
+}
+
+impl TransportResponse {
+    /// 状态码是否为 2xx
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+/// 发送 HTTP 请求的方式
+/// NcmApi 的所有请求都通过 Transport 发送，测试时可替换为 FixtureTransport
+pub trait Transport: Send + Sync {
+    /// 发送请求并读取完整响应，连接失败、超时等返回 NcmError::Network
+    fn send(&self, request: Request<Vec<u8>>) -> TransportFuture<'_>;
+
+    /// 保存登录状态的 cookie
+    fn cookie_jar(&self) -> Option<&CookieJar>;
+}
+
+/// 使用 isahc 发送请求
+pub struct IsahcTransport {
+    client: HttpClient,
+}
+
+impl IsahcTransport {
+    /// cookie_jar: 已保存的 cookie，为空时使用新的 cookie
+    /// proxy: 代理地址，见 NcmApi::set_proxy
+    pub fn new(cookie_jar: Option<CookieJar>, proxy: Option<&str>) -> Result<Self> {
+        // 未单独设置超时的请求使用默认策略的超时，NcmApi 的请求按 retry_policy 设置
+        let mut builder = HttpClient::builder()
+            .timeout(RetryPolicy::default().timeout)
+            .max_connections(DEFAULT_MAX_CONNECTIONS)
+            .cookies();
+        if let Some(cookie_jar) = cookie_jar {
+            builder = builder.cookie_jar(cookie_jar);
+        }
+        if let Some(proxy) = proxy {
+            builder = builder.proxy(Some(proxy.parse()?));
+        }
+
+        Ok(Self {
+            client: builder.build()?,
+        })
+    }
+}
+
+impl Transport for IsahcTransport {
+    fn send(&self, request: Request<Vec<u8>>) -> TransportFuture<'_> {
+        Box::pin(async move {
+            let mut response = self.client.send_async(request).await?;
+            let status = response.status().as_u16();
+            let mut body = Vec::new();
+            // 读取响应内容失败同样视为网络错误
+            response
+                .copy_to(&mut body)
+                .await
+                .map_err(isahc::Error::from)?;
+
+            Ok(TransportResponse { status, body })
+        })
+    }
+
+    fn cookie_jar(&self) -> Option<&CookieJar> {
+        self.client.cookie_jar()
+    }
+}
+
+/// 回放录制的响应，不访问网络，用于离线测试
+/// 按请求路径（不含地址和查询参数）匹配响应
+#[derive(Default)]
+pub struct FixtureTransport {
+    fixtures: Mutex<HashMap<String, VecDeque<TransportResponse>>>,
+    requests: Mutex<Vec<String>>,
+    cookie_jar: Option<CookieJar>,
+}
+
+impl FixtureTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 为 path 添加一个 200 响应
+    pub fn with_fixture(self, path: &str, body: &str) -> Self {
+        self.with_response(path, 200, body)
+    }
+
+    /// 为 path 添加一个响应
+    /// 同一 path 的多个响应按添加顺序依次返回，最后一个会一直重复返回
+    pub fn with_response(self, path: &str, status: u16, body: &str) -> Self {
+        self.fixtures
+            .lock()
+            .unwrap()
+            .entry(path.to_string())
+            .or_default()
+            .push_back(TransportResponse {
+                status,
+                body: body.as_bytes().to_vec(),
+            });
+        self
+    }
+
+    /// 使用 cookie，用于测试需要 csrf 的请求
+    pub fn with_cookie_jar(mut self, cookie_jar: CookieJar) -> Self {
+        self.cookie_jar = Some(cookie_jar);
+        self
+    }
+
+    /// 已收到的请求路径，按请求顺序
+    pub fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl Transport for FixtureTransport {
+    fn send(&self, request: Request<Vec<u8>>) -> TransportFuture<'_> {
+        let path = request.uri().path().to_string();
+        self.requests.lock().unwrap().push(path.clone());
+
+        let response = match self.fixtures.lock().unwrap().get_mut(&path) {
+            Some(responses) if responses.len() > 1 => responses.pop_front(),
+            Some(responses) => responses.front().cloned(),
+            None => None,
+        };
+
+        Box::pin(async move {
+            response.ok_or_else(|| NcmError::Other(format!("no fixture for {}", path)))
+        })
+    }
+
+    fn cookie_jar(&self) -> Option<&CookieJar> {
+        self.cookie_jar.as_ref()
+    }
+}
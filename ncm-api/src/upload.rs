@@ -0,0 +1,252 @@
+//
+// 云盘文件上传（NOS 分片上传）
+//
+use crate::error::{NcmError, Result};
+use crate::transport::Transport;
+use isahc::config::Configurable;
+use isahc::Request;
+use openssl::hash::{hash, MessageDigest};
+use serde_json::Value;
+use std::io::{Read, Seek, SeekFrom};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// 默认分片大小: 4MB
+pub const DEFAULT_CHUNK_SIZE: usize = 4 * 1024 * 1024;
+
+/// 单个分片的上传超时，比普通请求的超时长
+const CHUNK_TIMEOUT: Duration = Duration::from_secs(100);
+
+/// 上传进度
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct UploadProgress {
+    /// 已上传字节数
+    pub uploaded: u64,
+    /// 文件总字节数
+    pub total: u64,
+}
+
+impl UploadProgress {
+    /// 已上传比例，0.0 ~ 1.0
+    pub fn ratio(&self) -> f64 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.uploaded as f64 / self.total as f64
+        }
+    }
+}
+
+/// NOS 分片上传客户端
+/// 每个分片 POST 到 `{host}/{bucket}/{object_key}?offset=..&complete=..&version=1.0[&context=..]`，
+/// 服务端返回已接收的 offset 和续传用的 context，下一个分片从返回的 offset 处继续
+pub struct ChunkedUploader {
+    transport: Arc<dyn Transport>,
+    host: String,
+    chunk_size: usize,
+}
+
+impl ChunkedUploader {
+    /// transport: 复用 NcmApi 的 Transport
+    /// host: 上传服务器地址，如 `http://45.127.129.8`
+    pub fn new(transport: Arc<dyn Transport>, host: &str) -> Self {
+        Self {
+            transport,
+            host: host.trim_end_matches('/').to_string(),
+            chunk_size: DEFAULT_CHUNK_SIZE,
+        }
+    }
+
+    /// 设置分片大小
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// 分片上传
+    /// bucket: NOS bucket
+    /// object_key: 由 nos/token/alloc 分配的对象名
+    /// token: 由 nos/token/alloc 分配的上传 token
+    /// reader: 文件内容，每个分片上传前从中读取，不会一次读入整个文件
+    /// on_progress: 每个分片上传完成后回调
+    pub async fn upload<R, F>(
+        &self,
+        bucket: &str,
+        object_key: &str,
+        token: &str,
+        reader: &mut R,
+        mut on_progress: F,
+    ) -> Result<()>
+    where
+        R: Read + Seek,
+        F: FnMut(UploadProgress),
+    {
+        let total = reader.seek(SeekFrom::End(0))? as usize;
+        let mut offset = 0;
+        let mut context: Option<String> = None;
+        let mut chunk = Vec::with_capacity(self.chunk_size.min(total));
+
+        loop {
+            let end = (offset + self.chunk_size).min(total);
+            let complete = end == total;
+            chunk.clear();
+            reader.seek(SeekFrom::Start(offset as u64))?;
+            reader
+                .by_ref()
+                .take((end - offset) as u64)
+                .read_to_end(&mut chunk)?;
+            if chunk.len() != end - offset {
+                return Err(NcmError::Other(format!(
+                    "upload chunk failed: file truncated at {}",
+                    offset + chunk.len()
+                )));
+            }
+
+            let mut url = format!(
+                "{}/{}/{}?offset={}&complete={}&version=1.0",
+                self.host,
+                bucket,
+                object_key.replace('/', "%2F"),
+                offset,
+                complete
+            );
+            if let Some(context) = &context {
+                url.push_str(&format!("&context={}", context));
+            }
+
+            let request = Request::post(&url)
+                .header("x-nos-token", token)
+                .header(
+                    "Content-MD5",
+                    hex::encode(hash(MessageDigest::md5(), &chunk)?),
+                )
+                .header("Content-Type", "audio/mpeg")
+                .timeout(CHUNK_TIMEOUT)
+                .body(chunk.clone())?;
+            let response = self.transport.send(request).await?;
+            if !response.is_success() {
+                return Err(NcmError::Http(response.status));
+            }
+            let value = serde_json::from_slice::<Value>(&response.body)?;
+
+            // 以服务端确认的 offset 为准，未完整接收时从该处重传
+            let confirmed_offset = value
+                .get("offset")
+                .and_then(Value::as_u64)
+                .map(|confirmed_offset| confirmed_offset as usize)
+                .unwrap_or(end);
+            let no_progress = confirmed_offset == offset && !chunk.is_empty();
+            if confirmed_offset > total || confirmed_offset < offset || no_progress {
+                return Err(NcmError::Other(format!(
+                    "upload chunk failed: invalid offset {}",
+                    confirmed_offset
+                )));
+            }
+            if let Some(new_context) = value.get("context").and_then(Value::as_str) {
+                context = Some(new_context.to_string());
+            }
+            offset = confirmed_offset;
+
+            on_progress(UploadProgress {
+                uploaded: offset as u64,
+                total: total as u64,
+            });
+
+            if complete && offset == total {
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_server::{MockRequest, MockResponse, MockServer};
+    use crate::transport::IsahcTransport;
+    use std::io::Cursor;
+
+    /// 本地模拟上传服务器，返回的 offset 为已接收的总字节数
+    fn stand_in_server(requests: usize) -> MockServer {
+        let mut uploaded = 0;
+        let mut count = 0;
+        MockServer::with_handler(requests, move |request| {
+            uploaded += request.body_len;
+            let body = format!(r#"{{"offset":{},"context":"ctx-{}"}}"#, uploaded, count);
+            count += 1;
+            MockResponse::Reply(200, body)
+        })
+    }
+
+    fn request(path: &str, body_len: usize) -> MockRequest {
+        MockRequest {
+            path: path.to_string(),
+            body_len,
+        }
+    }
+
+    fn transport() -> Arc<dyn Transport> {
+        Arc::new(IsahcTransport::new(None, None).unwrap())
+    }
+
+    #[tokio::test]
+    async fn upload_in_chunks_with_context() {
+        let server = stand_in_server(3);
+        let uploader = ChunkedUploader::new(transport(), &server.url()).chunk_size(4);
+
+        let mut progress = Vec::new();
+        uploader
+            .upload(
+                "bucket",
+                "obj/key.mp3",
+                "token",
+                &mut Cursor::new(b"0123456789"),
+                |p| progress.push(p.uploaded),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(progress, vec![4, 8, 10]);
+
+        assert_eq!(
+            server.received(),
+            vec![
+                request(
+                    "/bucket/obj%2Fkey.mp3?offset=0&complete=false&version=1.0",
+                    4
+                ),
+                request(
+                    "/bucket/obj%2Fkey.mp3?offset=4&complete=false&version=1.0&context=ctx-0",
+                    4
+                ),
+                request(
+                    "/bucket/obj%2Fkey.mp3?offset=8&complete=true&version=1.0&context=ctx-1",
+                    2
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn upload_empty_file_in_one_request() {
+        let server = stand_in_server(1);
+        let uploader = ChunkedUploader::new(transport(), &server.url());
+
+        let mut progress = Vec::new();
+        uploader
+            .upload("bucket", "key", "token", &mut Cursor::new(b""), |p| {
+                progress.push(p)
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(
+            progress,
+            vec![UploadProgress {
+                uploaded: 0,
+                total: 0
+            }]
+        );
+        assert_eq!(server.received().len(), 1);
+    }
+}
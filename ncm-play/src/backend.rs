@@ -0,0 +1,659 @@
+//
+// 音频播放后端
+//
+use crate::event::{self, PlaybackState, PlayerEvent, PlayerEventReceiver};
+use anyhow::Result;
+use gstreamer::ClockTime;
+use gstreamer_play::gst::prelude::*;
+use gstreamer_play::{gst, Play, PlaySignalAdapter, PlayVideoRenderer};
+use log::warn;
+use ncm_api::Loudness;
+use std::collections::HashMap;
+use std::f64::consts::FRAC_PI_2;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+/// 交叉淡入淡出时调整音量的间隔，单位: ms
+const FADE_STEP: u64 = 50;
+
+/// 播放音频的方式
+/// Player 的所有播放操作都通过 AudioBackend 完成，测试时可替换为 FakeBackend
+pub trait AudioBackend: Send {
+    /// 切换播放的 uri，同时清除 set_next_uri 设置的下一首
+    fn set_uri(&mut self, uri: &str);
+
+    /// 设置当前歌曲结束后无缝接着播放的 uri，None 表示取消
+    /// 切换后发出 PlayerEvent::NextTrackStarted
+    fn set_next_uri(&mut self, uri: Option<&str>);
+
+    /// 在 duration 内淡出当前歌曲，同时淡入 uri 并立即切换到 uri
+    /// 之后的播放位置、时长和事件均为 uri 的，不发出 NextTrackStarted
+    fn crossfade_to(&mut self, uri: &str, duration: ClockTime);
+
+    fn play(&mut self);
+
+    fn pause(&mut self);
+
+    fn stop(&mut self);
+
+    fn seek(&mut self, position: ClockTime);
+
+    /// volume: 0.0 ~ 1.0
+    fn set_volume(&mut self, volume: f64);
+
+    /// 响度均衡的增益（dB），只作用于当前歌曲，与音量相互独立
+    fn set_gain(&mut self, gain: f64);
+
+    /// 开启或关闭响度均衡，关闭时不分析响度，也不限制增益后的峰值
+    fn set_normalize(&mut self, enabled: bool);
+
+    fn position(&self) -> Option<ClockTime>;
+
+    fn duration(&self) -> Option<ClockTime>;
+
+    /// 正在播放的 uri，无缝切换后为下一首的 uri
+    fn current_uri(&self) -> Option<String>;
+
+    /// 订阅播放事件（播放结束、出错等）
+    fn subscribe(&self) -> PlayerEventReceiver;
+}
+
+/// 使用 gstreamer_play 播放
+/// 包含两路 Play，交叉淡入淡出时两路同时播放，结束后淡出的一路停止，平时只使用当前一路
+pub struct GstBackend {
+    decks: [Deck; 2],
+    active: usize, // 当前一路的下标
+    event_sender: broadcast::Sender<PlayerEvent>,
+    next_track: Arc<Mutex<NextTrack>>,
+    mixer: Arc<Mutex<Mixer>>,
+}
+
+/// 一路 Play
+struct Deck {
+    play: Play,
+    _signal_adapter: PlaySignalAdapter, // 保持信号连接
+    active: Arc<AtomicBool>,            // 是否为当前一路，只有当前一路产生事件
+    gain: Option<gst::Element>,         // 响度均衡的增益，缺少 replaygain 插件时为 None
+    loudness_filter: Option<gst::Bin>,  // 响度分析、增益和限幅，开启响度均衡时才接入 playbin
+}
+
+/// 两路共用的音量和淡入淡出状态，淡入淡出的线程每一步都按当前音量调整
+struct Mixer {
+    volume: f64,
+    fade_generation: u64, // 每次开始或结束淡入淡出时加一，使进行中的淡入淡出停止
+    fading: bool,
+}
+
+/// 无缝播放的下一首，在 gstreamer 的线程中读写
+#[derive(Default)]
+struct NextTrack {
+    uri: Option<String>,
+    switching: bool, // 下一首已交给 playbin，等待开始播放
+    last_position: Option<ClockTime>,
+}
+
+impl GstBackend {
+    /// 初始化 GStreamer，未安装 GStreamer 时返回错误
+    pub fn new() -> Result<Self> {
+        gst::init()?;
+
+        let (event_sender, _) = broadcast::channel(event::EVENT_CHANNEL_CAPACITY);
+        let next_track = Arc::new(Mutex::new(NextTrack::default()));
+        let decks = [
+            Deck::new(&event_sender, &next_track, true)?,
+            Deck::new(&event_sender, &next_track, false)?,
+        ];
+
+        Ok(Self {
+            decks,
+            active: 0,
+            event_sender,
+            next_track,
+            mixer: Arc::new(Mutex::new(Mixer {
+                volume: 1.0,
+                fade_generation: 0,
+                fading: false,
+            })),
+        })
+    }
+
+    fn active_play(&self) -> &Play {
+        &self.decks[self.active].play
+    }
+
+    /// 停止进行中的淡入淡出：停止淡出的一路，当前一路恢复音量
+    fn finish_fade(&mut self) {
+        let mut mixer = self.mixer.lock().unwrap();
+        mixer.fade_generation += 1;
+        mixer.fading = false;
+        self.decks[1 - self.active].play.stop();
+        self.active_play().set_volume(mixer.volume);
+    }
+
+    /// 在新线程中按等功率曲线调整两路的音量，结束后停止淡出的一路
+    fn fade(
+        fade_out: Play,
+        fade_in: Play,
+        duration: ClockTime,
+        generation: u64,
+        mixer: Arc<Mutex<Mixer>>,
+    ) {
+        thread::spawn(move || {
+            let steps = (duration.mseconds() / FADE_STEP).max(1);
+            for step in 1..=steps {
+                thread::sleep(Duration::from_millis(FADE_STEP));
+
+                let mut mixer = mixer.lock().unwrap();
+                if mixer.fade_generation != generation {
+                    return;
+                }
+                let angle = step as f64 / steps as f64 * FRAC_PI_2;
+                fade_out.set_volume(mixer.volume * angle.cos());
+                fade_in.set_volume(mixer.volume * angle.sin());
+                if step == steps {
+                    fade_out.stop();
+                    mixer.fading = false;
+                }
+            }
+        });
+    }
+}
+
+impl Deck {
+    fn new(
+        sender: &broadcast::Sender<PlayerEvent>,
+        next_track: &Arc<Mutex<NextTrack>>,
+        active: bool,
+    ) -> Result<Self> {
+        let play = Play::new(None::<PlayVideoRenderer>);
+
+        let mut config = play.config();
+        config.set_user_agent(
+            "User-Agent: Mozilla/5.0 (X11; Linux x86_64; rv:100.0) Gecko/20100101 Firefox/100.0",
+        );
+        config.set_position_update_interval(250);
+        config.set_seek_accurate(true);
+        play.set_config(config)?;
+
+        let active = Arc::new(AtomicBool::new(active));
+        let signal_adapter = event::connect_signals(&play, sender, &active);
+        Self::connect_gapless(&play, &signal_adapter, &active, next_track, sender);
+
+        // 默认关闭响度均衡，filter 在 set_normalize 时接入
+        let loudness_filter = match Self::loudness_filter() {
+            Ok(filter) => {
+                Self::connect_loudness_analysis(&play, sender);
+                Some(filter)
+            }
+            Err(e) => {
+                warn!("Loudness normalization unavailable: {:?}", e);
+                None
+            }
+        };
+
+        Ok(Self {
+            play,
+            _signal_adapter: signal_adapter,
+            active,
+            gain: loudness_filter
+                .as_ref()
+                .and_then(|filter| filter.by_name("gain")),
+            loudness_filter,
+        })
+    }
+
+    /// rganalysis 分析响度，volume 调整增益，rglimiter 防止增益后削波
+    fn loudness_filter() -> Result<gst::Bin> {
+        Ok(gst::parse::bin_from_description(
+            "audioconvert ! rganalysis name=analysis ! volume name=gain ! rglimiter name=limiter ! audioconvert",
+            true,
+        )?)
+    }
+
+    /// 关闭时立即停止限幅，并从 playbin 移除整个 filter，下一次设置 uri 后不再分析响度
+    /// 开启时同样在下一次设置 uri 后接入
+    fn set_normalize(&self, enabled: bool) {
+        let Some(filter) = &self.loudness_filter else {
+            return;
+        };
+        if let Some(limiter) = filter.by_name("limiter") {
+            limiter.set_property("enabled", enabled);
+        }
+        self.play
+            .pipeline()
+            .set_property("audio-filter", enabled.then_some(filter));
+    }
+
+    /// rganalysis 在一首歌播放完时以 tag 消息给出分析结果
+    /// 淡出的一路也需要保存结果，不按 active 过滤
+    fn connect_loudness_analysis(play: &Play, sender: &broadcast::Sender<PlayerEvent>) {
+        let pipeline = play.pipeline();
+        let Some(bus) = pipeline.bus() else {
+            return;
+        };
+
+        let playbin = pipeline.downgrade();
+        let tx = sender.clone();
+        bus.connect_message(Some("tag"), move |_, message| {
+            let gst::MessageView::Tag(tag) = message.view() else {
+                return;
+            };
+            if !message.src().is_some_and(|src| src.name() == "analysis") {
+                return;
+            }
+
+            let tags = tag.tags();
+            if let (Some(gain), Some(peak), Some(playbin)) = (
+                tags.get::<gst::tags::TrackGain>(),
+                tags.get::<gst::tags::TrackPeak>(),
+                playbin.upgrade(),
+            ) {
+                if let Some(uri) = playbin.property::<Option<String>>("current-uri") {
+                    let _ = tx.send(PlayerEvent::LoudnessAnalyzed {
+                        uri,
+                        loudness: Loudness {
+                            gain: gain.get(),
+                            peak: peak.get(),
+                        },
+                    });
+                }
+            }
+        });
+    }
+
+    /// playbin 读完当前歌曲时（about-to-finish）设置下一首的 uri，实现无缝切换
+    /// 切换时 gstreamer_play 不会发出信号，播放位置回退即表示下一首已开始播放
+    fn connect_gapless(
+        play: &Play,
+        signal_adapter: &PlaySignalAdapter,
+        active: &Arc<AtomicBool>,
+        next_track: &Arc<Mutex<NextTrack>>,
+        sender: &broadcast::Sender<PlayerEvent>,
+    ) {
+        let is_active = active.clone();
+        let next = next_track.clone();
+        play.pipeline()
+            .connect("about-to-finish", false, move |values| {
+                if !is_active.load(Ordering::Relaxed) {
+                    return None;
+                }
+                let mut next = next.lock().unwrap();
+                if let (Some(uri), Ok(playbin)) = (next.uri.take(), values[0].get::<gst::Element>())
+                {
+                    playbin.set_property("uri", uri.as_str());
+                    next.switching = true;
+                }
+                None
+            });
+
+        let is_active = active.clone();
+        let next = next_track.clone();
+        let tx = sender.clone();
+        signal_adapter.connect_position_updated(move |_, position| {
+            if !is_active.load(Ordering::Relaxed) {
+                return;
+            }
+            let mut next = next.lock().unwrap();
+            if next.switching && position < next.last_position {
+                next.switching = false;
+                let _ = tx.send(PlayerEvent::NextTrackStarted);
+            }
+            next.last_position = position;
+        });
+    }
+}
+
+impl AudioBackend for GstBackend {
+    fn set_uri(&mut self, uri: &str) {
+        self.finish_fade();
+        *self.next_track.lock().unwrap() = NextTrack::default();
+        self.active_play().set_uri(Some(uri));
+    }
+
+    fn set_next_uri(&mut self, uri: Option<&str>) {
+        self.next_track.lock().unwrap().uri = uri.map(str::to_string);
+    }
+
+    fn crossfade_to(&mut self, uri: &str, duration: ClockTime) {
+        self.finish_fade();
+        *self.next_track.lock().unwrap() = NextTrack::default();
+
+        let from = self.active;
+        let to = 1 - from;
+        let fade_in = &self.decks[to];
+        fade_in.play.set_uri(Some(uri));
+        fade_in.play.set_volume(0.0);
+        fade_in.play.play();
+
+        // 之后只有淡入的一路产生事件
+        self.decks[from].active.store(false, Ordering::Relaxed);
+        self.decks[to].active.store(true, Ordering::Relaxed);
+        self.active = to;
+
+        let generation = {
+            let mut mixer = self.mixer.lock().unwrap();
+            mixer.fading = true;
+            mixer.fade_generation
+        };
+        Self::fade(
+            self.decks[from].play.clone(),
+            self.decks[to].play.clone(),
+            duration,
+            generation,
+            self.mixer.clone(),
+        );
+    }
+
+    fn play(&mut self) {
+        self.active_play().play();
+    }
+
+    fn pause(&mut self) {
+        self.finish_fade();
+        self.active_play().pause();
+    }
+
+    fn stop(&mut self) {
+        self.finish_fade();
+        self.active_play().stop();
+    }
+
+    fn seek(&mut self, position: ClockTime) {
+        self.finish_fade();
+        self.active_play().seek(position);
+    }
+
+    fn set_volume(&mut self, volume: f64) {
+        let mut mixer = self.mixer.lock().unwrap();
+        mixer.volume = volume;
+        // 淡入淡出中不打断，由淡入淡出的线程在下一步按新音量调整两路
+        if !mixer.fading {
+            self.active_play().set_volume(volume);
+        }
+    }
+
+    fn set_gain(&mut self, gain: f64) {
+        if let Some(element) = &self.decks[self.active].gain {
+            // volume 元素的范围为 0 ~ 10
+            element.set_property("volume", 10_f64.powf(gain / 20.0).min(10.0));
+        }
+    }
+
+    fn set_normalize(&mut self, enabled: bool) {
+        for deck in &self.decks {
+            deck.set_normalize(enabled);
+        }
+    }
+
+    fn position(&self) -> Option<ClockTime> {
+        self.active_play().position()
+    }
+
+    fn duration(&self) -> Option<ClockTime> {
+        self.active_play().duration()
+    }
+
+    fn current_uri(&self) -> Option<String> {
+        // Play 的 uri 属性在无缝切换后不变，使用 playbin 的 current-uri
+        self.active_play()
+            .pipeline()
+            .property::<Option<String>>("current-uri")
+    }
+
+    fn subscribe(&self) -> PlayerEventReceiver {
+        self.event_sender.subscribe()
+    }
+}
+
+/// 不输出声音的后端，播放位置只随 advance 推进，结果确定，用于测试
+/// clone 得到的 FakeBackend 共享同一状态，交给 Player 后仍可在外部控制时钟
+#[derive(Clone)]
+pub struct FakeBackend {
+    state: Arc<Mutex<FakeState>>,
+    event_sender: broadcast::Sender<PlayerEvent>,
+}
+
+#[derive(Default)]
+struct FakeState {
+    uri: Option<String>,
+    next_uri: Option<String>,
+    fading_out: Option<(String, u64)>, // 淡出中的 uri 和剩余淡出时间（ms）
+    playing: bool,
+    position: u64, // 单位: ms
+    duration: Option<u64>,
+    volume: f64,
+    gain: f64,
+    normalize: bool,
+    durations: HashMap<String, u64>,
+}
+
+impl Default for FakeBackend {
+    fn default() -> Self {
+        let (event_sender, _) = broadcast::channel(event::EVENT_CHANNEL_CAPACITY);
+        Self {
+            state: Arc::new(Mutex::new(FakeState::default())),
+            event_sender,
+        }
+    }
+}
+
+impl FakeBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置 uri 对应的歌曲时长（ms），之后 set_uri 时生效，未设置的歌曲时长未知
+    pub fn set_duration_for(&self, uri: &str, duration: u64) {
+        self.state
+            .lock()
+            .unwrap()
+            .durations
+            .insert(uri.to_string(), duration);
+    }
+
+    /// 推进时钟（ms），播放中时播放位置随之前进
+    /// 到达歌曲时长时，设置了下一首则无缝切换并发出 NextTrackStarted，否则发出 EndOfStream
+    pub fn advance(&self, millis: u64) {
+        let mut state = self.state.lock().unwrap();
+        if !state.playing {
+            return;
+        }
+
+        state.position += millis;
+        if let Some((_, remaining)) = &mut state.fading_out {
+            *remaining = remaining.saturating_sub(millis);
+            if *remaining == 0 {
+                state.fading_out = None;
+            }
+        }
+        let Some(duration) = state
+            .duration
+            .filter(|duration| state.position >= *duration)
+        else {
+            return;
+        };
+        if let Some(next_uri) = state.next_uri.take() {
+            state.position -= duration;
+            state.duration = state.durations.get(&next_uri).copied();
+            state.uri = Some(next_uri);
+            let next_duration = state.duration;
+            drop(state);
+            self.send(PlayerEvent::NextTrackStarted);
+            self.send(PlayerEvent::DurationChanged(
+                next_duration.map(ClockTime::from_mseconds),
+            ));
+        } else {
+            state.position = duration;
+            state.playing = false;
+            drop(state);
+            self.send(PlayerEvent::EndOfStream);
+        }
+    }
+
+    /// 模拟当前歌曲的响度分析完成，未开启响度均衡时不分析
+    pub fn analyze(&self, loudness: Loudness) {
+        if !self.state.lock().unwrap().normalize {
+            return;
+        }
+        if let Some(uri) = self.uri() {
+            self.send(PlayerEvent::LoudnessAnalyzed { uri, loudness });
+        }
+    }
+
+    /// 模拟播放出错（如链接失效）
+    pub fn fail(&self, message: &str) {
+        self.state.lock().unwrap().playing = false;
+        self.send(PlayerEvent::Error(message.to_string()));
+    }
+
+    /// 当前播放的 uri
+    pub fn uri(&self) -> Option<String> {
+        self.state.lock().unwrap().uri.clone()
+    }
+
+    /// 等待无缝播放的下一首 uri
+    pub fn next_uri(&self) -> Option<String> {
+        self.state.lock().unwrap().next_uri.clone()
+    }
+
+    /// 交叉淡入淡出中正在淡出的 uri
+    pub fn fading_out_uri(&self) -> Option<String> {
+        self.state
+            .lock()
+            .unwrap()
+            .fading_out
+            .as_ref()
+            .map(|(uri, _)| uri.clone())
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.state.lock().unwrap().playing
+    }
+
+    pub fn volume(&self) -> f64 {
+        self.state.lock().unwrap().volume
+    }
+
+    /// 响度均衡的增益（dB）
+    pub fn gain(&self) -> f64 {
+        self.state.lock().unwrap().gain
+    }
+
+    /// 是否开启响度均衡
+    pub fn normalize_enabled(&self) -> bool {
+        self.state.lock().unwrap().normalize
+    }
+
+    fn send(&self, event: PlayerEvent) {
+        let _ = self.event_sender.send(event);
+    }
+}
+
+impl AudioBackend for FakeBackend {
+    fn set_uri(&mut self, uri: &str) {
+        let duration = {
+            let mut state = self.state.lock().unwrap();
+            state.uri = Some(uri.to_string());
+            state.next_uri = None;
+            state.fading_out = None;
+            state.position = 0;
+            state.duration = state.durations.get(uri).copied();
+            state.duration
+        };
+        self.send(PlayerEvent::DurationChanged(
+            duration.map(ClockTime::from_mseconds),
+        ));
+    }
+
+    fn set_next_uri(&mut self, uri: Option<&str>) {
+        self.state.lock().unwrap().next_uri = uri.map(str::to_string);
+    }
+
+    fn crossfade_to(&mut self, uri: &str, duration: ClockTime) {
+        let next_duration = {
+            let mut state = self.state.lock().unwrap();
+            state.fading_out = state
+                .uri
+                .replace(uri.to_string())
+                .map(|from| (from, duration.mseconds()));
+            state.next_uri = None;
+            state.position = 0;
+            state.playing = true;
+            state.duration = state.durations.get(uri).copied();
+            state.duration
+        };
+        self.send(PlayerEvent::DurationChanged(
+            next_duration.map(ClockTime::from_mseconds),
+        ));
+        self.send(PlayerEvent::StateChanged(PlaybackState::Playing));
+    }
+
+    fn play(&mut self) {
+        self.state.lock().unwrap().playing = true;
+        self.send(PlayerEvent::StateChanged(PlaybackState::Playing));
+    }
+
+    fn pause(&mut self) {
+        {
+            let mut state = self.state.lock().unwrap();
+            state.playing = false;
+            state.fading_out = None;
+        }
+        self.send(PlayerEvent::StateChanged(PlaybackState::Paused));
+    }
+
+    fn stop(&mut self) {
+        {
+            let mut state = self.state.lock().unwrap();
+            state.playing = false;
+            state.position = 0;
+            state.fading_out = None;
+        }
+        self.send(PlayerEvent::StateChanged(PlaybackState::Stopped));
+    }
+
+    fn seek(&mut self, position: ClockTime) {
+        let mut state = self.state.lock().unwrap();
+        state.position = position.mseconds();
+        state.fading_out = None;
+    }
+
+    fn set_volume(&mut self, volume: f64) {
+        self.state.lock().unwrap().volume = volume;
+    }
+
+    fn set_gain(&mut self, gain: f64) {
+        self.state.lock().unwrap().gain = gain;
+    }
+
+    fn set_normalize(&mut self, enabled: bool) {
+        self.state.lock().unwrap().normalize = enabled;
+    }
+
+    fn position(&self) -> Option<ClockTime> {
+        let state = self.state.lock().unwrap();
+        state
+            .uri
+            .as_ref()
+            .map(|_| ClockTime::from_mseconds(state.position))
+    }
+
+    fn duration(&self) -> Option<ClockTime> {
+        self.state
+            .lock()
+            .unwrap()
+            .duration
+            .map(ClockTime::from_mseconds)
+    }
+
+    fn current_uri(&self) -> Option<String> {
+        self.uri()
+    }
+
+    fn subscribe(&self) -> PlayerEventReceiver {
+        self.event_sender.subscribe()
+    }
+}
@@ -0,0 +1,90 @@
+//
+// 播放器事件
+//
+use gstreamer::ClockTime;
+use gstreamer_play::{Play, PlaySignalAdapter, PlayState as GstPlayState};
+use ncm_api::Loudness;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+/// 事件通道容量，订阅方处理不及时会丢弃最旧的事件
+pub(crate) const EVENT_CHANNEL_CAPACITY: usize = 64;
+
+pub type PlayerEventReceiver = broadcast::Receiver<PlayerEvent>;
+
+/// 播放器事件，由播放后端（gstreamer_play 的信号）产生
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlayerEvent {
+    /// 一首歌播放结束
+    EndOfStream,
+    /// set_next_uri 设置的下一首已无缝接着播放
+    NextTrackStarted,
+    /// 播放出错（链接失效、解码失败等）
+    Error(String),
+    /// 缓冲进度，0~100
+    Buffering(i32),
+    /// 歌曲时长变化
+    DurationChanged(Option<ClockTime>),
+    /// 播放状态变化
+    StateChanged(PlaybackState),
+    /// 一首歌播放完时的响度分析结果，uri 为分析的歌曲
+    LoudnessAnalyzed { uri: String, loudness: Loudness },
+}
+
+/// gstreamer_play 的播放状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaybackState {
+    Stopped,
+    Buffering,
+    Paused,
+    Playing,
+}
+
+impl From<GstPlayState> for PlaybackState {
+    fn from(state: GstPlayState) -> Self {
+        match state {
+            GstPlayState::Buffering => PlaybackState::Buffering,
+            GstPlayState::Paused => PlaybackState::Paused,
+            GstPlayState::Playing => PlaybackState::Playing,
+            _ => PlaybackState::Stopped,
+        }
+    }
+}
+
+/// 连接 gstreamer_play 的信号，转换为 PlayerEvent 发送到事件通道
+/// 只在 active 为 true 时发送，交叉淡入淡出时淡出的一路不产生事件
+/// 返回的 PlaySignalAdapter 需要与 Play 一同保存，被 drop 后不再产生事件
+pub(crate) fn connect_signals(
+    play: &Play,
+    sender: &broadcast::Sender<PlayerEvent>,
+    active: &Arc<AtomicBool>,
+) -> PlaySignalAdapter {
+    // 在 gstreamer 的线程中直接发出信号，不依赖 glib 主循环
+    let adapter = PlaySignalAdapter::new_sync_emit(play);
+
+    let send = {
+        let tx = sender.clone();
+        let active = active.clone();
+        move |event: PlayerEvent| {
+            if active.load(Ordering::Relaxed) {
+                // 没有订阅方时发送失败，忽略即可
+                let _ = tx.send(event);
+            }
+        }
+    };
+
+    let send_event = send.clone();
+    adapter.connect_end_of_stream(move |_| send_event(PlayerEvent::EndOfStream));
+    let send_event = send.clone();
+    adapter.connect_error(move |_, error, _| send_event(PlayerEvent::Error(error.to_string())));
+    let send_event = send.clone();
+    adapter.connect_buffering(move |_, percent| send_event(PlayerEvent::Buffering(percent)));
+    let send_event = send.clone();
+    adapter.connect_duration_changed(move |_, duration| {
+        send_event(PlayerEvent::DurationChanged(duration))
+    });
+    adapter.connect_state_changed(move |_, state| send(PlayerEvent::StateChanged(state.into())));
+
+    adapter
+}
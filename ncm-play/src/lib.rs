@@ -1,10 +1,21 @@
+mod backend;
+mod event;
+mod local;
+mod prefetch;
+mod queue;
+
+pub use crate::backend::{AudioBackend, FakeBackend, GstBackend};
+pub use crate::event::{PlaybackState, PlayerEvent, PlayerEventReceiver};
+pub use crate::local::{is_local_song, load_local, local_song_lyrics, local_song_path};
+use crate::prefetch::{NextSong, Prefetch, RadioRefill, ResolvedSong, PREFETCH_BEFORE_END};
+pub use crate::queue::PlayQueue;
 use anyhow::{anyhow, Result};
 use gstreamer::ClockTime;
-use gstreamer_play::{gst, Play, PlayVideoRenderer};
-use log::debug;
-use ncm_api::{NcmApi, SongInfo};
+use log::{debug, error, warn};
+use ncm_api::{Loudness, LyricLine, NcmApi, NcmError, NormalizeMode, SongInfo, SongUrl};
 use rand::{thread_rng, Rng};
 use std::fmt;
+use tokio::sync::broadcast::error::TryRecvError;
 use tokio::sync::MutexGuard;
 
 #[derive(Clone, PartialEq)]
@@ -28,8 +39,30 @@ pub enum PlayMode {
     SingleRepeat,
     ListRepeat,
     Shuffle,
+    /// 私人 FM / 心动模式，播放列表即电台缓冲，快播完时自动获取更多歌曲
+    Radio(RadioSource),
 }
 
+/// 电台歌曲来源
+#[derive(Clone)]
+pub enum RadioSource {
+    /// 私人 FM
+    PersonalFm,
+    /// 心动模式
+    /// songlist_id: “我喜欢的音乐”歌单 id
+    /// seed_song_id: 起始歌曲 id
+    Heartbeat { songlist_id: u64, seed_song_id: u64 },
+}
+
+/// 电台缓冲中未播放的歌曲不多于此数量时获取更多歌曲
+const RADIO_BUFFER_LOW_WATER: usize = 2;
+
+/// 交叉淡入淡出的最大时长，单位: s，需在预取下一首之后开始
+const MAX_CROSSFADE: u64 = 12;
+
+/// 事件丢失后，播放位置距结尾不多于此值时视为已播放结束，单位: ms
+const LAGGED_END_TOLERANCE: u64 = 500;
+
 impl fmt::Display for PlayMode {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -37,59 +70,88 @@ impl fmt::Display for PlayMode {
             PlayMode::SingleRepeat => write!(f, "单曲循环"),
             PlayMode::ListRepeat => write!(f, "列表循环"),
             PlayMode::Shuffle => write!(f, "随机播放"),
+            PlayMode::Radio(RadioSource::PersonalFm) => write!(f, "私人FM"),
+            PlayMode::Radio(RadioSource::Heartbeat { .. }) => write!(f, "心动模式"),
         }
     }
 }
 
 pub struct Player {
-    play: Play,
+    backend: Box<dyn AudioBackend>,
+    event_receiver: PlayerEventReceiver, // Player 自身处理播放结束、出错
     //
     play_state: PlayState,
     play_mode: PlayMode,
+    play_mode_before_radio: PlayMode, // 进入电台模式前的播放模式，离开电台时恢复
     //
     volume: f64,
+    crossfade: u64, // 交叉淡入淡出时长，单位: s，0 表示不启用
+    normalize_mode: NormalizeMode,
     //
     current_playlist_name: String,
     current_playlist: Vec<SongInfo>,
-    play_index_history_stack: Vec<usize>, // 历史记录，保存播放的歌曲在 playlist 中的 index，栈顶为当前播放
+    play_queue: PlayQueue,
+    play_history_stack: Vec<SongInfo>, // 历史记录，保存播放过的歌曲，栈顶为当前播放
     //
     current_song_index: Option<usize>,
     current_song_info: Option<SongInfo>,
-    current_song_lyrics: Option<Vec<(String, Option<String>)>>, // 兼容带翻译的歌词
-    current_song_lyric_timestamps: Option<Vec<u64>>,            // 单位: ms
+    current_song_url: Option<SongUrl>, // 当前歌曲 url 及实际音质
+    current_song_lyrics: Option<Vec<LyricLine>>,
+    current_song_lyric_timestamps: Option<Vec<u64>>, // 单位: ms
     current_song_lyric_index: Option<usize>,
+    current_song_lyric_offset: i64, // 单位: ms，正数表示歌词延后显示
+    current_song_loudness: Option<Loudness>,
+    current_album_loudness: Option<Loudness>,
+    analyzed_loudness: Vec<(String, Loudness)>, // 待保存的响度分析结果（uri, 响度）
+    //
+    prefetch: Prefetch,                      // 预取的下一首
+    radio_refill: RadioRefill,               // 电台模式下在后台获取更多歌曲
+    url_refreshed: bool,                     // 当前歌曲是否已因播放出错重新获取过链接
+    url_refresh_position: Option<ClockTime>, // 需要重新获取链接时，恢复播放的位置
 }
 
 impl Player {
-    pub fn new() -> Self {
-        gst::init().expect("Failed to initialize GST");
-
-        let play = Play::new(None::<PlayVideoRenderer>);
+    /// 使用 GStreamer 播放，初始化失败（如未安装 GStreamer）时返回错误
+    pub fn new() -> Result<Self> {
+        let backend =
+            GstBackend::new().map_err(|e| anyhow!("Failed to initialize GStreamer: {}", e))?;
 
-        let mut config = play.config();
-        config.set_user_agent(
-            "User-Agent: Mozilla/5.0 (X11; Linux x86_64; rv:100.0) Gecko/20100101 Firefox/100.0",
-        );
-        config.set_position_update_interval(250);
-        config.set_seek_accurate(true);
-        play.set_config(config).unwrap();
+        Ok(Self::with_backend(Box::new(backend)))
+    }
 
+    /// 使用指定的 AudioBackend 播放，如测试时使用 FakeBackend
+    pub fn with_backend(mut backend: Box<dyn AudioBackend>) -> Self {
         let volume = 0.2;
-        play.set_volume(volume);
+        backend.set_volume(volume);
+        let event_receiver = backend.subscribe();
 
         Self {
-            play,
+            backend,
+            event_receiver,
             play_state: PlayState::Stopped,
             play_mode: PlayMode::Shuffle,
+            play_mode_before_radio: PlayMode::Shuffle,
             volume,
+            crossfade: 0,
+            normalize_mode: NormalizeMode::Off,
             current_playlist_name: String::new(),
             current_playlist: Vec::new(),
-            play_index_history_stack: Vec::new(),
+            play_queue: PlayQueue::new(),
+            play_history_stack: Vec::new(),
             current_song_index: None,
             current_song_info: None,
+            current_song_url: None,
             current_song_lyrics: None,
             current_song_lyric_timestamps: None,
             current_song_lyric_index: None,
+            current_song_lyric_offset: 0,
+            current_song_loudness: None,
+            current_album_loudness: None,
+            analyzed_loudness: Vec::new(),
+            prefetch: Prefetch::None,
+            radio_refill: RadioRefill::Idle,
+            url_refreshed: false,
+            url_refresh_position: None,
         }
     }
 }
@@ -103,12 +165,12 @@ impl Player {
             volume = 0.0;
         }
         self.volume = volume;
-        self.play.set_volume(volume);
+        self.backend.set_volume(volume);
     }
 
     pub fn mute(&mut self) {
         self.volume = 0.0;
-        self.play.set_volume(0.0);
+        self.backend.set_volume(0.0);
     }
 
     pub fn volume(&self) -> f64 {
@@ -119,6 +181,27 @@ impl Player {
         self.play_state == PlayState::Playing
     }
 
+    /// 设置切歌时交叉淡入淡出的时长（秒），0 表示不启用，最大为 12 秒
+    pub fn set_crossfade(&mut self, seconds: u64) {
+        self.crossfade = seconds.min(MAX_CROSSFADE);
+    }
+
+    pub fn crossfade(&self) -> u64 {
+        self.crossfade
+    }
+
+    /// 设置响度均衡模式，立即作用于当前歌曲
+    /// 歌曲播放完一次后才有响度数据，未分析过的歌曲不调整
+    pub fn set_normalize_mode(&mut self, mode: NormalizeMode) {
+        self.normalize_mode = mode;
+        self.backend.set_normalize(mode != NormalizeMode::Off);
+        self.apply_normalize_gain();
+    }
+
+    pub fn normalize_mode(&self) -> NormalizeMode {
+        self.normalize_mode
+    }
+
     pub fn play_mode(&self) -> String {
         self.play_mode.to_string()
     }
@@ -127,12 +210,22 @@ impl Player {
         self.play_mode = mode;
     }
 
+    /// 是否为私人 FM / 心动模式
+    pub fn is_radio_mode(&self) -> bool {
+        matches!(self.play_mode, PlayMode::Radio(_))
+    }
+
     pub fn duration(&self) -> Option<ClockTime> {
-        self.play.duration()
+        self.backend.duration()
     }
 
     pub fn position(&self) -> Option<ClockTime> {
-        self.play.position()
+        self.backend.position()
+    }
+
+    /// 订阅播放器事件
+    pub fn subscribe(&self) -> PlayerEventReceiver {
+        self.backend.subscribe()
     }
 
     pub fn current_playlist_name_ref(&self) -> &String {
@@ -143,21 +236,51 @@ impl Player {
         self.current_playlist.clone()
     }
 
+    pub fn current_playlist_len(&self) -> usize {
+        self.current_playlist.len()
+    }
+
+    pub fn play_queue_ref(&self) -> &PlayQueue {
+        &self.play_queue
+    }
+
+    pub fn play_queue_mut(&mut self) -> &mut PlayQueue {
+        &mut self.play_queue
+    }
+
     pub fn current_song_info_ref(&self) -> &Option<SongInfo> {
         &self.current_song_info
     }
 
+    pub fn current_song_url_ref(&self) -> &Option<SongUrl> {
+        &self.current_song_url
+    }
+
     pub fn current_song_index(&self) -> Option<usize> {
         self.current_song_index.clone()
     }
 
-    pub fn current_song_lyrics(&self) -> Option<Vec<(String, Option<String>)>> {
+    pub fn current_song_lyrics(&self) -> Option<Vec<LyricLine>> {
         self.current_song_lyrics.clone()
     }
 
     pub fn current_song_lyric_index(&self) -> Option<usize> {
         self.current_song_lyric_index
     }
+
+    /// 当前歌词行已唱过的字符数，用于逐字高亮
+    /// 当前歌词行没有逐字歌词时返回 None
+    pub fn current_song_lyric_word_offset(&self) -> Option<usize> {
+        let line = self
+            .current_song_lyrics
+            .as_ref()?
+            .get(self.current_song_lyric_index?)?;
+        line.sung_chars(self.lyric_position()?)
+    }
+
+    pub fn current_song_lyric_offset(&self) -> i64 {
+        self.current_song_lyric_offset
+    }
 }
 
 /// playlist 搜索
@@ -234,19 +357,24 @@ impl Player {
     /// 切换播放/暂停
     pub fn play_or_pause(&mut self) {
         if self.play_state == PlayState::Playing {
-            self.play.pause();
+            self.backend.pause();
             self.play_state = PlayState::Paused;
         } else if self.play_state == PlayState::Paused {
-            self.play.play();
+            self.backend.play();
             self.play_state = PlayState::Playing;
         }
     }
 
     /// 切换播放列表
+    /// 电台模式下切换到其他播放列表时，恢复进入电台前的播放模式
     pub fn switch_playlist(&mut self, playlist_name: String, playlist: Vec<SongInfo>) {
+        if self.is_radio_mode() {
+            self.play_mode = self.play_mode_before_radio.clone();
+        }
         self.current_playlist_name = playlist_name;
         self.current_playlist = playlist;
-        self.play_index_history_stack = Vec::new();
+        self.radio_refill = RadioRefill::Idle;
+        self.play_history_stack = Vec::new();
         self.current_song_index = if self.current_playlist.is_empty() {
             None
         } else {
@@ -254,27 +382,64 @@ impl Player {
         };
     }
 
+    /// 加入当前播放列表末尾，返回歌曲在播放列表中的 index
+    /// 歌曲已在播放列表中时不重复加入，直接返回原 index
+    pub fn add_to_playlist(&mut self, song_info: SongInfo) -> usize {
+        if let Some(index) = self.current_playlist.iter().position(|s| *s == song_info) {
+            return index;
+        }
+
+        self.current_playlist.push(song_info);
+        if self.current_song_index.is_none() {
+            self.current_song_index = Some(0);
+        }
+
+        self.current_playlist.len() - 1
+    }
+
     /// 自动播放
     pub async fn auto_play<'a>(&mut self, ncm_api_guard: MutexGuard<'a, NcmApi>) -> Result<()> {
-        // 判断一首歌是否播放完
-        if self.play_state == PlayState::Playing {
-            if let (Some(position), Some(duration)) = (self.position(), self.duration()) {
-                let position_msec = position.mseconds();
-                let duration_msec = duration.mseconds();
-
-                if duration_msec - position_msec <= 10 {
-                    self.play_state = PlayState::Ended;
-                }
+        // 根据播放器事件判断一首歌是否播放完
+        self.handle_player_events();
+        self.save_analyzed_loudness(&ncm_api_guard);
+
+        if let Some(position) = self.url_refresh_position.take() {
+            // 链接可能已失效，重新获取后从出错位置继续播放
+            self.play_history_stack.pop();
+            let result = self.play_next(ncm_api_guard).await;
+            self.url_refreshed = true;
+            if result.is_err() {
+                self.play_state = PlayState::Stopped;
+            } else {
+                self.backend.seek(position);
             }
+            return result;
         }
 
         if self.play_state == PlayState::Playing {
-            // 当前歌曲仍在播放，推进歌词
+            // 当前歌曲仍在播放，推进歌词，接近结束时预取下一首
             self.auto_lyric_forward();
+            self.update_prefetch(&ncm_api_guard).await;
         } else if self.play_state == PlayState::Ended {
-            // 播放下一首
-            self.update_next_to_play();
-            self.play_next(ncm_api_guard).await?;
+            // 播放下一首，优先使用预取的歌曲
+            match self.prefetched_next() {
+                Some(next) => self.set_next_song(next),
+                None => {
+                    // 电台缓冲已播完时保持 Ended，等待后台获取完成后再切歌
+                    if !self.refill_radio_buffer(&ncm_api_guard) {
+                        return Ok(());
+                    }
+                    self.update_next_to_play(false);
+                }
+            }
+            if let Err(e) = self.play_next(ncm_api_guard).await {
+                // 无版权的歌曲保持 Ended 状态，下一轮自动跳过
+                // 其他错误（网络异常等）停止播放，避免反复请求
+                if !matches!(e.downcast_ref::<NcmError>(), Some(NcmError::Blocked(_))) {
+                    self.play_state = PlayState::Stopped;
+                }
+                return Err(e);
+            }
         }
 
         Ok(())
@@ -297,8 +462,29 @@ impl Player {
         Ok(())
     }
 
-    /// 根据当前模式开始播放
+    /// 立刻播放队列中指定位置的歌曲，歌曲随即出队
+    pub async fn play_from_queue_now<'a>(
+        &mut self,
+        queue_index: usize,
+        ncm_api_guard: MutexGuard<'a, NcmApi>,
+    ) -> Result<()> {
+        if let Some(song_info) = self.play_queue.remove(queue_index) {
+            self.play_state = PlayState::Playing;
+            self.set_current_song(song_info);
+
+            self.play_next(ncm_api_guard).await?;
+        }
+
+        Ok(())
+    }
+
+    /// 根据当前模式开始播放，队列不为空时先播放队列
     pub async fn start_play<'a>(&mut self, ncm_api_guard: MutexGuard<'a, NcmApi>) -> Result<()> {
+        if let Some(song_info) = self.play_queue.pop_front() {
+            self.set_current_song(song_info);
+            return self.play_next(ncm_api_guard).await;
+        }
+
         match self.play_mode {
             PlayMode::ListRepeat => {
                 self.current_song_index = Some(0);
@@ -313,6 +499,12 @@ impl Player {
                 self.play_next(ncm_api_guard).await?;
                 Ok(())
             }
+            PlayMode::Radio(_) if !self.current_playlist.is_empty() => {
+                self.current_song_index = Some(0);
+                self.current_song_info = Some(self.current_playlist[0].clone());
+                self.play_next(ncm_api_guard).await?;
+                Ok(())
+            }
             _ => Err(anyhow!("start命令只在`列表循环`和`随机播放`模式下有效")),
         }
     }
@@ -329,14 +521,27 @@ impl Player {
             // 当前单曲播放半秒后才可以切换到下一首，留出缓冲时间，防止切换过快
             if let Some(position) = self.position() {
                 if position.mseconds() >= 500 {
-                    self.update_next_to_play();
+                    if !self.refill_radio_buffer(&ncm_api_guard) {
+                        self.wait_for_radio_refill();
+                        return Ok(());
+                    }
+                    let prev_song_info = self.current_song_info.clone();
+                    let prev_song_index = self.current_song_index;
+                    self.update_next_to_play(true);
 
                     debug!(
                         "[{:?}] {:?}, ",
                         self.current_song_index, self.current_song_info
                     );
 
-                    self.play_next(ncm_api_guard).await?;
+                    // 播放中手动切歌同样交叉淡入淡出
+                    let crossfade = match (&prev_song_info, &self.current_song_info) {
+                        (Some(from), Some(to)) if self.play_state == PlayState::Playing => self
+                            .crossfade_duration(from, prev_song_index, to, self.current_song_index),
+                        _ => None,
+                    };
+                    self.play_next_with_crossfade(ncm_api_guard, crossfade)
+                        .await?;
                 }
             }
         }
@@ -353,16 +558,14 @@ impl Player {
         if let Some(position) = self.position() {
             if position.mseconds() >= 500 {
                 // 出栈历史记录
-                if let Some(current_song_index) = self.play_index_history_stack.pop() {
-                    if let Some(prev_song_index) = self.play_index_history_stack.pop() {
+                if let Some(current_song_info) = self.play_history_stack.pop() {
+                    if let Some(prev_song_info) = self.play_history_stack.pop() {
                         // 播放上一首
-                        self.current_song_index = Some(prev_song_index);
-                        self.current_song_info =
-                            Some(self.current_playlist[prev_song_index].clone());
+                        self.set_current_song(prev_song_info);
                         self.play_next(ncm_api_guard).await?;
                     } else {
                         // 无上一首（当前为第一首播放）
-                        self.play_index_history_stack.push(current_song_index);
+                        self.play_history_stack.push(current_song_info);
                     }
                 }
             }
@@ -371,6 +574,65 @@ impl Player {
         Ok(())
     }
 
+    /// 进入私人 FM / 心动模式：获取电台歌曲作为播放列表并立刻播放
+    pub async fn start_radio<'a>(
+        &mut self,
+        source: RadioSource,
+        ncm_api_guard: MutexGuard<'a, NcmApi>,
+    ) -> Result<()> {
+        let songs = Self::fetch_radio_songs(&source, &ncm_api_guard).await?;
+        if songs.is_empty() {
+            return Err(anyhow!("电台暂无可播放的歌曲"));
+        }
+
+        let play_mode = PlayMode::Radio(source);
+        self.switch_playlist(play_mode.to_string(), songs);
+        if !self.is_radio_mode() {
+            self.play_mode_before_radio = self.play_mode.clone();
+        }
+        self.play_mode = play_mode;
+
+        self.play_state = PlayState::Playing;
+        self.current_song_index = Some(0);
+        self.current_song_info = Some(self.current_playlist[0].clone());
+        self.play_next(ncm_api_guard).await
+    }
+
+    /// 不喜欢当前歌曲（私人 FM 垃圾桶）：从电台中移除并立刻播放下一首
+    pub async fn trash_current_song<'a>(
+        &mut self,
+        ncm_api_guard: MutexGuard<'a, NcmApi>,
+    ) -> Result<()> {
+        if !self.is_radio_mode() {
+            return Err(anyhow!("trash命令只在`私人FM`和`心动模式`下有效"));
+        }
+
+        if let Some(song_info) = self.current_song_info.clone() {
+            ncm_api_guard.fm_trash(song_info.id).await?;
+
+            // 从播放历史和电台缓冲中移除，防止切到上一首时再次播放
+            self.play_history_stack.pop();
+            if let Some(index) = self
+                .current_playlist
+                .iter()
+                .position(|s| s.id == song_info.id)
+            {
+                self.current_playlist.remove(index);
+                // 指向被移除歌曲的前一首，之后即可选中原来的下一首
+                self.current_song_index = index.checked_sub(1);
+            }
+
+            if !self.refill_radio_buffer(&ncm_api_guard) {
+                self.wait_for_radio_refill();
+                return Ok(());
+            }
+            self.update_next_to_play(true);
+            self.play_next(ncm_api_guard).await?;
+        }
+
+        Ok(())
+    }
+
     /// 跳转到所给编号的时间戳处播放
     pub async fn seek_to_timestamp_with_index(&mut self, index: usize) -> Result<()> {
         if self.play_state == PlayState::Playing
@@ -380,21 +642,74 @@ impl Player {
             if let Some(timestamps) = self.current_song_lyric_timestamps.clone() {
                 if index < timestamps.len() {
                     self.current_song_lyric_index = Some(index);
-                    let timestamp = timestamps[index];
-                    self.play.seek(ClockTime::from_mseconds(timestamp));
+                    // 按歌词时间偏移修正跳转位置
+                    let timestamp =
+                        (timestamps[index] as i64 + self.current_song_lyric_offset).max(0);
+                    self.backend
+                        .seek(ClockTime::from_mseconds(timestamp as u64));
                 }
             }
         }
 
         Ok(())
     }
+
+    /// 删除当前歌曲的歌词缓存并重新获取歌词
+    pub async fn reload_current_song_lyrics<'a>(
+        &mut self,
+        ncm_api_guard: MutexGuard<'a, NcmApi>,
+    ) -> Result<()> {
+        if let Some(song_info) = &self.current_song_info {
+            ncm_api_guard.remove_lyric_cache(song_info.id)?;
+            self.update_current_lyric_encoded(ncm_api_guard).await?;
+            self.sync_lyric_index_to_position();
+        }
+
+        Ok(())
+    }
+
+    /// 设置当前歌曲的歌词时间偏移（ms），按歌曲 id 保存
+    pub async fn set_current_song_lyric_offset<'a>(
+        &mut self,
+        ncm_api_guard: MutexGuard<'a, NcmApi>,
+        offset: i64,
+    ) -> Result<()> {
+        let song_info = self
+            .current_song_info
+            .as_ref()
+            .ok_or_else(|| anyhow!("当前没有播放的歌曲"))?;
+        ncm_api_guard.set_lyric_offset(song_info.id, offset)?;
+
+        self.current_song_lyric_offset = offset;
+        // 偏移减小时歌词可能需要回退
+        self.sync_lyric_index_to_position();
+
+        Ok(())
+    }
 }
 
 /// private
 impl Player {
-    /// 根据模式更新下一首播放的歌曲
+    /// 设置当前歌曲
+    /// 歌曲在播放列表中时同步 current_song_index，否则保持不变，以便之后从原位置继续播放列表
+    fn set_current_song(&mut self, song_info: SongInfo) {
+        if let Some(index) = self.current_playlist.iter().position(|s| *s == song_info) {
+            self.current_song_index = Some(index);
+        }
+        self.current_song_info = Some(song_info);
+    }
+
+    /// 根据队列和模式更新下一首播放的歌曲
     /// 更新 self.current_song_info & self.current_song_index
-    fn update_next_to_play(&mut self) {
+    /// manual: 是否为手动切歌，手动切歌时`单曲循环`模式也会先播放队列
+    fn update_next_to_play(&mut self, manual: bool) {
+        if manual || !matches!(self.play_mode, PlayMode::SingleRepeat) {
+            if let Some(song_info) = self.play_queue.pop_front() {
+                self.set_current_song(song_info);
+                return;
+            }
+        }
+
         self.current_song_info = match self.play_mode {
             PlayMode::Single => None,
             PlayMode::SingleRepeat => self.current_song_info.clone(),
@@ -419,40 +734,403 @@ impl Player {
                     None
                 }
             }
+            PlayMode::Radio(_) => {
+                // 电台不循环，缓冲播完（获取更多歌曲失败）时停止
+                let index = self.current_song_index.map_or(0, |index| index + 1);
+                if index < self.current_playlist.len() {
+                    self.current_song_index = Some(index);
+                    Some(self.current_playlist[index].clone())
+                } else {
+                    None
+                }
+            }
         };
     }
 
-    fn play_new_song_by_uri(&mut self, uri: &str) {
-        self.play.stop();
-        self.play.set_uri(Some(uri));
-        self.play.set_volume(self.volume);
-        self.play.play();
+    /// 自动切歌时下一首播放的歌曲，与 update_next_to_play(false) 的选择一致，但不改变播放状态
+    /// `随机播放`模式下每次调用的结果不同
+    fn peek_next_to_play(&self) -> Option<NextSong> {
+        if !matches!(self.play_mode, PlayMode::SingleRepeat) {
+            if let Some(song_info) = self.play_queue.get(0) {
+                return Some(NextSong {
+                    index: self.current_playlist.iter().position(|s| s == song_info),
+                    song_info: song_info.clone(),
+                    from_queue: true,
+                });
+            }
+        }
+
+        if self.current_playlist.is_empty() {
+            return None;
+        }
+        let index = match self.play_mode {
+            PlayMode::Single => return None,
+            PlayMode::SingleRepeat => {
+                return self.current_song_info.clone().map(|song_info| NextSong {
+                    song_info,
+                    index: self.current_song_index,
+                    from_queue: false,
+                })
+            }
+            PlayMode::ListRepeat => (self.current_song_index? + 1) % self.current_playlist.len(),
+            PlayMode::Shuffle => {
+                self.current_song_index?;
+                thread_rng().gen_range(0..self.current_playlist.len())
+            }
+            PlayMode::Radio(_) => self.current_song_index.map_or(0, |index| index + 1),
+        };
+
+        Some(NextSong {
+            song_info: self.current_playlist.get(index)?.clone(),
+            index: Some(index),
+            from_queue: false,
+        })
     }
 
-    /// 播放下一首
-    async fn play_next<'a>(&mut self, ncm_api_guard: MutexGuard<'a, NcmApi>) -> Result<()> {
-        if let Some(mut song_info) = self.current_song_info.clone() {
-            // 获取歌曲 uri
-            if let Ok(url) = ncm_api_guard.get_song_url(song_info.id).await {
-                song_info.song_url = url;
+    /// next 是否仍为下一首播放的歌曲（队列、播放模式等未改变）
+    fn is_next_to_play(&self, next: &NextSong) -> bool {
+        if !matches!(self.play_mode, PlayMode::SingleRepeat) {
+            if let Some(song_info) = self.play_queue.get(0) {
+                return next.from_queue && song_info.id == next.song_info.id;
+            }
+        }
+        if next.from_queue {
+            return false;
+        }
+
+        match self.play_mode {
+            PlayMode::Shuffle => next
+                .index
+                .and_then(|index| self.current_playlist.get(index))
+                .is_some_and(|song_info| song_info.id == next.song_info.id),
+            _ => self.peek_next_to_play().is_some_and(|peek| {
+                peek.index == next.index && peek.song_info.id == next.song_info.id
+            }),
+        }
+    }
+
+    /// 切换到 next，与 update_next_to_play 相同地更新队列和 current_song_index
+    fn set_next_song(&mut self, next: NextSong) {
+        if next.from_queue
+            && self
+                .play_queue
+                .get(0)
+                .is_some_and(|song_info| song_info.id == next.song_info.id)
+        {
+            self.play_queue.pop_front();
+        }
+        if next.index.is_some() {
+            self.current_song_index = next.index;
+        }
+        self.current_song_info = Some(next.song_info);
+    }
+
+    /// 预取完成且仍为下一首时返回预取的歌曲
+    fn prefetched_next(&self) -> Option<NextSong> {
+        match &self.prefetch {
+            Prefetch::Ready { next, .. } if self.is_next_to_play(next) => Some(next.clone()),
+            _ => None,
+        }
+    }
 
-                // 更新当前歌曲信息
-                self.current_song_info = Some(song_info.clone());
+    /// 当前歌曲的剩余时间，单位: ms
+    fn remaining(&self) -> Option<u64> {
+        let duration = self
+            .duration()
+            .map(|duration| duration.mseconds())
+            .or(self.current_song_info.as_ref().map(|s| s.duration))
+            .filter(|duration| *duration > 0)?;
 
-                // 入栈播放历史
-                if let Some(index) = self.current_song_index {
-                    self.play_index_history_stack.push(index);
+        Some(duration.saturating_sub(self.position()?.mseconds()))
+    }
+
+    /// 当前歌曲是否即将播放结束，需要预取下一首
+    fn is_near_end(&self) -> bool {
+        self.remaining()
+            .is_some_and(|remaining| remaining <= PREFETCH_BEFORE_END)
+    }
+
+    /// 从 from 切换到 to 时交叉淡入淡出的时长，不超过当前歌曲时长的一半
+    /// 只在`列表循环`和`随机播放`模式下启用，按顺序播放同一专辑的歌曲时（无缝专辑）不启用
+    fn crossfade_duration(
+        &self,
+        from: &SongInfo,
+        from_index: Option<usize>,
+        to: &SongInfo,
+        to_index: Option<usize>,
+    ) -> Option<ClockTime> {
+        if self.crossfade == 0
+            || !matches!(self.play_mode, PlayMode::ListRepeat | PlayMode::Shuffle)
+        {
+            return None;
+        }
+
+        let in_order = from_index.is_some() && to_index == from_index.map(|index| index + 1);
+        if in_order && is_same_album(from, to) {
+            return None;
+        }
+
+        let duration = self
+            .duration()
+            .map_or(from.duration, |duration| duration.mseconds());
+        Some(ClockTime::from_seconds(self.crossfade).min(ClockTime::from_mseconds(duration / 2)))
+            .filter(|crossfade| *crossfade > ClockTime::ZERO)
+    }
+
+    /// 当前歌曲即将结束时在后台获取下一首的链接和歌词，完成后交给播放后端无缝切换
+    /// 下一首改变（如修改了队列、播放模式）或链接失效时重新获取
+    async fn update_prefetch(&mut self, ncm_api: &NcmApi) {
+        self.prefetch.poll();
+        if let Some(songs) = self.radio_refill.poll() {
+            self.append_radio_songs(songs);
+        }
+
+        if let Some(next) = self.prefetch.next() {
+            if !self.is_next_to_play(next) || self.prefetch.is_expired() {
+                self.cancel_prefetch();
+            }
+        }
+
+        if self.is_near_end() {
+            // 电台缓冲不足时同样在后台获取，缓冲已空时获取完成后再预取
+            if matches!(self.radio_refill, RadioRefill::Idle) {
+                if let Some(source) = self.radio_refill_source() {
+                    self.radio_refill = RadioRefill::start(ncm_api, source);
                 }
+            }
+            if matches!(self.prefetch, Prefetch::None) {
+                if let Some(next) = self.peek_next_to_play() {
+                    self.prefetch = Prefetch::start(ncm_api, next);
+                }
+            }
+        }
 
-                // 获取歌词
-                self.update_current_lyric_encoded(ncm_api_guard).await?;
+        // 需要交叉淡入淡出时不交给播放后端无缝播放，剩余时间不多于淡入淡出时长时开始
+        let crossfade = match (&self.prefetch, &self.current_song_info) {
+            (Prefetch::Ready { next, .. }, Some(current_song_info)) => self.crossfade_duration(
+                current_song_info,
+                self.current_song_index,
+                &next.song_info,
+                next.index,
+            ),
+            _ => None,
+        };
+        let remaining = self.remaining();
+        let mut crossfade_started = false;
+        if let Prefetch::Ready {
+            resolved,
+            handed_over,
+            ..
+        } = &mut self.prefetch
+        {
+            let uri = resolved.song_info.song_url.as_str();
+            match crossfade {
+                Some(crossfade) => {
+                    if *handed_over {
+                        self.backend.set_next_uri(None);
+                        *handed_over = false;
+                    }
+                    if remaining.is_some_and(|remaining| remaining <= crossfade.mseconds()) {
+                        self.backend.crossfade_to(uri, crossfade);
+                        crossfade_started = true;
+                    }
+                }
+                None if !*handed_over => {
+                    self.backend.set_next_uri(Some(uri));
+                    *handed_over = true;
+                }
+                None => {}
+            }
+        }
+        if crossfade_started {
+            self.switch_to_prefetched();
+        }
+    }
+
+    fn cancel_prefetch(&mut self) {
+        self.prefetch = Prefetch::None;
+        self.backend.set_next_uri(None);
+    }
+
+    /// 取出预取的歌曲，歌曲不符或链接已失效时返回 None
+    /// 开始播放新歌曲时调用，预取状态随之清空
+    fn take_prefetched(&mut self, song_id: u64) -> Option<ResolvedSong> {
+        match std::mem::replace(&mut self.prefetch, Prefetch::None) {
+            Prefetch::Ready { resolved, .. }
+                if resolved.song_info.id == song_id && !resolved.is_expired() =>
+            {
+                Some(resolved)
+            }
+            _ => None,
+        }
+    }
+
+    /// 播放后端已切换到预取的下一首（无缝播放或交叉淡入淡出），更新当前歌曲
+    fn switch_to_prefetched(&mut self) {
+        if let Prefetch::Ready { next, resolved, .. } =
+            std::mem::replace(&mut self.prefetch, Prefetch::None)
+        {
+            self.set_next_song(next);
+            self.set_resolved_song(resolved);
+            self.sync_lyric_index_to_position();
+            // 无缝播放时在切换后才设置增益，歌曲开头的一小段仍使用上一首的增益
+            self.apply_normalize_gain();
+        }
+    }
+
+    /// 更新当前歌曲的链接、歌词并记入播放历史
+    fn set_resolved_song(&mut self, resolved: ResolvedSong) {
+        // 入栈播放历史
+        self.play_history_stack.push(resolved.song_info.clone());
+
+        self.current_song_info = Some(resolved.song_info);
+        self.current_song_url = resolved.song_url;
+        self.current_song_lyric_offset = resolved.lyric_offset;
+        // 新的歌曲可以再次在后台获取电台歌曲
+        if matches!(self.radio_refill, RadioRefill::Done) {
+            self.radio_refill = RadioRefill::Idle;
+        }
+        self.current_song_loudness = resolved.loudness;
+        self.current_album_loudness = resolved.album_loudness;
+        self.set_current_lyrics(resolved.lyrics);
+        self.url_refreshed = false;
+    }
+
+    fn set_current_lyrics(&mut self, lyrics: Option<Vec<LyricLine>>) {
+        if let Some(lyrics) = lyrics {
+            // 获取歌词和时间戳（在 ncm-api 中已解析过）
+            let timestamps: Vec<u64> = lyrics.iter().map(|lyric| lyric.time).collect();
+
+            self.current_song_lyrics = Some(lyrics);
+            self.current_song_lyric_timestamps = Some(timestamps);
+            self.current_song_lyric_index = Some(0);
+        } else {
+            // 无歌词（纯音乐或网络异常）
+            self.current_song_lyrics = None;
+            self.current_song_lyric_timestamps = None;
+            self.current_song_lyric_index = None;
+        }
+    }
+
+    /// 获取电台歌曲
+    async fn fetch_radio_songs(source: &RadioSource, ncm_api: &NcmApi) -> Result<Vec<SongInfo>> {
+        let songs = match source {
+            RadioSource::PersonalFm => ncm_api.personal_fm().await?,
+            RadioSource::Heartbeat {
+                songlist_id,
+                seed_song_id,
+            } => {
+                ncm_api
+                    .intelligence_songs(*seed_song_id, *songlist_id)
+                    .await?
+            }
+        };
+        Ok(songs)
+    }
+
+    /// 电台模式下，未播放的歌曲不足时在后台获取更多歌曲加入电台缓冲，不等待获取完成
+    /// 返回 false 表示缓冲已播完且仍在获取中，需等获取完成后再切歌
+    /// 获取失败时仅记录日志，缓冲播完后停止播放
+    fn refill_radio_buffer(&mut self, ncm_api: &NcmApi) -> bool {
+        if let Some(songs) = self.radio_refill.poll() {
+            self.append_radio_songs(songs);
+        }
+        let Some(source) = self.radio_refill_source() else {
+            return true;
+        };
+        if matches!(self.radio_refill, RadioRefill::Idle) {
+            self.radio_refill = RadioRefill::start(ncm_api, source);
+        }
+
+        let played_len = self.current_song_index.map_or(0, |index| index + 1);
+        !matches!(self.radio_refill, RadioRefill::Pending { .. })
+            || !self.play_queue.is_empty()
+            || self.current_playlist.len() > played_len
+    }
+
+    /// 手动切歌时电台缓冲已播完：停止当前歌曲并标记为 Ended，获取完成后由 auto_play 播放下一首
+    fn wait_for_radio_refill(&mut self) {
+        self.backend.stop();
+        self.play_state = PlayState::Ended;
+    }
+
+    /// 电台缓冲中未播放的歌曲不足时，返回获取更多歌曲使用的电台
+    fn radio_refill_source(&self) -> Option<RadioSource> {
+        let source = match &self.play_mode {
+            PlayMode::Radio(source) => source.clone(),
+            _ => return None,
+        };
+
+        let played_len = self.current_song_index.map_or(0, |index| index + 1);
+        if self.current_playlist.len() > played_len + RADIO_BUFFER_LOW_WATER {
+            return None;
+        }
 
-                // 播放
-                self.play_new_song_by_uri(song_info.song_url.as_str());
+        // 心动模式以当前歌曲作为新的起始歌曲，推荐随播放内容变化
+        let source = match (source, &self.current_song_info) {
+            (RadioSource::Heartbeat { songlist_id, .. }, Some(song_info)) => {
+                RadioSource::Heartbeat {
+                    songlist_id,
+                    seed_song_id: song_info.id,
+                }
+            }
+            (source, _) => source,
+        };
+
+        Some(source)
+    }
+
+    /// 加入电台缓冲，跳过已有的歌曲
+    fn append_radio_songs(&mut self, songs: Vec<SongInfo>) {
+        if !self.is_radio_mode() {
+            return;
+        }
+        for song_info in songs {
+            if !self.current_playlist.iter().any(|s| s.id == song_info.id) {
+                self.current_playlist.push(song_info);
+            }
+        }
+    }
+
+    fn play_new_song_by_uri(&mut self, uri: &str) {
+        self.backend.stop();
+        // 丢弃上一首歌未处理的事件，避免误判新歌曲已播放结束
+        self.event_receiver = self.event_receiver.resubscribe();
+        self.backend.set_uri(uri);
+        self.backend.set_volume(self.volume);
+        self.backend.play();
+    }
+
+    /// 播放下一首
+    async fn play_next<'a>(&mut self, ncm_api_guard: MutexGuard<'a, NcmApi>) -> Result<()> {
+        self.play_next_with_crossfade(ncm_api_guard, None).await
+    }
 
-                // 播放状态
-                self.play_state = PlayState::Playing;
+    /// 播放下一首，crossfade 不为 None 时与当前歌曲交叉淡入淡出
+    async fn play_next_with_crossfade<'a>(
+        &mut self,
+        ncm_api_guard: MutexGuard<'a, NcmApi>,
+        crossfade: Option<ClockTime>,
+    ) -> Result<()> {
+        if let Some(song_info) = self.current_song_info.clone() {
+            // 优先使用预取的链接和歌词，否则获取
+            // 获取歌曲 uri 失败时（无版权、网络异常等）返回错误，由调用方提示
+            let resolved = match self.take_prefetched(song_info.id) {
+                Some(resolved) => resolved,
+                None => ResolvedSong::resolve(&ncm_api_guard, song_info).await?,
+            };
+            let uri = resolved.song_info.song_url.clone();
+            self.set_resolved_song(resolved);
+
+            // 播放
+            match crossfade {
+                Some(crossfade) => self.backend.crossfade_to(uri.as_str(), crossfade),
+                None => self.play_new_song_by_uri(uri.as_str()),
             }
+            self.apply_normalize_gain();
+
+            // 播放状态
+            self.play_state = PlayState::Playing;
         } else {
             // 播放状态
             self.play_state = PlayState::Stopped;
@@ -466,32 +1144,156 @@ impl Player {
         ncm_api_guard: MutexGuard<'a, NcmApi>,
     ) -> Result<()> {
         if let Some(current_song_info) = self.current_song_info.clone() {
-            if let Ok(lyric_with_timestamp) = ncm_api_guard.song_lyric(current_song_info).await {
-                // debug!("get lyric: {:?}", lyric_with_timestamp);
-
-                // 获取歌词和时间戳（在 ncm-api 中已编码过）
-                let mut lyrics: Vec<(String, Option<String>)> = Vec::new();
-                let mut timestamps: Vec<u64> = Vec::new();
-                for (timestamp, lyric) in lyric_with_timestamp {
-                    lyrics.push(lyric);
-                    timestamps.push(timestamp);
+            self.current_song_lyric_offset = ncm_api_guard.lyric_offset(current_song_info.id);
+            // 本地歌曲使用同名 .lrc 文件
+            let lyrics = if is_local_song(&current_song_info) {
+                local_song_lyrics(&current_song_info)
+            } else {
+                ncm_api_guard.song_lyric(current_song_info).await.ok()
+            };
+            self.set_current_lyrics(lyrics);
+        } else {
+            self.set_current_lyrics(None);
+        }
+
+        Ok(())
+    }
+
+    /// 按歌词时间偏移修正后的播放位置，单位: ms
+    fn lyric_position(&self) -> Option<u64> {
+        let position = self.position()?.mseconds() as i64;
+        Some((position - self.current_song_lyric_offset).max(0) as u64)
+    }
+
+    /// 定位到当前播放位置对应的歌词行
+    fn sync_lyric_index_to_position(&mut self) {
+        if let (Some(timestamps), Some(position)) =
+            (&self.current_song_lyric_timestamps, self.lyric_position())
+        {
+            let index = timestamps
+                .partition_point(|timestamp| *timestamp <= position)
+                .saturating_sub(1);
+            self.current_song_lyric_index = Some(index);
+        }
+    }
+
+    /// 处理播放结束、出错事件，出错的提示由订阅事件的界面显示
+    fn handle_player_events(&mut self) {
+        let mut lagged = false;
+        loop {
+            match self.event_receiver.try_recv() {
+                Ok(PlayerEvent::EndOfStream) => {
+                    if self.play_state == PlayState::Playing {
+                        self.play_state = PlayState::Ended;
+                    }
+                }
+                Ok(PlayerEvent::NextTrackStarted) => self.switch_to_prefetched(),
+                Ok(PlayerEvent::LoudnessAnalyzed { uri, loudness }) => {
+                    self.analyzed_loudness.push((uri, loudness))
+                }
+                Ok(PlayerEvent::Error(message)) => {
+                    error!("播放出错: {}", message);
+                    if self.can_refresh_url() {
+                        // 可能是链接已失效，重新获取一次链接
+                        self.url_refresh_position =
+                            Some(self.position().unwrap_or(ClockTime::ZERO));
+                        continue;
+                    }
+                    // 停止播放，避免出错后反复切歌
+                    self.backend.stop();
+                    self.play_state = PlayState::Stopped;
+                }
+                Ok(_) => {}
+                Err(TryRecvError::Lagged(count)) => {
+                    warn!("{} player events dropped", count);
+                    lagged = true;
                 }
+                Err(TryRecvError::Empty | TryRecvError::Closed) => break,
+            }
+        }
 
-                self.current_song_lyrics = Some(lyrics);
-                self.current_song_lyric_timestamps = Some(timestamps);
-                self.current_song_lyric_index = Some(0);
+        if lagged {
+            self.resync_after_lag();
+        }
+    }
 
-                return Ok(());
+    /// 事件通道溢出时 NextTrackStarted、EndOfStream 可能已丢失，根据播放后端的状态补上
+    fn resync_after_lag(&mut self) {
+        // 后端已在播放无缝切换的下一首
+        if let Prefetch::Ready {
+            resolved,
+            handed_over: true,
+            ..
+        } = &self.prefetch
+        {
+            if self.backend.current_uri().as_deref() == Some(resolved.song_info.song_url.as_str()) {
+                self.switch_to_prefetched();
+                return;
             }
         }
 
-        // 无歌词（纯音乐或网络异常）
-        // debug!("failed to get lyric");
-        self.current_song_lyrics = None;
-        self.current_song_lyric_timestamps = None;
-        self.current_song_lyric_index = None;
+        // 当前歌曲已播放到结尾
+        if self.play_state == PlayState::Playing {
+            if let (Some(position), Some(duration)) =
+                (self.backend.position(), self.backend.duration())
+            {
+                if position.mseconds() + LAGGED_END_TOLERANCE >= duration.mseconds() {
+                    self.play_state = PlayState::Ended;
+                }
+            }
+        }
+    }
 
-        Ok(())
+    /// 按歌曲 id 保存响度分析结果，分析的歌曲可能已不是当前歌曲（无缝播放、淡入淡出）
+    fn save_analyzed_loudness(&mut self, ncm_api: &NcmApi) {
+        for (uri, loudness) in std::mem::take(&mut self.analyzed_loudness) {
+            let Some(song_info) = self
+                .play_history_stack
+                .iter()
+                .rev()
+                .find(|song_info| song_info.song_url == uri)
+            else {
+                continue;
+            };
+
+            if let Err(e) = ncm_api.set_song_loudness(song_info, loudness) {
+                error!("failed to save loudness of {}: {:?}", song_info.id, e);
+            }
+            // 当前歌曲的增益在下次播放时生效，避免播放中音量突变
+            if self
+                .current_song_info
+                .as_ref()
+                .is_some_and(|current| current.id == song_info.id)
+            {
+                self.current_song_loudness = Some(loudness);
+            }
+            // 预取时可能还没有分析结果（如单曲循环）
+            if let Prefetch::Ready { resolved, .. } = &mut self.prefetch {
+                if resolved.song_info.id == song_info.id {
+                    resolved.loudness = Some(loudness);
+                }
+            }
+        }
+    }
+
+    /// 按响度均衡模式设置当前歌曲的增益
+    fn apply_normalize_gain(&mut self) {
+        let loudness = match self.normalize_mode {
+            NormalizeMode::Off => None,
+            NormalizeMode::Track => self.current_song_loudness,
+            NormalizeMode::Album => self.current_album_loudness.or(self.current_song_loudness),
+        };
+        self.backend.set_gain(loudness.map_or(0.0, normalize_gain));
+    }
+
+    /// 播放网络歌曲出错时，每首歌可以重新获取一次链接
+    fn can_refresh_url(&self) -> bool {
+        self.play_state == PlayState::Playing
+            && !self.url_refreshed
+            && self
+                .current_song_info
+                .as_ref()
+                .is_some_and(|song_info| !is_local_song(song_info))
     }
 
     fn auto_lyric_forward(&mut self) {
@@ -499,14 +1301,14 @@ impl Player {
             self.current_song_lyric_index,
             self.current_song_lyric_timestamps.clone(),
         ) {
-            if let Some(current_position) = self.position() {
+            if let Some(current_position) = self.lyric_position() {
                 debug!("index: {}, len: {}", current_song_lyric_index, current_song_lyric_timestamps.len());
                 if current_song_lyric_index + 1 < current_song_lyric_timestamps.len() {
                     let next_timestamp =
                         current_song_lyric_timestamps[current_song_lyric_index + 1];
 
                     // 已经到下一句歌词的时间戳
-                    if current_position.mseconds() >= next_timestamp {
+                    if current_position >= next_timestamp {
                         self.current_song_lyric_index = Some(current_song_lyric_index + 1);
                     }
                 }
@@ -514,3 +1316,555 @@ impl Player {
         }
     }
 }
+
+/// 响度均衡的增益（dB），增益后的峰值不超过满幅，防止削波
+fn normalize_gain(loudness: Loudness) -> f64 {
+    if loudness.peak > 0.0 {
+        loudness.gain.min(-20.0 * loudness.peak.log10())
+    } else {
+        loudness.gain
+    }
+}
+
+/// 是否为同一专辑的歌曲，本地歌曲没有专辑 id，按专辑名和歌手判断
+fn is_same_album(a: &SongInfo, b: &SongInfo) -> bool {
+    if a.album_id != 0 || b.album_id != 0 {
+        a.album_id == b.album_id
+    } else {
+        !a.album.is_empty() && a.album == b.album && a.singer == b.singer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::local::LOCAL_SONG_ID_FLAG;
+    use gstreamer::glib;
+    use ncm_api::{FixtureTransport, SongCopyright};
+    use std::path::{Path, PathBuf};
+    use std::sync::Arc;
+    use std::{env, fs};
+    use tokio::sync::Mutex;
+
+    /// 每首歌时长，单位: ms
+    const SONG_DURATION: u64 = 3000;
+
+    /// 本地歌曲，播放时不访问网络，歌词读取 dir 下的同名 .lrc 文件
+    fn song(dir: &Path, n: u64) -> SongInfo {
+        let path = dir.join(format!("{}.wav", n));
+        SongInfo {
+            id: LOCAL_SONG_ID_FLAG | n,
+            name: format!("song {}", n),
+            singer: String::new(),
+            singers: Vec::new(),
+            album: String::new(),
+            album_id: 0,
+            pic_url: String::new(),
+            duration: SONG_DURATION,
+            song_url: glib::filename_to_uri(path, None).unwrap().to_string(),
+            copyright: SongCopyright::Free,
+        }
+    }
+
+    /// 使用 FakeBackend 的 Player，播放列表为 song_count 首本地歌曲
+    fn test_player(
+        name: &str,
+        song_count: u64,
+    ) -> (Player, FakeBackend, Mutex<NcmApi>, Vec<SongInfo>, PathBuf) {
+        let dir = env::temp_dir().join(format!("ncm-play-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        let backend = FakeBackend::new();
+        let songs: Vec<SongInfo> = (0..song_count).map(|n| song(&dir, n)).collect();
+        for song_info in songs.iter() {
+            backend.set_duration_for(&song_info.song_url, SONG_DURATION);
+        }
+
+        let mut player = Player::with_backend(Box::new(backend.clone()));
+        player.switch_playlist(String::from("test"), songs.clone());
+
+        let api = NcmApi::with_transport(
+            Arc::new(FixtureTransport::new()),
+            dir.join("cookies.json"),
+            dir.clone(),
+            dir.clone(),
+        );
+
+        (player, backend, Mutex::new(api), songs, dir)
+    }
+
+    #[test]
+    fn update_next_to_play_by_mode() {
+        let (mut player, _, _, songs, _) = test_player("next-by-mode", 3);
+        player.set_play_mode(PlayMode::ListRepeat);
+        player.current_song_index = Some(2);
+        player.current_song_info = Some(songs[2].clone());
+
+        // 列表循环播完最后一首回到第一首
+        player.update_next_to_play(false);
+        assert_eq!(player.current_song_index, Some(0));
+        assert_eq!(player.current_song_info, Some(songs[0].clone()));
+
+        player.set_play_mode(PlayMode::SingleRepeat);
+        player.update_next_to_play(false);
+        assert_eq!(player.current_song_info, Some(songs[0].clone()));
+
+        player.set_play_mode(PlayMode::Single);
+        player.update_next_to_play(false);
+        assert_eq!(player.current_song_info, None);
+    }
+
+    #[test]
+    fn update_next_to_play_prefers_queue() {
+        let (mut player, _, _, songs, _) = test_player("next-queue", 3);
+        player.set_play_mode(PlayMode::SingleRepeat);
+        player.current_song_index = Some(0);
+        player.current_song_info = Some(songs[0].clone());
+        player.play_queue_mut().append(songs[2].clone());
+
+        // 单曲循环自动切歌时不播放队列
+        player.update_next_to_play(false);
+        assert_eq!(player.current_song_info, Some(songs[0].clone()));
+        assert_eq!(player.play_queue_mut().len(), 1);
+
+        // 手动切歌时先播放队列，歌曲在播放列表中时同步位置
+        player.update_next_to_play(true);
+        assert_eq!(player.current_song_info, Some(songs[2].clone()));
+        assert_eq!(player.current_song_index, Some(2));
+        assert!(player.play_queue_mut().is_empty());
+    }
+
+    #[tokio::test]
+    async fn queue_plays_moved_and_removed_songs() {
+        let (mut player, backend, api, songs, _) = test_player("queue-moves", 4);
+        player.set_play_mode(PlayMode::ListRepeat);
+        player
+            .play_particularly_now(0, api.lock().await)
+            .await
+            .unwrap();
+        for song_info in [&songs[1], &songs[2], &songs[0]] {
+            player.play_queue_mut().append(song_info.clone());
+        }
+
+        // 将正在播放的歌曲移到队首，播放结束后再播放一次
+        assert!(player.play_queue_mut().move_to(2, 0));
+        backend.advance(SONG_DURATION);
+        player.auto_play(api.lock().await).await.unwrap();
+        assert_eq!(player.current_song_index(), Some(0));
+        assert_eq!(backend.uri(), Some(songs[0].song_url.clone()));
+        assert_eq!(
+            player.play_queue_ref().songs(),
+            vec![songs[1].clone(), songs[2].clone()]
+        );
+
+        // 立刻播放队尾的歌曲，其余歌曲保持顺序
+        player
+            .play_from_queue_now(1, api.lock().await)
+            .await
+            .unwrap();
+        assert_eq!(player.current_song_index(), Some(2));
+        assert_eq!(player.play_queue_ref().songs(), vec![songs[1].clone()]);
+
+        // 队列播完后按播放列表继续
+        backend.advance(SONG_DURATION);
+        player.auto_play(api.lock().await).await.unwrap();
+        assert_eq!(player.current_song_index(), Some(1));
+        assert!(player.play_queue_ref().is_empty());
+        backend.advance(SONG_DURATION);
+        player.auto_play(api.lock().await).await.unwrap();
+        assert_eq!(player.current_song_index(), Some(2));
+    }
+
+    #[tokio::test]
+    async fn auto_play_next_song_at_end_of_stream() {
+        let (mut player, backend, api, songs, _) = test_player("end-of-stream", 2);
+        player.set_play_mode(PlayMode::ListRepeat);
+        player
+            .play_particularly_now(0, api.lock().await)
+            .await
+            .unwrap();
+        assert_eq!(backend.uri(), Some(songs[0].song_url.clone()));
+
+        backend.advance(SONG_DURATION - 1);
+        player.auto_play(api.lock().await).await.unwrap();
+        assert_eq!(player.current_song_index(), Some(0));
+
+        backend.advance(1);
+        player.auto_play(api.lock().await).await.unwrap();
+        assert_eq!(player.current_song_index(), Some(1));
+        assert_eq!(backend.uri(), Some(songs[1].song_url.clone()));
+        assert!(player.is_playing());
+    }
+
+    #[tokio::test]
+    async fn auto_play_next_song_after_events_lagged() {
+        let (mut player, backend, api, songs, _) = test_player("events-lagged", 2);
+        player.set_play_mode(PlayMode::ListRepeat);
+        player
+            .play_particularly_now(0, api.lock().await)
+            .await
+            .unwrap();
+
+        // EndOfStream 之后的事件使通道溢出，EndOfStream 被丢弃
+        backend.advance(SONG_DURATION);
+        let mut flood = backend.clone();
+        for _ in 0..=crate::event::EVENT_CHANNEL_CAPACITY {
+            flood.pause();
+        }
+        player.auto_play(api.lock().await).await.unwrap();
+        assert_eq!(player.current_song_index(), Some(1));
+        assert_eq!(backend.uri(), Some(songs[1].song_url.clone()));
+    }
+
+    #[tokio::test]
+    async fn play_error_stops_playback() {
+        let (mut player, backend, api, _, _) = test_player("play-error", 2);
+        player.set_play_mode(PlayMode::ListRepeat);
+        player
+            .play_particularly_now(0, api.lock().await)
+            .await
+            .unwrap();
+
+        backend.fail("Not Found");
+        player.auto_play(api.lock().await).await.unwrap();
+        assert!(!player.is_playing());
+        assert_eq!(player.current_song_index(), Some(0));
+    }
+
+    #[tokio::test]
+    async fn play_prev_song_now_returns_to_previous() {
+        let (mut player, backend, api, songs, _) = test_player("prev-song", 3);
+        player.set_play_mode(PlayMode::ListRepeat);
+        player
+            .play_particularly_now(0, api.lock().await)
+            .await
+            .unwrap();
+        backend.advance(SONG_DURATION);
+        player.auto_play(api.lock().await).await.unwrap();
+        assert_eq!(player.current_song_index(), Some(1));
+
+        // 播放不足半秒时不切换
+        backend.advance(100);
+        player.play_prev_song_now(api.lock().await).await.unwrap();
+        assert_eq!(player.current_song_index(), Some(1));
+
+        backend.advance(400);
+        player.play_prev_song_now(api.lock().await).await.unwrap();
+        assert_eq!(player.current_song_index(), Some(0));
+        assert_eq!(backend.uri(), Some(songs[0].song_url.clone()));
+
+        // 已是第一首时保持不变
+        backend.advance(500);
+        player.play_prev_song_now(api.lock().await).await.unwrap();
+        assert_eq!(player.current_song_index(), Some(0));
+        assert_eq!(player.play_history_stack, vec![songs[0].clone()]);
+    }
+
+    #[tokio::test]
+    async fn auto_lyric_forward_with_offset() {
+        let (mut player, backend, api, _, dir) = test_player("lyric-offset", 1);
+        fs::write(
+            dir.join("0.lrc"),
+            "[00:00.00]一\n[00:01.00]二\n[00:02.00]三",
+        )
+        .unwrap();
+        player
+            .play_particularly_now(0, api.lock().await)
+            .await
+            .unwrap();
+        assert_eq!(player.current_song_lyric_index(), Some(0));
+
+        backend.advance(1000);
+        player.auto_play(api.lock().await).await.unwrap();
+        assert_eq!(player.current_song_lyric_index(), Some(1));
+
+        // 歌词延后 500ms 显示，回到上一句
+        player
+            .set_current_song_lyric_offset(api.lock().await, 500)
+            .await
+            .unwrap();
+        assert_eq!(player.current_song_lyric_index(), Some(0));
+        assert_eq!(
+            api.lock()
+                .await
+                .lyric_offset(player.current_song_info.as_ref().unwrap().id),
+            500
+        );
+
+        backend.advance(500);
+        player.auto_play(api.lock().await).await.unwrap();
+        assert_eq!(player.current_song_lyric_index(), Some(1));
+
+        // 跳转到歌词时间戳时同样应用偏移
+        player.seek_to_timestamp_with_index(2).await.unwrap();
+        assert_eq!(player.position(), Some(ClockTime::from_mseconds(2500)));
+        assert_eq!(player.current_song_lyric_index(), Some(2));
+    }
+
+    #[tokio::test]
+    async fn prefetch_next_song_for_gapless_switch() {
+        let (mut player, backend, api, songs, dir) = test_player("gapless", 2);
+        fs::write(dir.join("1.lrc"), "[00:00.00]一\n[00:00.10]二").unwrap();
+        player.set_play_mode(PlayMode::ListRepeat);
+        player
+            .play_particularly_now(0, api.lock().await)
+            .await
+            .unwrap();
+        assert_eq!(backend.next_uri(), None);
+
+        // 剩余时间不多时预取下一首并交给播放后端
+        player.auto_play(api.lock().await).await.unwrap();
+        assert_eq!(backend.next_uri(), Some(songs[1].song_url.clone()));
+
+        backend.advance(SONG_DURATION + 200);
+        player.auto_play(api.lock().await).await.unwrap();
+        assert_eq!(player.current_song_index(), Some(1));
+        assert_eq!(backend.uri(), Some(songs[1].song_url.clone()));
+        assert!(player.is_playing());
+        assert_eq!(player.play_history_stack, songs);
+        // 无缝切换后从当前位置对应的歌词开始
+        assert_eq!(player.current_song_lyric_index(), Some(1));
+        // 列表循环，继续预取第一首
+        assert_eq!(backend.next_uri(), Some(songs[0].song_url.clone()));
+    }
+
+    #[tokio::test]
+    async fn prefetch_follows_queue_changes() {
+        let (mut player, backend, api, songs, _) = test_player("prefetch-queue", 3);
+        player.set_play_mode(PlayMode::ListRepeat);
+        player
+            .play_particularly_now(0, api.lock().await)
+            .await
+            .unwrap();
+        player.auto_play(api.lock().await).await.unwrap();
+        assert_eq!(backend.next_uri(), Some(songs[1].song_url.clone()));
+
+        player.play_queue_mut().append(songs[2].clone());
+        player.auto_play(api.lock().await).await.unwrap();
+        assert_eq!(backend.next_uri(), Some(songs[2].song_url.clone()));
+
+        backend.advance(SONG_DURATION);
+        player.auto_play(api.lock().await).await.unwrap();
+        assert_eq!(player.current_song_index(), Some(2));
+        assert!(player.play_queue_mut().is_empty());
+    }
+
+    #[tokio::test]
+    async fn single_mode_does_not_prefetch() {
+        let (mut player, backend, api, _, _) = test_player("prefetch-single", 2);
+        player.set_play_mode(PlayMode::Single);
+        player
+            .play_particularly_now(0, api.lock().await)
+            .await
+            .unwrap();
+        player.auto_play(api.lock().await).await.unwrap();
+        assert_eq!(backend.next_uri(), None);
+
+        backend.advance(SONG_DURATION);
+        player.auto_play(api.lock().await).await.unwrap();
+        player.auto_play(api.lock().await).await.unwrap();
+        assert!(!player.is_playing());
+        assert!(!backend.is_playing());
+    }
+
+    #[tokio::test]
+    async fn refresh_song_url_once_on_play_error() {
+        let dir = env::temp_dir().join(format!("ncm-play-refresh-url-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let transport = Arc::new(FixtureTransport::new().with_fixture(
+            "/eapi/song/enhance/player/url",
+            include_str!("../../ncm-api/fixtures/song_url.json"),
+        ));
+        let api = Mutex::new(NcmApi::with_transport(
+            transport.clone(),
+            dir.join("cookies.json"),
+            dir.clone(),
+            dir.clone(),
+        ));
+        let backend = FakeBackend::new();
+        let mut player = Player::with_backend(Box::new(backend.clone()));
+        let song_info = SongInfo {
+            id: 186016,
+            name: String::from("晴天"),
+            singer: String::from("周杰伦"),
+            singers: Vec::new(),
+            album: String::from("叶惠美"),
+            album_id: 18905,
+            pic_url: String::new(),
+            duration: 269000,
+            song_url: String::new(),
+            copyright: SongCopyright::Free,
+        };
+        player.switch_playlist(String::from("test"), vec![song_info]);
+        player
+            .play_particularly_now(0, api.lock().await)
+            .await
+            .unwrap();
+        let url_requests = || {
+            transport
+                .requests()
+                .iter()
+                .filter(|path| path.ends_with("/player/url"))
+                .count()
+        };
+        assert_eq!(url_requests(), 1);
+
+        // 链接失效时重新获取链接，从出错位置继续播放
+        backend.advance(1000);
+        backend.fail("Forbidden");
+        player.auto_play(api.lock().await).await.unwrap();
+        assert_eq!(url_requests(), 2);
+        assert!(player.is_playing());
+        assert_eq!(player.position(), Some(ClockTime::from_mseconds(1000)));
+        assert_eq!(player.play_history_stack.len(), 1);
+
+        // 每首歌只重新获取一次
+        backend.fail("Forbidden");
+        player.auto_play(api.lock().await).await.unwrap();
+        assert_eq!(url_requests(), 2);
+        assert!(!player.is_playing());
+    }
+
+    #[tokio::test]
+    async fn crossfade_to_next_song_near_end() {
+        let (mut player, backend, api, songs, _) = test_player("crossfade", 2);
+        player.set_play_mode(PlayMode::ListRepeat);
+        player.set_crossfade(1);
+        player
+            .play_particularly_now(0, api.lock().await)
+            .await
+            .unwrap();
+
+        // 淡入淡出时不交给播放后端无缝播放
+        player.auto_play(api.lock().await).await.unwrap();
+        assert_eq!(backend.next_uri(), None);
+        assert_eq!(player.current_song_index(), Some(0));
+
+        backend.advance(SONG_DURATION - 1000);
+        player.auto_play(api.lock().await).await.unwrap();
+        assert_eq!(player.current_song_index(), Some(1));
+        assert_eq!(backend.uri(), Some(songs[1].song_url.clone()));
+        assert_eq!(backend.fading_out_uri(), Some(songs[0].song_url.clone()));
+        assert_eq!(player.play_history_stack, songs);
+
+        backend.advance(1000);
+        assert_eq!(backend.fading_out_uri(), None);
+    }
+
+    #[tokio::test]
+    async fn crossfade_disabled_for_single_repeat_and_albums() {
+        let (mut player, backend, api, songs, _) = test_player("crossfade-repeat", 2);
+        player.set_crossfade(1);
+        player.set_play_mode(PlayMode::SingleRepeat);
+        player
+            .play_particularly_now(0, api.lock().await)
+            .await
+            .unwrap();
+        player.auto_play(api.lock().await).await.unwrap();
+        assert_eq!(backend.next_uri(), Some(songs[0].song_url.clone()));
+
+        // 按顺序播放同一专辑时无缝播放
+        let (mut player, backend, api, mut songs, _) = test_player("crossfade-album", 2);
+        for song_info in songs.iter_mut() {
+            song_info.album = String::from("范特西");
+            song_info.singer = String::from("周杰伦");
+        }
+        player.switch_playlist(String::from("album"), songs.clone());
+        player.set_crossfade(1);
+        player.set_play_mode(PlayMode::ListRepeat);
+        player
+            .play_particularly_now(0, api.lock().await)
+            .await
+            .unwrap();
+        player.auto_play(api.lock().await).await.unwrap();
+        assert_eq!(backend.next_uri(), Some(songs[1].song_url.clone()));
+
+        backend.advance(SONG_DURATION);
+        player.auto_play(api.lock().await).await.unwrap();
+        assert_eq!(player.current_song_index(), Some(1));
+        assert_eq!(backend.fading_out_uri(), None);
+    }
+
+    #[tokio::test]
+    async fn play_next_song_now_crossfades() {
+        let (mut player, backend, api, songs, _) = test_player("crossfade-next", 3);
+        player.set_play_mode(PlayMode::ListRepeat);
+        player.set_crossfade(MAX_CROSSFADE + 1);
+        assert_eq!(player.crossfade(), MAX_CROSSFADE);
+        player
+            .play_particularly_now(0, api.lock().await)
+            .await
+            .unwrap();
+
+        backend.advance(600);
+        player.play_next_song_now(api.lock().await).await.unwrap();
+        assert_eq!(player.current_song_index(), Some(1));
+        assert_eq!(backend.uri(), Some(songs[1].song_url.clone()));
+        assert_eq!(backend.fading_out_uri(), Some(songs[0].song_url.clone()));
+
+        // 不超过当前歌曲时长的一半
+        backend.advance(SONG_DURATION / 2);
+        assert_eq!(backend.fading_out_uri(), None);
+    }
+
+    #[tokio::test]
+    async fn normalize_with_analyzed_loudness() {
+        let (mut player, backend, api, mut songs, _) = test_player("normalize", 2);
+        for song_info in songs.iter_mut() {
+            song_info.album_id = 18905;
+        }
+        player.switch_playlist(String::from("album"), songs.clone());
+        player.set_play_mode(PlayMode::ListRepeat);
+        player.set_normalize_mode(NormalizeMode::Track);
+        player
+            .play_particularly_now(0, api.lock().await)
+            .await
+            .unwrap();
+        // 未分析过的歌曲不调整
+        assert_eq!(backend.gain(), 0.0);
+
+        let loudness = Loudness {
+            gain: -6.0,
+            peak: 0.9,
+        };
+        backend.analyze(loudness);
+        player.auto_play(api.lock().await).await.unwrap();
+        assert_eq!(api.lock().await.song_loudness(songs[0].id), Some(loudness));
+        // 播放中不改变增益
+        assert_eq!(backend.gain(), 0.0);
+
+        player
+            .play_particularly_now(0, api.lock().await)
+            .await
+            .unwrap();
+        assert_eq!(backend.gain(), -6.0);
+
+        // 专辑模式下，未分析过的歌曲使用专辑响度
+        player.set_normalize_mode(NormalizeMode::Album);
+        player
+            .play_particularly_now(1, api.lock().await)
+            .await
+            .unwrap();
+        assert!((backend.gain() + 6.0).abs() < 1e-9);
+
+        player.set_normalize_mode(NormalizeMode::Off);
+        assert_eq!(backend.gain(), 0.0);
+        // 关闭后不再分析响度
+        assert!(!backend.normalize_enabled());
+        backend.analyze(Loudness {
+            gain: -3.0,
+            peak: 0.5,
+        });
+        player.auto_play(api.lock().await).await.unwrap();
+        assert_eq!(api.lock().await.song_loudness(songs[1].id), None);
+
+        // 增益后的峰值不超过满幅
+        let gain = normalize_gain(Loudness {
+            gain: 6.0,
+            peak: 0.9,
+        });
+        assert!((gain + 20.0 * 0.9_f64.log10()).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,219 @@
+//
+// 本地音频文件
+//
+use anyhow::{anyhow, Result};
+use gstreamer::ClockTime;
+use gstreamer_pbutils::{Discoverer, DiscovererInfo};
+use gstreamer_play::gst;
+use log::warn;
+use ncm_api::{Lrc, LyricLine, SongCopyright, SongInfo, SongUrl};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 按扩展名识别的音频文件
+const AUDIO_EXTENSIONS: [&str; 10] = [
+    "mp3", "flac", "ogg", "opus", "m4a", "aac", "wav", "ape", "wma", "aiff",
+];
+
+/// 读取单个文件标签的超时，单位: s
+const DISCOVER_TIMEOUT: u64 = 5;
+
+/// 本地歌曲 id 的最高位为 1，不会与网易云的歌曲 id 重复
+pub(crate) const LOCAL_SONG_ID_FLAG: u64 = 1 << 63;
+
+/// 读取本地音频文件，path 为目录时递归读取其中所有音频文件，按路径排序
+/// 标签中没有歌名时使用文件名，无法识别的文件会被跳过
+pub fn load_local(path: &Path) -> Result<Vec<SongInfo>> {
+    gst::init()?;
+
+    let mut files = Vec::new();
+    if path.is_dir() {
+        collect_audio_files(path, &mut files)?;
+        files.sort();
+    } else if path.is_file() {
+        files.push(path.to_path_buf());
+    } else {
+        return Err(anyhow!("{} 不存在", path.display()));
+    }
+
+    let discoverer = Discoverer::new(ClockTime::from_seconds(DISCOVER_TIMEOUT))?;
+    let mut songs = Vec::new();
+    for file in files {
+        let file = file.canonicalize().unwrap_or(file);
+        let uri = gst::glib::filename_to_uri(&file, None)?;
+        match discoverer.discover_uri(&uri) {
+            Ok(info) => songs.push(song_info_from_discoverer(&file, uri.as_str(), &info)),
+            Err(e) => warn!("无法读取音频文件 {}: {}", file.display(), e),
+        }
+    }
+
+    Ok(songs)
+}
+
+/// 是否为 load_local 读取的本地歌曲
+pub fn is_local_song(song_info: &SongInfo) -> bool {
+    song_info.id & LOCAL_SONG_ID_FLAG != 0
+}
+
+/// 本地歌曲的文件路径
+pub fn local_song_path(song_info: &SongInfo) -> Option<PathBuf> {
+    if !is_local_song(song_info) {
+        return None;
+    }
+    gst::glib::filename_from_uri(&song_info.song_url)
+        .ok()
+        .map(|(path, _)| path)
+}
+
+/// 本地歌曲的文件信息，码率按文件大小与时长估算
+pub fn local_song_url(song_info: &SongInfo) -> Option<SongUrl> {
+    let path = local_song_path(song_info)?;
+    let size = fs::metadata(&path).ok()?.len();
+    let rate = if song_info.duration > 0 {
+        (size * 8 * 1000 / song_info.duration) as u32
+    } else {
+        0
+    };
+
+    Some(SongUrl {
+        id: song_info.id,
+        url: song_info.song_url.clone(),
+        rate,
+        size,
+        codec: path
+            .extension()
+            .map(|extension| extension.to_string_lossy().to_lowercase())
+            .unwrap_or_default(),
+    })
+}
+
+/// 读取与音频文件同名的 .lrc 歌词
+pub fn local_song_lyrics(song_info: &SongInfo) -> Option<Vec<LyricLine>> {
+    let lrc_path = local_song_path(song_info)?.with_extension("lrc");
+    let lyric = Lrc::parse(&fs::read_to_string(lrc_path).ok()?);
+    if lyric.is_empty() {
+        return None;
+    }
+
+    Some(lyric.merge_layers(&Lrc::default(), &Lrc::default()))
+}
+
+fn collect_audio_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_audio_files(&path, files)?;
+        } else if path.extension().is_some_and(|extension| {
+            AUDIO_EXTENSIONS.contains(&extension.to_string_lossy().to_lowercase().as_str())
+        }) {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+fn song_info_from_discoverer(file: &Path, uri: &str, info: &DiscovererInfo) -> SongInfo {
+    let tags = info.tags();
+    let title = tags
+        .as_ref()
+        .and_then(|tags| tags.get::<gst::tags::Title>())
+        .map(|title| title.get().to_string());
+    let artist = tags
+        .as_ref()
+        .and_then(|tags| tags.get::<gst::tags::Artist>())
+        .map(|artist| artist.get().to_string());
+    let album = tags
+        .as_ref()
+        .and_then(|tags| tags.get::<gst::tags::Album>())
+        .map(|album| album.get().to_string());
+
+    SongInfo {
+        id: local_song_id(file),
+        name: title.unwrap_or_else(|| {
+            file.file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default()
+        }),
+        singer: artist.unwrap_or_default(),
+        singers: Vec::new(),
+        album: album.unwrap_or_default(),
+        album_id: 0,
+        pic_url: String::new(),
+        duration: info.duration().map_or(0, |duration| duration.mseconds()),
+        song_url: uri.to_string(),
+        copyright: SongCopyright::Free,
+    }
+}
+
+/// 由文件路径生成固定的歌曲 id（FNV-1a），重启后不变，用于保存歌词时间偏移等
+fn local_song_id(file: &Path) -> u64 {
+    let hash = file
+        .to_string_lossy()
+        .bytes()
+        .fold(0xcbf29ce484222325_u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        });
+
+    hash | LOCAL_SONG_ID_FLAG
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    /// 生成 duration_ms 长的静音 wav 文件（8kHz 单声道 16bit）
+    fn write_wav(path: &Path, duration_ms: u32) {
+        let data_len = 8000 * 2 * duration_ms / 1000;
+        let mut wav = Vec::new();
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+        wav.extend_from_slice(b"WAVEfmt ");
+        wav.extend_from_slice(&16u32.to_le_bytes());
+        wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+        wav.extend_from_slice(&1u16.to_le_bytes()); // 单声道
+        wav.extend_from_slice(&8000u32.to_le_bytes());
+        wav.extend_from_slice(&16000u32.to_le_bytes());
+        wav.extend_from_slice(&2u16.to_le_bytes());
+        wav.extend_from_slice(&16u16.to_le_bytes());
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&data_len.to_le_bytes());
+        wav.resize(wav.len() + data_len as usize, 0);
+        fs::write(path, wav).unwrap();
+    }
+
+    #[test]
+    fn load_local_dir_with_lyrics() {
+        let dir = env::temp_dir().join(format!("ncm-play-local-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("disc2")).unwrap();
+        write_wav(&dir.join("01 静音.wav"), 2000);
+        write_wav(&dir.join("disc2").join("02 silence.wav"), 1000);
+        fs::write(
+            dir.join("01 静音.lrc"),
+            "[00:00.50]第一句\n[00:01.50]第二句",
+        )
+        .unwrap();
+        fs::write(dir.join("cover.jpg"), b"not audio").unwrap();
+
+        let songs = load_local(&dir).unwrap();
+        let names: Vec<&str> = songs.iter().map(|song| song.name.as_str()).collect();
+        assert_eq!(names, vec!["01 静音", "02 silence"]);
+        assert!(songs.iter().all(is_local_song));
+        assert_ne!(songs[0].id, songs[1].id);
+        assert!(songs[0].duration.abs_diff(2000) < 50);
+
+        let song_url = local_song_url(&songs[0]).unwrap();
+        assert_eq!(song_url.codec, "wav");
+        assert_eq!(song_url.size, 44 + 32000);
+
+        let lyrics = local_song_lyrics(&songs[0]).unwrap();
+        let texts: Vec<&str> = lyrics.iter().map(|line| line.text.as_str()).collect();
+        assert_eq!(texts, vec!["第一句", "第二句"]);
+        assert!(local_song_lyrics(&songs[1]).is_none());
+
+        // 重新读取时 id 不变
+        assert_eq!(load_local(&dir).unwrap()[0].id, songs[0].id);
+    }
+}
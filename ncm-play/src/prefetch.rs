@@ -0,0 +1,208 @@
+//
+// 预取下一首歌曲
+//
+use crate::local::{self, is_local_song};
+use crate::{Player, RadioSource};
+use anyhow::Result;
+use log::warn;
+use ncm_api::{Loudness, LyricLine, NcmApi, SongInfo, SongUrl};
+use std::time::{Duration, Instant};
+use tokio::sync::oneshot;
+
+/// 当前歌曲剩余时间不多于此值时预取下一首，单位: ms
+pub(crate) const PREFETCH_BEFORE_END: u64 = 30_000;
+
+/// 歌曲链接的有效时间，网易云的链接约 20 分钟后失效，留出余量
+const SONG_URL_TTL: Duration = Duration::from_secs(15 * 60);
+
+/// 下一首播放的歌曲
+#[derive(Clone)]
+pub(crate) struct NextSong {
+    pub(crate) song_info: SongInfo,
+    pub(crate) index: Option<usize>, // 歌曲在播放列表中的位置
+    pub(crate) from_queue: bool,
+}
+
+/// 已获取链接和歌词，可以直接播放的歌曲
+pub(crate) struct ResolvedSong {
+    pub(crate) song_info: SongInfo, // song_url 为实际播放的链接
+    pub(crate) song_url: Option<SongUrl>,
+    pub(crate) lyrics: Option<Vec<LyricLine>>,
+    pub(crate) lyric_offset: i64,
+    pub(crate) loudness: Option<Loudness>,
+    pub(crate) album_loudness: Option<Loudness>,
+    resolved_at: Instant,
+}
+
+impl ResolvedSong {
+    /// 获取歌曲链接和歌词
+    /// 获取链接失败时（无版权、网络异常等）返回错误，获取歌词失败时视为无歌词
+    pub(crate) async fn resolve(ncm_api: &NcmApi, mut song_info: SongInfo) -> Result<Self> {
+        if is_local_song(&song_info) {
+            return Ok(Self::local(ncm_api, song_info));
+        }
+
+        let song_url = ncm_api.get_song_url(song_info.id).await?;
+        song_info.song_url = song_url.url.clone();
+        let lyrics = ncm_api.song_lyric(song_info.clone()).await.ok();
+
+        Ok(Self {
+            lyric_offset: ncm_api.lyric_offset(song_info.id),
+            loudness: ncm_api.song_loudness(song_info.id),
+            album_loudness: ncm_api.album_loudness(song_info.album_id),
+            song_info,
+            song_url: Some(song_url),
+            lyrics,
+            resolved_at: Instant::now(),
+        })
+    }
+
+    /// 本地歌曲已有文件 uri，歌词使用同名 .lrc 文件，不需要访问网络
+    pub(crate) fn local(ncm_api: &NcmApi, song_info: SongInfo) -> Self {
+        Self {
+            lyric_offset: ncm_api.lyric_offset(song_info.id),
+            loudness: ncm_api.song_loudness(song_info.id),
+            album_loudness: ncm_api.album_loudness(song_info.album_id),
+            song_url: local::local_song_url(&song_info),
+            lyrics: local::local_song_lyrics(&song_info),
+            song_info,
+            resolved_at: Instant::now(),
+        }
+    }
+
+    /// 链接是否已失效，本地歌曲不会失效
+    pub(crate) fn is_expired(&self) -> bool {
+        !is_local_song(&self.song_info) && self.resolved_at.elapsed() >= SONG_URL_TTL
+    }
+}
+
+/// 下一首的预取状态
+pub(crate) enum Prefetch {
+    None,
+    /// 正在后台获取，不占用 NcmApi 的锁
+    Pending {
+        next: NextSong,
+        receiver: oneshot::Receiver<Result<ResolvedSong>>,
+    },
+    /// 获取完成
+    /// handed_over: 是否已交给播放后端无缝切换
+    Ready {
+        next: NextSong,
+        resolved: ResolvedSong,
+        handed_over: bool,
+    },
+    /// 获取失败，切歌时再获取一次
+    Failed {
+        next: NextSong,
+    },
+}
+
+impl Prefetch {
+    /// 开始获取 next 的链接和歌词，网络歌曲在后台任务中获取
+    pub(crate) fn start(ncm_api: &NcmApi, next: NextSong) -> Self {
+        if is_local_song(&next.song_info) {
+            return Prefetch::Ready {
+                resolved: ResolvedSong::local(ncm_api, next.song_info.clone()),
+                next,
+                handed_over: false,
+            };
+        }
+
+        let (sender, receiver) = oneshot::channel();
+        let ncm_api = ncm_api.clone();
+        let song_info = next.song_info.clone();
+        tokio::spawn(async move {
+            // 预取已取消时 receiver 被 drop，发送失败，忽略即可
+            let _ = sender.send(ResolvedSong::resolve(&ncm_api, song_info).await);
+        });
+
+        Prefetch::Pending { next, receiver }
+    }
+
+    /// 检查后台获取是否完成
+    pub(crate) fn poll(&mut self) {
+        if let Prefetch::Pending { next, receiver } = self {
+            let result = match receiver.try_recv() {
+                Ok(result) => result,
+                Err(oneshot::error::TryRecvError::Empty) => return,
+                Err(oneshot::error::TryRecvError::Closed) => {
+                    Err(anyhow::anyhow!("prefetch task aborted"))
+                }
+            };
+
+            let next = next.clone();
+            *self = match result {
+                Ok(resolved) => Prefetch::Ready {
+                    next,
+                    resolved,
+                    handed_over: false,
+                },
+                Err(e) => {
+                    warn!("failed to prefetch song {}: {:?}", next.song_info.id, e);
+                    Prefetch::Failed { next }
+                }
+            };
+        }
+    }
+
+    pub(crate) fn next(&self) -> Option<&NextSong> {
+        match self {
+            Prefetch::None => None,
+            Prefetch::Pending { next, .. }
+            | Prefetch::Ready { next, .. }
+            | Prefetch::Failed { next } => Some(next),
+        }
+    }
+
+    /// 已获取但链接已失效
+    pub(crate) fn is_expired(&self) -> bool {
+        matches!(self, Prefetch::Ready { resolved, .. } if resolved.is_expired())
+    }
+}
+
+/// 电台模式下在后台获取更多歌曲的状态
+pub(crate) enum RadioRefill {
+    Idle,
+    /// 正在后台获取，不占用 NcmApi 的锁
+    Pending {
+        receiver: oneshot::Receiver<Result<Vec<SongInfo>>>,
+    },
+    /// 当前歌曲已获取过（失败时也不再重试，切换到下一首后再获取）
+    Done,
+}
+
+impl RadioRefill {
+    pub(crate) fn start(ncm_api: &NcmApi, source: RadioSource) -> Self {
+        let (sender, receiver) = oneshot::channel();
+        let ncm_api = ncm_api.clone();
+        tokio::spawn(async move {
+            // 已切换播放列表时 receiver 被 drop，发送失败，忽略即可
+            let _ = sender.send(Player::fetch_radio_songs(&source, &ncm_api).await);
+        });
+
+        RadioRefill::Pending { receiver }
+    }
+
+    /// 检查后台获取是否完成，完成时返回获取到的歌曲
+    pub(crate) fn poll(&mut self) -> Option<Vec<SongInfo>> {
+        let RadioRefill::Pending { receiver } = self else {
+            return None;
+        };
+        let result = match receiver.try_recv() {
+            Ok(result) => result,
+            Err(oneshot::error::TryRecvError::Empty) => return None,
+            Err(oneshot::error::TryRecvError::Closed) => {
+                Err(anyhow::anyhow!("radio refill task aborted"))
+            }
+        };
+
+        *self = RadioRefill::Done;
+        match result {
+            Ok(songs) => Some(songs),
+            Err(e) => {
+                warn!("failed to refill radio buffer: {:?}", e);
+                None
+            }
+        }
+    }
+}
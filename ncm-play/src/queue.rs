@@ -0,0 +1,185 @@
+use ncm_api::SongInfo;
+use std::collections::VecDeque;
+
+/// 播放队列
+/// 队列中的歌曲优先于当前播放列表播放，与播放模式的关系：
+///   - 自动切歌时：除`单曲循环`外，先依次播放队列中的歌曲，队列为空后再按播放模式从播放列表中选择下一首
+///   - 手动切到下一首时：任何模式下都先播放队列中的歌曲
+///   - 队列中的歌曲播放后即出队，不会因`列表循环`或`随机播放`被再次选中
+#[derive(Default)]
+pub struct PlayQueue {
+    songs: VecDeque<SongInfo>,
+}
+
+impl PlayQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.songs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.songs.is_empty()
+    }
+
+    pub fn songs(&self) -> Vec<SongInfo> {
+        self.songs.iter().cloned().collect()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&SongInfo> {
+        self.songs.get(index)
+    }
+
+    /// 下一首播放：放到队首
+    /// 歌曲已在队列中时移动到队首
+    pub fn play_next(&mut self, song_info: SongInfo) {
+        self.songs.retain(|s| *s != song_info);
+        self.songs.push_front(song_info);
+    }
+
+    /// 加入队尾，歌曲已在队列中时不重复加入
+    /// 返回是否加入
+    pub fn append(&mut self, song_info: SongInfo) -> bool {
+        if self.songs.contains(&song_info) {
+            return false;
+        }
+        self.songs.push_back(song_info);
+
+        true
+    }
+
+    /// 移除指定位置的歌曲
+    pub fn remove(&mut self, index: usize) -> Option<SongInfo> {
+        self.songs.remove(index)
+    }
+
+    /// 将 from 处的歌曲移动到 to 处
+    /// 返回是否移动成功
+    pub fn move_to(&mut self, from: usize, to: usize) -> bool {
+        if from >= self.songs.len() || to >= self.songs.len() {
+            return false;
+        }
+        if from != to {
+            if let Some(song_info) = self.songs.remove(from) {
+                self.songs.insert(to, song_info);
+            }
+        }
+
+        true
+    }
+
+    pub fn clear(&mut self) {
+        self.songs.clear();
+    }
+
+    /// 出队下一首要播放的歌曲
+    pub fn pop_front(&mut self) -> Option<SongInfo> {
+        self.songs.pop_front()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ncm_api::SongCopyright;
+
+    fn song(id: u64) -> SongInfo {
+        SongInfo {
+            id,
+            name: format!("song {id}"),
+            singer: String::new(),
+            singers: Vec::new(),
+            album: String::new(),
+            album_id: 0,
+            pic_url: String::new(),
+            duration: 0,
+            song_url: String::new(),
+            copyright: SongCopyright::Free,
+        }
+    }
+
+    fn queue(ids: &[u64]) -> PlayQueue {
+        let mut queue = PlayQueue::new();
+        for id in ids {
+            queue.append(song(*id));
+        }
+        queue
+    }
+
+    fn ids(queue: &PlayQueue) -> Vec<u64> {
+        queue.songs().iter().map(|song_info| song_info.id).collect()
+    }
+
+    #[test]
+    fn empty_queue() {
+        let mut queue = PlayQueue::new();
+
+        assert!(queue.is_empty());
+        assert_eq!(queue.get(0), None);
+        assert_eq!(queue.remove(0), None);
+        assert!(!queue.move_to(0, 0));
+        assert_eq!(queue.pop_front(), None);
+
+        queue.play_next(song(1));
+        assert_eq!(ids(&queue), vec![1]);
+    }
+
+    #[test]
+    fn append_skips_queued_song() {
+        let mut queue = queue(&[1, 2]);
+
+        assert!(queue.append(song(3)));
+        assert!(!queue.append(song(1)));
+        assert_eq!(ids(&queue), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn play_next_moves_queued_song_to_front() {
+        let mut queue = queue(&[1, 2, 3]);
+
+        queue.play_next(song(4));
+        assert_eq!(ids(&queue), vec![4, 1, 2, 3]);
+
+        // 已在队列中的歌曲只移动，不重复
+        queue.play_next(song(3));
+        assert_eq!(ids(&queue), vec![3, 4, 1, 2]);
+        queue.play_next(song(3));
+        assert_eq!(ids(&queue), vec![3, 4, 1, 2]);
+    }
+
+    #[test]
+    fn move_to() {
+        let mut queue = queue(&[1, 2, 3, 4]);
+
+        assert!(queue.move_to(0, 3));
+        assert_eq!(ids(&queue), vec![2, 3, 4, 1]);
+        assert!(queue.move_to(3, 0));
+        assert_eq!(ids(&queue), vec![1, 2, 3, 4]);
+        assert!(queue.move_to(2, 1));
+        assert_eq!(ids(&queue), vec![1, 3, 2, 4]);
+        assert!(queue.move_to(2, 2));
+        assert_eq!(ids(&queue), vec![1, 3, 2, 4]);
+
+        // 越界时不改变队列
+        assert!(!queue.move_to(4, 0));
+        assert!(!queue.move_to(0, 4));
+        assert_eq!(ids(&queue), vec![1, 3, 2, 4]);
+    }
+
+    #[test]
+    fn remove_around_index() {
+        let mut queue = queue(&[1, 2, 3, 4]);
+
+        assert_eq!(queue.remove(1).map(|s| s.id), Some(2));
+        assert_eq!(ids(&queue), vec![1, 3, 4]);
+        assert_eq!(queue.remove(0).map(|s| s.id), Some(1));
+        assert_eq!(queue.remove(1).map(|s| s.id), Some(4));
+        assert_eq!(queue.remove(1), None);
+        assert_eq!(ids(&queue), vec![3]);
+
+        assert_eq!(queue.pop_front().map(|s| s.id), Some(3));
+        assert!(queue.is_empty());
+    }
+}
@@ -1,9 +1,13 @@
+mod cli;
 mod command;
 mod logo;
 mod path;
+mod settings;
 mod ui;
 
+pub use cli::*;
 pub use command::*;
 pub use logo::*;
 pub use path::*;
+pub use settings::*;
 pub use ui::*;
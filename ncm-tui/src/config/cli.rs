@@ -0,0 +1,75 @@
+use clap::builder::PossibleValuesParser;
+use clap::{value_parser, Arg, Command};
+use std::path::PathBuf;
+
+/// 命令行参数
+/// 未指定的参数使用配置文件中的值
+pub struct CliArgs {
+    /// 启动时加载的歌单在用户歌单列表中的位置（0 为“我喜欢的音乐”）
+    pub playlist: Option<u16>,
+    /// 配置文件路径
+    pub config: Option<PathBuf>,
+    /// 日志等级
+    pub log_level: Option<String>,
+    /// 代理地址
+    pub proxy: Option<String>,
+    /// 启动时打开的本地音频目录或文件
+    pub open: Option<PathBuf>,
+}
+
+impl CliArgs {
+    pub fn parse() -> Self {
+        let matches = Command::new("ncm-tui-player")
+            .version(env!("CARGO_PKG_VERSION"))
+            .about("A TUI player client for netease-cloud-music written in Rust.")
+            .arg(
+                Arg::new("playlist")
+                    .short('p')
+                    .long("playlist")
+                    .visible_alias("playList")
+                    .value_name("INDEX")
+                    .value_parser(value_parser!(u16))
+                    .help("Index of the playlist loaded at startup (0 is \"liked songs\")"),
+            )
+            .arg(
+                Arg::new("config")
+                    .short('c')
+                    .long("config")
+                    .value_name("FILE")
+                    .value_parser(value_parser!(PathBuf))
+                    .help("Path of the config file"),
+            )
+            .arg(
+                Arg::new("log-level")
+                    .long("log-level")
+                    .value_name("LEVEL")
+                    .value_parser(PossibleValuesParser::new([
+                        "off", "error", "warn", "info", "debug", "trace",
+                    ]))
+                    .help("Log level, logs are written to the data directory"),
+            )
+            .arg(
+                Arg::new("proxy")
+                    .long("proxy")
+                    .value_name("URL")
+                    .help("Proxy for all requests, e.g. socks5://127.0.0.1:1080"),
+            )
+            .arg(
+                Arg::new("open")
+                    .short('o')
+                    .long("open")
+                    .value_name("PATH")
+                    .value_parser(value_parser!(PathBuf))
+                    .help("Local audio directory or file opened as the playlist at startup"),
+            )
+            .get_matches();
+
+        Self {
+            playlist: matches.get_one::<u16>("playlist").copied(),
+            config: matches.get_one::<PathBuf>("config").cloned(),
+            log_level: matches.get_one::<String>("log-level").cloned(),
+            proxy: matches.get_one::<String>("proxy").cloned(),
+            open: matches.get_one::<PathBuf>("open").cloned(),
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use crate::config::Command::SwitchPlayMode;
-use crate::config::ScreenEnum;
+use crate::config::{LyricLayer, ScreenEnum};
 use anyhow::{anyhow, Result};
+use ncm_api::{NormalizeMode, SongQuality};
 use ncm_play::PlayMode;
 
 #[derive(Clone)]
@@ -17,6 +18,7 @@ pub enum Command {
     PrevSong,
     SearchForward(Vec<String>),
     SearchBackward(Vec<String>),
+    SearchOnline(Vec<String>),
     //
     Down,
     Up,
@@ -27,6 +29,37 @@ pub enum Command {
     WhereIsThisSong,
     GoToTop,
     GoToBottom,
+    NextPage,
+    PrevPage,
+    AddToPlaylist,
+    OpenAlbum,
+    OpenArtist(Option<usize>),
+    /// Some(true): 喜欢，Some(false): 取消喜欢，None: 切换
+    Like(Option<bool>),
+    /// Some(true): 收藏，Some(false): 取消收藏，None: 切换
+    Collect(Option<bool>),
+    Enqueue,
+    EnqueueNext,
+    Delete,
+    MoveUp,
+    MoveDown,
+    ClearQueue,
+    DailyRecommend,
+    StartFm,
+    StartHeartbeat,
+    Trash,
+    SetQuality(SongQuality),
+    SetLyricLayer(LyricLayer),
+    /// 设置交叉淡入淡出时长（秒）
+    SetCrossfade(u64),
+    SetNormalize(NormalizeMode),
+    ReloadLyric,
+    /// 调整当前歌曲的歌词时间偏移（ms）
+    ShiftLyricOffset(i64),
+    /// 设置当前歌曲的歌词时间偏移（ms）
+    SetLyricOffset(i64),
+    CloudUpload(String),
+    OpenLocal(String),
     //
     Nop,
 }
@@ -39,7 +72,11 @@ impl Command {
             Some("q" | "quit" | "exit") => Ok(Self::Quit),
             Some("screen") => match tokens.next() {
                 Some("1" | "main") => Ok(Self::GotoScreen(ScreenEnum::Main)),
-                // Some("2" | "playlist" | "playlists") => Ok(Self::GotoScreen(ScreenEnum::Playlists)),
+                Some("2" | "playlist" | "playlists") => Ok(Self::GotoScreen(ScreenEnum::Playlists)),
+                Some("3" | "search") => Ok(Self::GotoScreen(ScreenEnum::Search)),
+                Some("4" | "queue") => Ok(Self::GotoScreen(ScreenEnum::Queue)),
+                Some("5" | "discover") => Ok(Self::GotoScreen(ScreenEnum::Discover)),
+                Some("6" | "cloud") => Ok(Self::GotoScreen(ScreenEnum::Cloud)),
                 Some("0" | "help") => Ok(Self::GotoScreen(ScreenEnum::Help)),
                 Some(other) => Err(anyhow!("screen: Invalid screen identifier: {}", other)),
                 None => Err(anyhow!("screen: Missing argument SCREEN_ID")),
@@ -63,6 +100,8 @@ impl Command {
                 Some("sr" | "single-repeat") => Ok(SwitchPlayMode(PlayMode::SingleRepeat)),
                 Some("lr" | "list-repeat") => Ok(SwitchPlayMode(PlayMode::ListRepeat)),
                 Some("s" | "shuf" | "shuffle") => Ok(SwitchPlayMode(PlayMode::Shuffle)),
+                Some("fm") => Ok(Self::StartFm),
+                Some("hb" | "heartbeat") => Ok(Self::StartHeartbeat),
                 Some(other) => Err(anyhow!("switch: Invalid play mode identifier: {}", other)),
                 None => Err(anyhow!("switch: Missing argument PLAY_MODE")),
             },
@@ -74,6 +113,93 @@ impl Command {
                 Some(other) => Err(anyhow!("where: Invalid argument '{}'", other)),
                 None => Err(anyhow!("where: Missing argument")),
             },
+            Some("album") => Ok(Self::OpenAlbum),
+            Some("artist" | "singer") => match tokens.next() {
+                Some(num) => match num.parse::<usize>() {
+                    Ok(index) if index > 0 => Ok(Self::OpenArtist(Some(index - 1))),
+                    _ => Err(anyhow!("artist: Invalid argument INDEX")),
+                },
+                None => Ok(Self::OpenArtist(None)),
+            },
+            Some("like") => Ok(Self::Like(Some(true))),
+            Some("unlike") => Ok(Self::Like(Some(false))),
+            Some("collect" | "subscribe") => Ok(Self::Collect(Some(true))),
+            Some("uncollect" | "unsubscribe") => Ok(Self::Collect(Some(false))),
+            Some("queue") => match tokens.next() {
+                Some("clear") => Ok(Self::ClearQueue),
+                Some("add" | "append") => Ok(Self::Enqueue),
+                Some("next") => Ok(Self::EnqueueNext),
+                Some(other) => Err(anyhow!("queue: Invalid argument '{}'", other)),
+                None => Ok(Self::GotoScreen(ScreenEnum::Queue)),
+            },
+            Some("discover" | "toplist") => Ok(Self::GotoScreen(ScreenEnum::Discover)),
+            Some("cloud") => Ok(Self::GotoScreen(ScreenEnum::Cloud)),
+            Some("upload") => {
+                // 文件路径可能包含空格
+                let file_path = tokens.collect::<Vec<_>>().join(" ");
+                if file_path.is_empty() {
+                    Err(anyhow!("upload: Missing argument FILE_PATH"))
+                } else {
+                    Ok(Self::CloudUpload(file_path))
+                }
+            }
+            Some("open" | "local") => {
+                // 路径可能包含空格
+                let path = tokens.collect::<Vec<_>>().join(" ");
+                if path.is_empty() {
+                    Err(anyhow!("open: Missing argument PATH"))
+                } else {
+                    Ok(Self::OpenLocal(path))
+                }
+            }
+            Some("quality") => match tokens.next() {
+                Some("standard") => Ok(Self::SetQuality(SongQuality::Standard)),
+                Some("higher") => Ok(Self::SetQuality(SongQuality::Higher)),
+                Some("exhigh") => Ok(Self::SetQuality(SongQuality::Exhigh)),
+                Some("lossless") => Ok(Self::SetQuality(SongQuality::Lossless)),
+                Some("hires") => Ok(Self::SetQuality(SongQuality::Hires)),
+                Some(other) => Err(anyhow!("quality: Invalid quality identifier: {}", other)),
+                None => Err(anyhow!("quality: Missing argument QUALITY")),
+            },
+            Some("lyric" | "lyrics") => match tokens.next() {
+                Some("trans" | "translation") => Ok(Self::SetLyricLayer(LyricLayer::Trans)),
+                Some("roma" | "romanization") => Ok(Self::SetLyricLayer(LyricLayer::Roma)),
+                Some("both") => Ok(Self::SetLyricLayer(LyricLayer::Both)),
+                Some("off" | "none") => Ok(Self::SetLyricLayer(LyricLayer::Off)),
+                Some("reload") => Ok(Self::ReloadLyric),
+                Some("offset") => match tokens.next() {
+                    // 带符号时为相对调整，如 +300 / -300，否则为直接设置，如 0
+                    Some(num) => match num.parse::<i64>() {
+                        Ok(offset) if num.starts_with(['+', '-']) => {
+                            Ok(Self::ShiftLyricOffset(offset))
+                        }
+                        Ok(offset) => Ok(Self::SetLyricOffset(offset)),
+                        Err(_) => Err(anyhow!("lyric offset: Invalid argument MILLISECONDS")),
+                    },
+                    None => Err(anyhow!("lyric offset: Missing argument MILLISECONDS")),
+                },
+                Some(other) => Err(anyhow!("lyric: Invalid argument '{}'", other)),
+                None => Err(anyhow!("lyric: Missing argument")),
+            },
+            Some("crossfade") => match tokens.next() {
+                Some("off") => Ok(Self::SetCrossfade(0)),
+                Some(num) => match num.parse::<u64>() {
+                    Ok(seconds) => Ok(Self::SetCrossfade(seconds)),
+                    Err(_) => Err(anyhow!("crossfade: Invalid argument SECONDS")),
+                },
+                None => Err(anyhow!("crossfade: Missing argument SECONDS")),
+            },
+            Some("normalize") => match tokens.next() {
+                Some("off") => Ok(Self::SetNormalize(NormalizeMode::Off)),
+                Some("track") => Ok(Self::SetNormalize(NormalizeMode::Track)),
+                Some("album") => Ok(Self::SetNormalize(NormalizeMode::Album)),
+                Some(other) => Err(anyhow!("normalize: Invalid mode identifier: {}", other)),
+                None => Err(anyhow!("normalize: Missing argument MODE")),
+            },
+            Some("daily" | "recommend") => Ok(Self::DailyRecommend),
+            Some("fm") => Ok(Self::StartFm),
+            Some("hb" | "heartbeat") => Ok(Self::StartHeartbeat),
+            Some("trash" | "dislike") => Ok(Self::Trash),
             Some("top") => Ok(Self::GoToTop),
             Some("bottom") => Ok(Self::GoToBottom),
             Some("/") => {
@@ -83,6 +209,17 @@ impl Command {
                 }
                 Ok(Self::SearchForward(keywords))
             }
+            Some("search") => {
+                let mut keywords = Vec::new();
+                while let Some(keyword) = tokens.next() {
+                    keywords.push(keyword.to_string());
+                }
+                if keywords.is_empty() {
+                    Err(anyhow!("search: Missing argument KEYWORDS"))
+                } else {
+                    Ok(Self::SearchOnline(keywords))
+                }
+            }
             Some("?") => {
                 let mut keywords = Vec::new();
                 while let Some(keyword) = tokens.next() {
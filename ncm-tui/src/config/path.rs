@@ -12,10 +12,14 @@ pub struct Path {
     // 二级目录
     pub login_cookie: PathBuf,
     pub lyrics: PathBuf,
+    pub users: PathBuf,
+    pub settings: PathBuf,
+    pub log: PathBuf,
 }
 
 impl Path {
-    pub fn new() -> Self {
+    /// settings: 命令行指定的配置文件路径，为空时使用配置目录下的 config.yml
+    pub fn new(settings: Option<PathBuf>) -> Self {
         // 获取并验证各个目录路径
         let data = dirs_next::data_dir().unwrap_or_else(|| {
             panic!("Could not retrieve the data directory.");
@@ -53,12 +57,31 @@ impl Path {
             });
         }
 
+        let users = data.clone().join("users");
+        if !users.exists() {
+            fs::create_dir(&users).unwrap_or_else(|e| {
+                panic!("Couldn't create users dir at {:?}: {}", users, e);
+            });
+        }
+
+        let settings = settings.unwrap_or_else(|| config.clone().join("config.yml"));
+
+        let log = data.clone().join("ncm-tui.log");
+
         Self {
             data,
             config,
             cache,
             login_cookie,
             lyrics,
+            users,
+            settings,
+            log,
         }
     }
+
+    /// 按用户保存的设置文件
+    pub fn user_settings(&self, uid: u64) -> PathBuf {
+        self.users.join(format!("{uid}.yml"))
+    }
 }
@@ -0,0 +1,151 @@
+use crate::config::CliArgs;
+use anyhow::{anyhow, Result};
+use ncm_api::{NormalizeMode, SongQuality};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+
+/// 配置文件（yaml 格式）
+/// 所有字段均可省略，命令行参数优先于配置文件
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct Settings {
+    /// 启动时加载的歌单在用户歌单列表中的位置（0 为“我喜欢的音乐”）
+    pub startup_playlist: u16,
+    /// 日志等级，为空时不记录日志
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_level: Option<String>,
+    /// 代理地址
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+    /// 音质: standard / higher / exhigh / lossless / hires
+    pub quality: SongQuality,
+    /// 单次网络请求超时（秒）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_timeout: Option<u64>,
+    /// 网络请求失败后的最大重试次数
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_retries: Option<u32>,
+    /// 接口地址列表，请求失败时依次切换，为空时使用默认地址
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub api_hosts: Vec<String>,
+    /// 歌词显示: trans / roma / both / off，登录后优先使用用户的设置
+    pub lyric_layer: LyricLayer,
+    /// 切歌时交叉淡入淡出的时长（秒），0 表示不启用
+    pub crossfade: u64,
+    /// 响度均衡: off / track / album
+    pub normalize: NormalizeMode,
+}
+
+/// 按用户保存的设置（yaml 格式），位于数据目录下的 users/<uid>.yml
+/// 未设置的字段使用配置文件中的值
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct UserSettings {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lyric_layer: Option<LyricLayer>,
+}
+
+/// 主界面歌词下方显示的内容
+#[derive(Serialize, Deserialize, Default, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum LyricLayer {
+    /// 翻译
+    #[default]
+    Trans,
+    /// 音译（罗马音）
+    Roma,
+    /// 翻译和音译
+    Both,
+    /// 仅显示原文
+    Off,
+}
+
+impl LyricLayer {
+    pub fn show_translation(&self) -> bool {
+        matches!(self, LyricLayer::Trans | LyricLayer::Both)
+    }
+
+    pub fn show_romanization(&self) -> bool {
+        matches!(self, LyricLayer::Roma | LyricLayer::Both)
+    }
+}
+
+impl fmt::Display for LyricLayer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LyricLayer::Trans => write!(f, "翻译"),
+            LyricLayer::Roma => write!(f, "音译"),
+            LyricLayer::Both => write!(f, "翻译和音译"),
+            LyricLayer::Off => write!(f, "仅原文"),
+        }
+    }
+}
+
+impl Settings {
+    /// 读取配置文件，文件不存在时使用默认配置，格式错误时返回错误
+    pub fn load(path: &PathBuf) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(content) => serde_yml::from_str(&content)
+                .map_err(|e| anyhow!("Couldn't parse config file at {:?}: {}", path, e)),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    /// 写入配置文件
+    pub fn save(&self, path: &PathBuf) -> Result<()> {
+        fs::write(path, serde_yml::to_string(self)?)?;
+
+        Ok(())
+    }
+
+    /// 修改配置文件中的值并保存
+    /// 重新读取配置文件后修改，不会把命令行参数写入配置文件
+    pub fn update_file<F>(path: &PathBuf, update: F) -> Result<()>
+    where
+        F: FnOnce(&mut Self),
+    {
+        let mut settings = Self::load(path)?;
+        update(&mut settings);
+        settings.save(path)
+    }
+
+    /// 使用命令行参数覆盖配置文件中的值
+    pub fn merge_cli_args(mut self, cli_args: &CliArgs) -> Self {
+        if let Some(playlist) = cli_args.playlist {
+            self.startup_playlist = playlist;
+        }
+        if let Some(log_level) = &cli_args.log_level {
+            self.log_level = Some(log_level.clone());
+        }
+        if let Some(proxy) = &cli_args.proxy {
+            self.proxy = Some(proxy.clone());
+        }
+
+        self
+    }
+}
+
+impl UserSettings {
+    /// 读取用户设置，文件不存在时使用默认值，格式错误时返回错误
+    pub fn load(path: &PathBuf) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(content) => serde_yml::from_str(&content)
+                .map_err(|e| anyhow!("Couldn't parse user settings at {:?}: {}", path, e)),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    /// 修改用户设置并保存
+    pub fn update_file<F>(path: &PathBuf, update: F) -> Result<()>
+    where
+        F: FnOnce(&mut Self),
+    {
+        let mut user_settings = Self::load(path)?;
+        update(&mut user_settings);
+        fs::write(path, serde_yml::to_string(&user_settings)?)?;
+
+        Ok(())
+    }
+}
@@ -9,7 +9,14 @@ pub enum AppMode {
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone, Copy)]
 pub enum ScreenEnum {
     Main,
+    Playlists,
     Login,
     Help,
+    Search,
+    Queue,
+    Discover,
+    Cloud,
+    Album,
+    Artist,
     Launch,
 }
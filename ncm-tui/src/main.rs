@@ -1,18 +1,19 @@
 mod config;
 mod ui;
 
-use crate::config::Path;
+use crate::config::{CliArgs, Path, Settings};
 use crate::ui::App;
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use crossterm::terminal::{enable_raw_mode, EnterAlternateScreen};
 use crossterm::{event, execute};
 use lazy_static::lazy_static;
-use ncm_api::NcmApi;
+use ncm_api::{NcmApi, RetryPolicy};
 use ncm_play::Player;
 use ratatui::backend::CrosstermBackend;
 use ratatui::Terminal;
-use std::io;
-use std::sync::Arc;
+use std::ops::Deref;
+use std::{fs, io};
+use std::sync::{Arc, OnceLock};
 use std::time::Duration;
 use tokio::sync::Mutex;
 use tokio::task;
@@ -21,20 +22,67 @@ use tokio::time::sleep;
 const POLL_DURATION: Duration = Duration::from_millis(100);
 
 lazy_static! {
-    static ref PATH_CONFIG: Path = Path::new();
+    static ref CLI_ARGS: CliArgs = CliArgs::parse();
+    static ref PATH_CONFIG: Path = Path::new(CLI_ARGS.config.clone());
     static ref NCM_API: Arc<Mutex<NcmApi>> = Arc::new(Mutex::new(NcmApi::from_cookie_jar(
         PATH_CONFIG.login_cookie.clone(),
         PATH_CONFIG.lyrics.clone(),
         PATH_CONFIG.cache.clone(),
     )));
-    static ref PLAYER: Arc<Mutex<Player>> = Arc::new(Mutex::new(Player::new()));
+}
+
+// 创建时可能出错，在 main() 中初始化，出错时返回错误
+static SETTINGS: Global<Settings> = Global::new();
+static PLAYER: Global<Arc<Mutex<Player>>> = Global::new();
+
+/// 在 main() 中初始化一次的全局变量，初始化前使用属于程序错误
+struct Global<T>(OnceLock<T>);
+
+impl<T> Global<T> {
+    const fn new() -> Self {
+        Self(OnceLock::new())
+    }
+
+    fn init(&self, value: T) {
+        if self.0.set(value).is_err() {
+            panic!("global initialized twice");
+        }
+    }
+}
+
+impl<T> Deref for Global<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.0
+            .get()
+            .expect("global used before initialized in main()")
+    }
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // env_logger::init();
+    // 在进入 raw mode 之前加载配置和初始化 GStreamer，出错时直接返回错误
+    SETTINGS.init(Settings::load(&PATH_CONFIG.settings)?.merge_cli_args(&CLI_ARGS));
+    PLAYER.init(Arc::new(Mutex::new(Player::new()?)));
+
+    // 日志写入文件，避免干扰 tui 界面
+    init_logger()?;
 
-    let app = Arc::new(Mutex::new(App::new(create_terminal()?)));
+    if let Some(proxy) = &SETTINGS.proxy {
+        NCM_API.lock().await.set_proxy(proxy)?;
+    }
+    NCM_API.lock().await.set_song_quality(SETTINGS.quality);
+    NCM_API
+        .lock()
+        .await
+        .set_api_hosts(SETTINGS.api_hosts.clone());
+    NCM_API.lock().await.set_retry_policy(retry_policy());
+    PLAYER.lock().await.set_crossfade(SETTINGS.crossfade);
+    PLAYER.lock().await.set_normalize_mode(SETTINGS.normalize);
+
+    let player_events = PLAYER.lock().await.subscribe();
+    let app = Arc::new(Mutex::new(App::new(create_terminal()?, player_events)));
 
     // 绘制第一帧（launch screen）
     app.lock().await.draw_launch_screen()?;
@@ -48,7 +96,7 @@ async fn main() -> Result<()> {
         if ncm_api_2
             .lock()
             .await
-            .check_cookie_login()
+            .check_cookie_login(SETTINGS.startup_playlist)
             .await
             .unwrap_or(false)
         {
@@ -60,11 +108,19 @@ async fn main() -> Result<()> {
         } else {
             app2.lock().await.init_after_no_login().await;
         }
+
+        // 打开命令行指定的本地音频目录，不需要登录
+        if let Some(path) = &CLI_ARGS.open {
+            app2.lock().await.open_local(path.clone());
+        }
     });
 
     loop {
-        // 检查播放情况
-        PLAYER.lock().await.auto_play(NCM_API.lock().await).await?;
+        // 检查播放情况，播放失败时在命令行提示
+        let result = PLAYER.lock().await.auto_play(NCM_API.lock().await).await;
+        if let Err(e) = result {
+            app.lock().await.show_error(&e);
+        }
 
         // 根据 Controller 流程，先执行 update_model()，再执行 handle_event()
         app.lock().await.update_model().await?;
@@ -80,6 +136,22 @@ async fn main() -> Result<()> {
     }
 }
 
+fn init_logger() -> Result<()> {
+    if let Some(log_level) = &SETTINGS.log_level {
+        let log_file = fs::File::create(&PATH_CONFIG.log)?;
+        env_logger::Builder::new()
+            .filter_level(
+                log_level
+                    .parse()
+                    .map_err(|_| anyhow!("Invalid log level: {}", log_level))?,
+            )
+            .target(env_logger::Target::Pipe(Box::new(log_file)))
+            .init();
+    }
+
+    Ok(())
+}
+
 fn create_terminal() -> Result<Terminal<CrosstermBackend<io::Stdout>>> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -89,3 +161,16 @@ fn create_terminal() -> Result<Terminal<CrosstermBackend<io::Stdout>>> {
 
     Ok(terminal)
 }
+
+/// 根据配置文件生成网络请求的超时与重试策略，未设置的字段使用默认值
+fn retry_policy() -> RetryPolicy {
+    let mut retry_policy = RetryPolicy::default();
+    if let Some(request_timeout) = SETTINGS.request_timeout {
+        retry_policy.timeout = Duration::from_secs(request_timeout);
+    }
+    if let Some(request_retries) = SETTINGS.request_retries {
+        retry_policy.max_retries = request_retries;
+    }
+
+    retry_policy
+}
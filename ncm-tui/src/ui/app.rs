@@ -1,9 +1,9 @@
 use crate::config::LOGO_LINES;
 use crate::ui::widget::{BottomBar, CommandLine};
 use crate::{
-    config::{AppMode, Command, ScreenEnum},
+    config::{AppMode, Command, ScreenEnum, Settings, UserSettings},
     ui::{screen::*, Controller},
-    NCM_API, PLAYER,
+    NCM_API, PATH_CONFIG, PLAYER, SETTINGS,
 };
 use anyhow::Result;
 use crossterm::{
@@ -12,23 +12,46 @@ use crossterm::{
     execute,
     terminal::{disable_raw_mode, LeaveAlternateScreen},
 };
+use ncm_api::{NcmError, SongInfo};
+use ncm_play::{load_local, PlayerEvent, PlayerEventReceiver, RadioSource};
 use ratatui::prelude::*;
 use ratatui::style::palette::tailwind;
 use ratatui::widgets::Paragraph;
 use std::collections::VecDeque;
 use std::io::Stdout;
+use std::path::PathBuf;
+use tokio::sync::mpsc;
+use tokio::task;
+
+/// 后台读取本地音频的结果: (路径, 歌曲列表)
+type LocalSongsResult = (PathBuf, Result<Vec<SongInfo>>);
+
+/// 页面历史最大记录数
+const MAX_SCREEN_HISTORY: usize = 16;
 
 pub struct App<'a> {
     // model
     current_screen: ScreenEnum,
+    screen_history: Vec<ScreenEnum>, // 详情页按 Esc 依次返回
     current_mode: AppMode,
     need_re_update_view: bool,
     command_queue: VecDeque<Command>,
+    pending_trash: bool, // 等待再次按 x 确认不喜欢当前歌曲
+    player_events: PlayerEventReceiver,
+    local_songs_sender: mpsc::UnboundedSender<LocalSongsResult>,
+    local_songs_receiver: mpsc::UnboundedReceiver<LocalSongsResult>,
 
     // view
     main_screen: MainScreen<'a>,
+    playlists_screen: PlaylistsScreen<'a>,
     login_screen: LoginScreen<'a>,
     help_screen: HelpScreen<'a>,
+    album_screen: AlbumScreen<'a>,
+    artist_screen: ArtistScreen<'a>,
+    search_screen: SearchScreen<'a>,
+    queue_screen: QueueScreen<'a>,
+    discover_screen: DiscoverScreen<'a>,
+    cloud_screen: CloudScreen<'a>,
     command_line: CommandLine<'a>,
     bottom_bar: BottomBar<'a>,
 
@@ -39,19 +62,35 @@ pub struct App<'a> {
 
 /// public
 impl<'a> App<'a> {
-    pub fn new(terminal: Terminal<CrosstermBackend<Stdout>>) -> Self {
+    pub fn new(
+        terminal: Terminal<CrosstermBackend<Stdout>>,
+        player_events: PlayerEventReceiver,
+    ) -> Self {
         let normal_style = Style::default();
+        let (local_songs_sender, local_songs_receiver) = mpsc::unbounded_channel();
 
         Self {
             current_screen: ScreenEnum::Launch,
+            screen_history: Vec::new(),
             current_mode: AppMode::Normal,
             need_re_update_view: true,
             command_queue: VecDeque::new(),
+            pending_trash: false,
+            bottom_bar: BottomBar::new(&normal_style, player_events.resubscribe()),
+            player_events,
+            local_songs_sender,
+            local_songs_receiver,
             main_screen: MainScreen::new(&normal_style),
+            playlists_screen: PlaylistsScreen::new(&normal_style),
             login_screen: LoginScreen::new(&normal_style),
             help_screen: HelpScreen::new(&normal_style),
+            album_screen: AlbumScreen::new(&normal_style),
+            artist_screen: ArtistScreen::new(&normal_style),
+            search_screen: SearchScreen::new(&normal_style),
+            queue_screen: QueueScreen::new(&normal_style),
+            discover_screen: DiscoverScreen::new(&normal_style),
+            cloud_screen: CloudScreen::new(&normal_style),
             command_line: CommandLine::new(),
-            bottom_bar: BottomBar::new(&normal_style),
             terminal,
             normal_style,
         }
@@ -97,7 +136,11 @@ impl<'a> App<'a> {
                 .switch_playlist(playlist_name.clone(), playlist.clone());
         }
 
+        self.playlists_screen.reload();
+        self.cloud_screen.reload();
+
         self.switch_screen(ScreenEnum::Main).await;
+        self.load_lyric_layer().await;
 
         Ok(())
     }
@@ -107,6 +150,19 @@ impl<'a> App<'a> {
         self.switch_screen(ScreenEnum::Main).await;
     }
 
+    /// 在命令行显示错误信息，ncm-api 的错误附带处理提示
+    pub fn show_error(&mut self, e: &anyhow::Error) {
+        let message = match e.downcast_ref::<NcmError>() {
+            Some(NcmError::AuthRequired) => format!("{e}，使用`login`命令登录"),
+            Some(NcmError::Network(_) | NcmError::Http(_)) => {
+                format!("{e}，请检查网络或代理设置")
+            }
+            Some(NcmError::Blocked(_)) => format!("{e}，请尝试其他歌曲或降低音质"),
+            _ => e.to_string(),
+        };
+        self.command_line.set_content(message.as_str());
+    }
+
     pub fn restore_terminal(&mut self) -> Result<()> {
         disable_raw_mode()?;
         execute!(self.terminal.backend_mut(), LeaveAlternateScreen)?;
@@ -119,14 +175,42 @@ impl<'a> App<'a> {
 /// Controller
 impl<'a> App<'a> {
     pub async fn update_model(&mut self) -> Result<()> {
+        // 播放出错时在命令行提示
+        while let Ok(event) = self.player_events.try_recv() {
+            if let PlayerEvent::Error(message) = event {
+                self.command_line
+                    .set_content(format!("播放出错: {message}").as_str());
+            }
+        }
+
         // screen
-        self.need_re_update_view = match self.current_screen {
-            ScreenEnum::Help => false,
-            ScreenEnum::Login => self.update_login_model().await?,
-            ScreenEnum::Main => self.main_screen.update_model().await?,
-            _ => false,
+        let result = match self.current_screen {
+            ScreenEnum::Help => Ok(false),
+            ScreenEnum::Login => self.update_login_model().await,
+            ScreenEnum::Main => self.main_screen.update_model().await,
+            ScreenEnum::Playlists => self.playlists_screen.update_model().await,
+            ScreenEnum::Search => self.search_screen.update_model().await,
+            ScreenEnum::Queue => self.queue_screen.update_model().await,
+            ScreenEnum::Discover => self.discover_screen.update_model().await,
+            ScreenEnum::Cloud => self.cloud_screen.update_model().await,
+            ScreenEnum::Album => self.album_screen.update_model().await,
+            ScreenEnum::Artist => self.artist_screen.update_model().await,
+            _ => Ok(false),
+        };
+        // 网络请求等失败时在命令行提示，不退出程序
+        self.need_re_update_view = match result {
+            Ok(need_re_update_view) => need_re_update_view,
+            Err(e) => {
+                self.show_error(&e);
+                true
+            }
         };
 
+        // 后台读取完成的本地音频
+        while let Ok((path, result)) = self.local_songs_receiver.try_recv() {
+            self.switch_to_local(path, result).await;
+        }
+
         // bottom_bar
         self.bottom_bar.update_model().await?;
 
@@ -191,6 +275,14 @@ impl<'a> App<'a> {
 
         // 执行命令
         if let Some(cmd) = self.command_queue.pop_front() {
+            // 详情页按 Esc 返回上一个页面，不再向下传递
+            if let Command::Esc = cmd {
+                if let ScreenEnum::Album | ScreenEnum::Artist = self.current_screen {
+                    self.back_to_prev_screen();
+                    return Ok(true);
+                }
+            }
+
             // app响应的事件
             match cmd.clone() {
                 Command::Quit => {
@@ -206,6 +298,9 @@ impl<'a> App<'a> {
                     self.login_screen = LoginScreen::new(&self.normal_style);
                     // TODO: 清除 cache
                     NCM_API.lock().await.logout().await;
+                    self.playlists_screen.reload();
+                    self.cloud_screen.reload();
+                    self.load_lyric_layer().await;
                 }
                 Command::PlayOrPause => {
                     PLAYER.lock().await.play_or_pause();
@@ -218,23 +313,28 @@ impl<'a> App<'a> {
                 }
                 Command::StartPlay => {
                     if let Err(e) = PLAYER.lock().await.start_play(NCM_API.lock().await).await {
-                        // self.show_prompt(e.to_string().as_str());
-                        self.command_line.set_content(e.to_string().as_str());
+                        self.show_error(&e);
                     }
                 }
                 Command::NextSong => {
-                    PLAYER
+                    let result = PLAYER
                         .lock()
                         .await
                         .play_next_song_now(NCM_API.lock().await)
-                        .await?;
+                        .await;
+                    if let Err(e) = result {
+                        self.show_error(&e);
+                    }
                 }
                 Command::PrevSong => {
-                    PLAYER
+                    let result = PLAYER
                         .lock()
                         .await
                         .play_prev_song_now(NCM_API.lock().await)
-                        .await?;
+                        .await;
+                    if let Err(e) = result {
+                        self.show_error(&e);
+                    }
                 }
                 Command::SearchForward(search_keywords) => {
                     self.switch_to_search_mode(search_keywords);
@@ -242,6 +342,175 @@ impl<'a> App<'a> {
                 Command::SearchBackward(search_keywords) => {
                     self.switch_to_search_mode(search_keywords);
                 }
+                Command::SearchOnline(_) => {
+                    self.switch_screen(ScreenEnum::Search).await;
+                }
+                Command::OpenAlbum => {
+                    let album_id = match self.current_screen {
+                        ScreenEnum::Main => self
+                            .main_screen
+                            .selected_song_info()
+                            .await
+                            .map(|song_info| song_info.album_id),
+                        ScreenEnum::Search => self.search_screen.selected_album_id(),
+                        ScreenEnum::Artist => self.artist_screen.selected_album_id(),
+                        ScreenEnum::Discover => self.discover_screen.selected_album_id(),
+                        _ => None,
+                    };
+                    if let Some(album_id) = album_id {
+                        match self.album_screen.load(album_id).await {
+                            Ok(_) => self.switch_screen(ScreenEnum::Album).await,
+                            Err(e) => self
+                                .command_line
+                                .set_content(format!("专辑获取失败: {e}").as_str()),
+                        }
+                    }
+                }
+                Command::OpenArtist(index) => {
+                    self.open_artist(index).await;
+                }
+                Command::Like(like) => {
+                    self.like_song(like).await;
+                }
+                Command::Enqueue | Command::EnqueueNext => {
+                    if let Some(song_info) = self.selected_song_info().await {
+                        let mut player_guard = PLAYER.lock().await;
+                        let message = if let Command::EnqueueNext = cmd {
+                            player_guard.play_queue_mut().play_next(song_info.clone());
+                            format!("下一首播放: {}", song_info.name)
+                        } else if player_guard.play_queue_mut().append(song_info.clone()) {
+                            format!("已加入播放队列: {}", song_info.name)
+                        } else {
+                            format!("已在播放队列中: {}", song_info.name)
+                        };
+                        drop(player_guard);
+                        self.command_line.set_content(message.as_str());
+                    }
+                }
+                Command::ClearQueue => {
+                    PLAYER.lock().await.play_queue_mut().clear();
+                    self.command_line.set_content("已清空播放队列");
+                }
+                Command::DailyRecommend => {
+                    self.play_daily_recommend().await;
+                }
+                Command::StartFm | Command::StartHeartbeat => {
+                    self.start_radio(matches!(cmd, Command::StartHeartbeat))
+                        .await;
+                }
+                Command::SetQuality(quality) => {
+                    NCM_API.lock().await.set_song_quality(quality);
+                    // 保存到配置文件，下次启动时使用
+                    match Settings::update_file(&PATH_CONFIG.settings, |settings| {
+                        settings.quality = quality
+                    }) {
+                        Ok(_) => self
+                            .command_line
+                            .set_content(format!("音质已设置为{quality}，下一首歌曲生效").as_str()),
+                        Err(e) => self.command_line.set_content(
+                            format!("音质已设置为{quality}，保存配置失败: {e}").as_str(),
+                        ),
+                    }
+                }
+                Command::SetLyricLayer(layer) => {
+                    self.main_screen.set_lyric_layer(layer);
+                    self.need_re_update_view = true;
+                    // 登录时按用户保存，未登录时保存到配置文件，下次启动时使用
+                    let uid = NCM_API.lock().await.login_info().map(|info| info.uid);
+                    let result = match uid {
+                        Some(uid) => {
+                            UserSettings::update_file(&PATH_CONFIG.user_settings(uid), |user| {
+                                user.lyric_layer = Some(layer)
+                            })
+                        }
+                        None => Settings::update_file(&PATH_CONFIG.settings, |settings| {
+                            settings.lyric_layer = layer
+                        }),
+                    };
+                    match result {
+                        Ok(_) => self
+                            .command_line
+                            .set_content(format!("歌词显示已设置为{layer}").as_str()),
+                        Err(e) => self.command_line.set_content(
+                            format!("歌词显示已设置为{layer}，保存配置失败: {e}").as_str(),
+                        ),
+                    }
+                }
+                Command::SetCrossfade(seconds) => {
+                    let mut player_guard = PLAYER.lock().await;
+                    player_guard.set_crossfade(seconds);
+                    let crossfade = player_guard.crossfade();
+                    let message = if crossfade == 0 {
+                        String::from("已关闭交叉淡入淡出")
+                    } else {
+                        format!("交叉淡入淡出已设置为{crossfade}秒")
+                    };
+                    // 保存到配置文件，下次启动时使用
+                    match Settings::update_file(&PATH_CONFIG.settings, |settings| {
+                        settings.crossfade = crossfade
+                    }) {
+                        Ok(_) => self.command_line.set_content(message.as_str()),
+                        Err(e) => self
+                            .command_line
+                            .set_content(format!("{message}，保存配置失败: {e}").as_str()),
+                    }
+                }
+                Command::SetNormalize(mode) => {
+                    PLAYER.lock().await.set_normalize_mode(mode);
+                    // 保存到配置文件，下次启动时使用
+                    match Settings::update_file(&PATH_CONFIG.settings, |settings| {
+                        settings.normalize = mode
+                    }) {
+                        Ok(_) => self
+                            .command_line
+                            .set_content(format!("响度均衡已设置为{mode}").as_str()),
+                        Err(e) => self.command_line.set_content(
+                            format!("响度均衡已设置为{mode}，保存配置失败: {e}").as_str(),
+                        ),
+                    }
+                }
+                Command::ReloadLyric => {
+                    let mut player_guard = PLAYER.lock().await;
+                    match player_guard
+                        .reload_current_song_lyrics(NCM_API.lock().await)
+                        .await
+                    {
+                        Ok(_) => {
+                            self.main_screen.reload_song_lyrics();
+                            if player_guard.current_song_info_ref().is_none() {
+                                self.command_line.set_content("当前没有播放的歌曲");
+                            } else if player_guard.current_song_lyrics().is_none() {
+                                self.command_line.set_content("未找到歌词");
+                            } else {
+                                self.command_line.set_content("已重新获取歌词");
+                            }
+                        }
+                        Err(e) => self.show_error(&e),
+                    }
+                }
+                Command::ShiftLyricOffset(delta) => {
+                    let offset = PLAYER.lock().await.current_song_lyric_offset() + delta;
+                    self.set_lyric_offset(offset).await;
+                }
+                Command::SetLyricOffset(offset) => {
+                    self.set_lyric_offset(offset).await;
+                }
+                Command::CloudUpload(file_path) => {
+                    self.cloud_upload(&file_path).await;
+                }
+                Command::OpenLocal(path) => {
+                    self.open_local(expand_home(&path));
+                }
+                Command::Trash => {
+                    if let Err(e) = PLAYER
+                        .lock()
+                        .await
+                        .trash_current_song(NCM_API.lock().await)
+                        .await
+                    {
+                        self.show_error(&e);
+                    }
+                }
                 _ => {}
             }
 
@@ -257,15 +526,38 @@ impl<'a> App<'a> {
                 | Command::GoToTop
                 | Command::GoToBottom
                 | Command::SearchForward(_)
-                | Command::SearchBackward(_) => {
+                | Command::SearchBackward(_)
+                | Command::SearchOnline(_)
+                | Command::NextPage
+                | Command::PrevPage
+                | Command::AddToPlaylist
+                | Command::Collect(_)
+                | Command::Delete
+                | Command::MoveUp
+                | Command::MoveDown => {
                     // 先 update_model(), 再 handle_event()
                     // 取或值
                     // 若写成 self.need_re_update_view = self.need_re_update_view || match ... {} ，match块内的方法可能不被执行
-                    self.need_re_update_view = match self.current_screen {
-                        ScreenEnum::Main => self.main_screen.handle_event(cmd).await?,
-                        ScreenEnum::Login => self.login_screen.handle_event(cmd).await?,
-                        ScreenEnum::Help => self.help_screen.handle_event(cmd).await?,
-                        _ => false,
+                    let result = match self.current_screen {
+                        ScreenEnum::Main => self.main_screen.handle_event(cmd).await,
+                        ScreenEnum::Playlists => self.playlists_screen.handle_event(cmd).await,
+                        ScreenEnum::Login => self.login_screen.handle_event(cmd).await,
+                        ScreenEnum::Help => self.help_screen.handle_event(cmd).await,
+                        ScreenEnum::Search => self.search_screen.handle_event(cmd).await,
+                        ScreenEnum::Queue => self.queue_screen.handle_event(cmd).await,
+                        ScreenEnum::Discover => self.discover_screen.handle_event(cmd).await,
+                        ScreenEnum::Cloud => self.cloud_screen.handle_event(cmd).await,
+                        ScreenEnum::Album => self.album_screen.handle_event(cmd).await,
+                        ScreenEnum::Artist => self.artist_screen.handle_event(cmd).await,
+                        _ => Ok(false),
+                    };
+                    // 网络请求等失败时在命令行提示，不退出程序
+                    self.need_re_update_view = match result {
+                        Ok(need_re_update_view) => need_re_update_view,
+                        Err(e) => {
+                            self.show_error(&e);
+                            true
+                        }
                     } || self.need_re_update_view;
                 }
                 _ => {}
@@ -282,6 +574,13 @@ impl<'a> App<'a> {
                 ScreenEnum::Help => {}
                 ScreenEnum::Login => self.login_screen.update_view(&self.normal_style),
                 ScreenEnum::Main => self.main_screen.update_view(&self.normal_style),
+                ScreenEnum::Playlists => self.playlists_screen.update_view(&self.normal_style),
+                ScreenEnum::Search => self.search_screen.update_view(&self.normal_style),
+                ScreenEnum::Queue => self.queue_screen.update_view(&self.normal_style),
+                ScreenEnum::Discover => self.discover_screen.update_view(&self.normal_style),
+                ScreenEnum::Cloud => self.cloud_screen.update_view(&self.normal_style),
+                ScreenEnum::Album => self.album_screen.update_view(&self.normal_style),
+                ScreenEnum::Artist => self.artist_screen.update_view(&self.normal_style),
                 _ => {}
             }
         }
@@ -323,6 +622,13 @@ impl<'a> App<'a> {
                 ScreenEnum::Help => self.help_screen.draw(frame, chunks[0]),
                 ScreenEnum::Login => self.login_screen.draw(frame, chunks[0]),
                 ScreenEnum::Main => self.main_screen.draw(frame, chunks[0]),
+                ScreenEnum::Playlists => self.playlists_screen.draw(frame, chunks[0]),
+                ScreenEnum::Search => self.search_screen.draw(frame, chunks[0]),
+                ScreenEnum::Queue => self.queue_screen.draw(frame, chunks[0]),
+                ScreenEnum::Discover => self.discover_screen.draw(frame, chunks[0]),
+                ScreenEnum::Cloud => self.cloud_screen.draw(frame, chunks[0]),
+                ScreenEnum::Album => self.album_screen.draw(frame, chunks[0]),
+                ScreenEnum::Artist => self.artist_screen.draw(frame, chunks[0]),
                 _ => {}
             }
 
@@ -340,6 +646,13 @@ impl<'a> App<'a> {
 /// private
 impl<'a> App<'a> {
     fn get_command_from_key(&mut self, key_code: KeyCode) {
+        // 不喜欢当前歌曲需要连续按两次 x 确认，其他按键取消
+        let pending_trash = self.pending_trash;
+        if pending_trash {
+            self.pending_trash = false;
+            self.command_line.clear_content();
+        }
+
         let cmd = match key_code {
             KeyCode::Down => Command::Down,
             KeyCode::Char('j') => Command::Down,
@@ -353,11 +666,36 @@ impl<'a> App<'a> {
             KeyCode::Left => Command::PrevPanel,
             KeyCode::Char('h') => Command::PrevPanel,
             KeyCode::Char('1') => Command::GotoScreen(ScreenEnum::Main),
+            KeyCode::Char('2') => Command::GotoScreen(ScreenEnum::Playlists),
+            KeyCode::Char('3') => Command::GotoScreen(ScreenEnum::Search),
+            KeyCode::Char('4') => Command::GotoScreen(ScreenEnum::Queue),
+            KeyCode::Char('5') => Command::GotoScreen(ScreenEnum::Discover),
+            KeyCode::Char('6') => Command::GotoScreen(ScreenEnum::Cloud),
             KeyCode::Char('0') => Command::GotoScreen(ScreenEnum::Help),
             KeyCode::F(1) => Command::GotoScreen(ScreenEnum::Help),
             KeyCode::Char('.') | KeyCode::Char('。') => Command::NextSong,
             KeyCode::Char(',') | KeyCode::Char('，') => Command::PrevSong,
             KeyCode::Char(':') | KeyCode::Char('：') => Command::EnterCommand,
+            KeyCode::Char(']') | KeyCode::Char('】') => Command::NextPage,
+            KeyCode::Char('[') | KeyCode::Char('【') => Command::PrevPage,
+            KeyCode::Char('a') => Command::AddToPlaylist,
+            KeyCode::Char('b') => Command::OpenAlbum,
+            KeyCode::Char('r') => Command::OpenArtist(None),
+            KeyCode::Char('f') => Command::Like(None),
+            KeyCode::Char('c') => Command::Collect(None),
+            KeyCode::Char('e') => Command::Enqueue,
+            KeyCode::Char('p') => Command::EnqueueNext,
+            KeyCode::Char('d') => Command::Delete,
+            KeyCode::Char('K') => Command::MoveUp,
+            KeyCode::Char('J') => Command::MoveDown,
+            KeyCode::Char('x') if pending_trash => Command::Trash,
+            KeyCode::Char('x') => {
+                self.pending_trash = true;
+                self.command_line.set_content("再按 x 确认不喜欢当前歌曲");
+                Command::Nop
+            }
+            KeyCode::Char('+') | KeyCode::Char('=') => Command::ShiftLyricOffset(100),
+            KeyCode::Char('-') => Command::ShiftLyricOffset(-100),
             KeyCode::Char('/') => {
                 self.switch_to_search_input_mode();
                 self.command_line.set_content("/ ");
@@ -452,6 +790,302 @@ impl<'a> App<'a> {
         }
 
         self.need_re_update_view = true;
+        if to_screen != self.current_screen {
+            self.screen_history.push(self.current_screen);
+            if self.screen_history.len() > MAX_SCREEN_HISTORY {
+                self.screen_history.remove(0);
+            }
+        }
         self.current_screen = to_screen;
     }
+
+    /// 返回上一个页面，不记录历史
+    fn back_to_prev_screen(&mut self) {
+        self.need_re_update_view = true;
+        self.current_screen = match self.screen_history.pop() {
+            Some(ScreenEnum::Launch) | None => ScreenEnum::Main,
+            Some(prev_screen) => prev_screen,
+        };
+    }
+
+    /// 当前页面选中的歌曲
+    async fn selected_song_info(&self) -> Option<SongInfo> {
+        match self.current_screen {
+            ScreenEnum::Main => self.main_screen.selected_song_info().await,
+            ScreenEnum::Search => self.search_screen.selected_song_info(),
+            ScreenEnum::Album => self.album_screen.selected_song_info(),
+            ScreenEnum::Artist => self.artist_screen.selected_song_info(),
+            ScreenEnum::Cloud => self.cloud_screen.selected_song_info(),
+            _ => None,
+        }
+    }
+
+    /// 当前页面选中的歌曲，没有选中时为当前播放的歌曲
+    async fn selected_or_current_song_info(&self) -> Option<SongInfo> {
+        match self.selected_song_info().await {
+            Some(song_info) => Some(song_info),
+            None => PLAYER.lock().await.current_song_info_ref().clone(),
+        }
+    }
+
+    /// 喜欢/取消喜欢选中的歌曲
+    /// like: 为空时根据当前状态切换
+    async fn like_song(&mut self, like: Option<bool>) {
+        let song_info = match self.selected_or_current_song_info().await {
+            Some(song_info) => song_info,
+            None => {
+                self.command_line.set_content("未选中歌曲");
+                return;
+            }
+        };
+
+        let mut ncm_api_guard = NCM_API.lock().await;
+        let like = like.unwrap_or(!ncm_api_guard.is_liked_song(song_info.id));
+        match ncm_api_guard.like_song(song_info.id, like).await {
+            Ok(_) => self.command_line.set_content(
+                format!(
+                    "{}: {}",
+                    if like { "已喜欢" } else { "已取消喜欢" },
+                    song_info.name
+                )
+                .as_str(),
+            ),
+            Err(e) => self
+                .command_line
+                .set_content(format!("操作失败: {e}").as_str()),
+        }
+    }
+
+    /// 上传本地文件到云盘，并切换到云盘页查看进度
+    async fn cloud_upload(&mut self, file_path: &str) {
+        if !NCM_API.lock().await.is_login() {
+            self.command_line.set_content("请先登录");
+            return;
+        }
+
+        match self.cloud_screen.start_upload(expand_home(file_path)).await {
+            Ok(_) => self.switch_screen(ScreenEnum::Cloud).await,
+            Err(e) => self
+                .command_line
+                .set_content(format!("上传失败: {e}").as_str()),
+        }
+    }
+
+    /// 以本地音频目录（或单个文件）为当前播放列表，不需要登录和网络
+    /// 读取标签较慢，在后台线程中读取，完成后由 update_model() 切换播放列表
+    pub fn open_local(&mut self, path: PathBuf) {
+        self.command_line.set_content("正在读取本地音频文件...");
+        let sender = self.local_songs_sender.clone();
+        task::spawn_blocking(move || {
+            let result = load_local(&path);
+            // App 已退出时接收端被 drop，忽略即可
+            let _ = sender.send((path, result));
+        });
+    }
+
+    /// 使用当前用户的歌词显示设置，未登录或用户未设置时使用配置文件中的值
+    async fn load_lyric_layer(&mut self) {
+        let uid = NCM_API.lock().await.login_info().map(|info| info.uid);
+        let user_lyric_layer = match uid {
+            Some(uid) => match UserSettings::load(&PATH_CONFIG.user_settings(uid)) {
+                Ok(user_settings) => user_settings.lyric_layer,
+                Err(e) => {
+                    self.command_line
+                        .set_content(format!("读取用户设置失败: {e}").as_str());
+                    None
+                }
+            },
+            None => None,
+        };
+        // 未登录时可能已通过 :lyric 修改了配置文件，重新读取
+        let lyric_layer = user_lyric_layer.unwrap_or_else(|| {
+            Settings::load(&PATH_CONFIG.settings)
+                .map(|settings| settings.lyric_layer)
+                .unwrap_or(SETTINGS.lyric_layer)
+        });
+
+        self.main_screen.set_lyric_layer(lyric_layer);
+        self.need_re_update_view = true;
+    }
+
+    /// 本地音频读取完成后切换到对应的播放列表
+    async fn switch_to_local(&mut self, path: PathBuf, result: Result<Vec<SongInfo>>) {
+        let songs = match result {
+            Ok(songs) if !songs.is_empty() => songs,
+            Ok(_) => {
+                self.command_line
+                    .set_content(format!("{} 中没有音频文件", path.display()).as_str());
+                return;
+            }
+            Err(e) => {
+                self.command_line
+                    .set_content(format!("读取本地音频失败: {e}").as_str());
+                return;
+            }
+        };
+
+        let playlist_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.display().to_string());
+        let song_count = songs.len();
+        PLAYER
+            .lock()
+            .await
+            .switch_playlist(format!("本地: {playlist_name}"), songs);
+        self.switch_screen(ScreenEnum::Main).await;
+        self.command_line
+            .set_content(format!("已打开 {playlist_name}，共 {song_count} 首").as_str());
+    }
+
+    /// 设置当前歌曲的歌词时间偏移并提示
+    async fn set_lyric_offset(&mut self, offset: i64) {
+        let result = PLAYER
+            .lock()
+            .await
+            .set_current_song_lyric_offset(NCM_API.lock().await, offset)
+            .await;
+        match result {
+            Ok(_) => self
+                .command_line
+                .set_content(format!("歌词时间偏移: {offset:+}ms").as_str()),
+            Err(e) => self.show_error(&e),
+        }
+    }
+
+    /// 以每日推荐歌曲为当前播放列表
+    async fn play_daily_recommend(&mut self) {
+        let songs = match NCM_API.lock().await.recommend_songs().await {
+            Ok(songs) => songs,
+            Err(e) => {
+                self.command_line
+                    .set_content(format!("每日推荐获取失败: {e}").as_str());
+                return;
+            }
+        };
+
+        PLAYER
+            .lock()
+            .await
+            .switch_playlist(String::from("每日推荐"), songs);
+        self.switch_screen(ScreenEnum::Main).await;
+    }
+
+    /// 进入私人 FM 或心动模式
+    /// 心动模式以当前播放歌曲为起始歌曲，没有时使用“我喜欢的音乐”中的第一首
+    async fn start_radio(&mut self, heartbeat: bool) {
+        let mut player_guard = PLAYER.lock().await;
+        let ncm_api_guard = NCM_API.lock().await;
+
+        let source = if heartbeat {
+            let seed_song_id = match player_guard.current_song_info_ref() {
+                Some(song_info) => Some(song_info.id),
+                None => ncm_api_guard
+                    .user_favorite_songlist()
+                    .1
+                    .and_then(|songs| songs.first().map(|song_info| song_info.id)),
+            };
+            let seed_song_id = match seed_song_id {
+                Some(seed_song_id) => seed_song_id,
+                None => {
+                    self.command_line.set_content("心动模式需要一首起始歌曲");
+                    return;
+                }
+            };
+            match ncm_api_guard.user_liked_songlist_id().await {
+                Ok(songlist_id) => RadioSource::Heartbeat {
+                    songlist_id,
+                    seed_song_id,
+                },
+                Err(e) => {
+                    self.command_line
+                        .set_content(format!("心动模式开启失败: {e}").as_str());
+                    return;
+                }
+            }
+        } else {
+            RadioSource::PersonalFm
+        };
+
+        match player_guard.start_radio(source, ncm_api_guard).await {
+            Ok(_) => {
+                drop(player_guard);
+                self.switch_screen(ScreenEnum::Main).await;
+            }
+            Err(e) => self
+                .command_line
+                .set_content(format!("电台开启失败: {e}").as_str()),
+        }
+    }
+
+    /// 打开选中歌曲（或当前播放歌曲）的歌手页
+    /// index: 多位歌手时的序号，为空时打开第一位歌手
+    async fn open_artist(&mut self, index: Option<usize>) {
+        let mut singers = match self.current_screen {
+            ScreenEnum::Main => self
+                .main_screen
+                .selected_song_info()
+                .await
+                .map(|song_info| song_info.singers)
+                .unwrap_or_default(),
+            ScreenEnum::Search => self.search_screen.selected_singers(),
+            ScreenEnum::Album => self
+                .album_screen
+                .selected_song_info()
+                .map(|song_info| song_info.singers)
+                .unwrap_or_default(),
+            ScreenEnum::Artist => self
+                .artist_screen
+                .selected_song_info()
+                .map(|song_info| song_info.singers)
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        };
+        if singers.is_empty() {
+            if let Some(song_info) = PLAYER.lock().await.current_song_info_ref() {
+                singers = song_info.singers.clone();
+            }
+        }
+
+        let singer = match singers.get(index.unwrap_or(0)) {
+            Some(singer) => singer.clone(),
+            None => {
+                self.command_line.set_content(if singers.is_empty() {
+                    "未找到歌手信息"
+                } else {
+                    "artist: 歌手序号超出范围"
+                });
+                return;
+            }
+        };
+
+        match self.artist_screen.load(singer).await {
+            Ok(_) => {
+                self.switch_screen(ScreenEnum::Artist).await;
+                if index.is_none() && singers.len() > 1 {
+                    let singer_names = singers
+                        .iter()
+                        .enumerate()
+                        .map(|(i, singer)| format!("{}.{}", i + 1, singer.name))
+                        .collect::<Vec<String>>()
+                        .join(" ");
+                    self.command_line.set_content(
+                        format!("该歌曲有多位歌手: {singer_names}，使用`artist <序号>`查看")
+                            .as_str(),
+                    );
+                }
+            }
+            Err(e) => self
+                .command_line
+                .set_content(format!("歌手信息获取失败: {e}").as_str()),
+        }
+    }
+}
+
+/// 展开路径开头的 `~/`
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs_next::home_dir()) {
+        (Some(relative_path), Some(home_dir)) => home_dir.join(relative_path),
+        _ => PathBuf::from(path),
+    }
 }
@@ -1,9 +1,23 @@
 //
+mod album_screen;
+mod artist_screen;
+mod cloud_screen;
+mod discover_screen;
 mod help_screen;
 mod login_screen;
 mod main_screen;
+mod playlists_screen;
+mod queue_screen;
+mod search_screen;
 
 //
+pub use album_screen::AlbumScreen;
+pub use artist_screen::ArtistScreen;
+pub use cloud_screen::CloudScreen;
+pub use discover_screen::DiscoverScreen;
 pub use help_screen::HelpScreen;
 pub use login_screen::LoginScreen;
 pub use main_screen::MainScreen;
+pub use playlists_screen::PlaylistsScreen;
+pub use queue_screen::QueueScreen;
+pub use search_screen::SearchScreen;
@@ -0,0 +1,268 @@
+use crate::config::Command;
+use crate::ui::Controller;
+use crate::{NCM_API, PLAYER};
+use anyhow::Result;
+use ncm_api::{AlbumDetail, AlbumDetailDynamic, SongInfo};
+use ratatui::layout::Rect;
+use ratatui::prelude::*;
+use ratatui::style::palette::tailwind;
+use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState, Wrap};
+use ratatui::Frame;
+
+const ITEM_SELECTED_STYLE: Style = Style::new()
+    .bg(tailwind::RED.c400)
+    .add_modifier(Modifier::BOLD);
+
+pub struct AlbumScreen<'a> {
+    // model
+    album_detail: Option<AlbumDetail>,
+    album_detail_dynamic: Option<AlbumDetailDynamic>,
+    track_table_rows: Vec<Row<'a>>,
+    track_table_state: TableState,
+
+    // view
+    album_info_page: Paragraph<'a>,
+    track_table: Table<'a>,
+}
+
+impl<'a> AlbumScreen<'a> {
+    pub fn new(_normal_style: &Style) -> Self {
+        Self {
+            album_detail: None,
+            album_detail_dynamic: None,
+            track_table_rows: Vec::new(),
+            track_table_state: TableState::new(),
+            album_info_page: Paragraph::default(),
+            track_table: Table::default(),
+        }
+    }
+}
+
+/// public
+impl<'a> AlbumScreen<'a> {
+    /// 获取专辑详情，切换到专辑页面前调用
+    pub async fn load(&mut self, album_id: u64) -> Result<()> {
+        let ncm_api_guard = NCM_API.lock().await;
+        let album_detail = ncm_api_guard.album_detail(album_id).await?;
+        // 动态信息获取失败时不影响专辑展示
+        let album_detail_dynamic = ncm_api_guard.album_detail_dynamic(album_id).await.ok();
+        drop(ncm_api_guard);
+
+        self.track_table_rows = album_detail
+            .songs
+            .iter()
+            .enumerate()
+            .map(|(index, song_info)| {
+                Row::from_iter(vec![
+                    Cell::new(format!("{:02}", index + 1)),
+                    Cell::new(song_info.name.clone()),
+                    Cell::new(song_info.singer.clone()),
+                    Cell::new(format!(
+                        "{:02}:{:02}",
+                        song_info.duration / 60000,
+                        song_info.duration % 60000 / 1000
+                    )),
+                ])
+            })
+            .collect();
+        self.track_table_state
+            .select(if self.track_table_rows.is_empty() {
+                None
+            } else {
+                Some(0)
+            });
+
+        self.album_detail = Some(album_detail);
+        self.album_detail_dynamic = album_detail_dynamic;
+
+        Ok(())
+    }
+
+    /// 曲目中选中的歌曲
+    pub fn selected_song_info(&self) -> Option<SongInfo> {
+        let selected = self.track_table_state.selected()?;
+        self.album_detail
+            .as_ref()
+            .and_then(|album_detail| album_detail.songs.get(selected))
+            .cloned()
+    }
+}
+
+impl<'a> Controller for AlbumScreen<'a> {
+    async fn update_model(&mut self) -> Result<bool> {
+        Ok(false)
+    }
+
+    async fn handle_event(&mut self, cmd: Command) -> Result<bool> {
+        match cmd {
+            Command::Down => {
+                // 直接使用 select_next() 存在越界问题
+                if let (Some(selected), list_len) = (
+                    self.track_table_state.selected(),
+                    self.track_table_rows.len(),
+                ) {
+                    if selected + 1 < list_len {
+                        self.track_table_state.select_next();
+                    }
+                }
+            }
+            Command::Up => self.track_table_state.select_previous(),
+            Command::GoToTop => self.track_table_state.select_first(),
+            Command::GoToBottom => {
+                // 使用 select_last() 会越界
+                if !self.track_table_rows.is_empty() {
+                    self.track_table_state
+                        .select(Some(self.track_table_rows.len() - 1));
+                }
+            }
+            Command::Play => {
+                // 以专辑作为当前播放列表，并播放选中的歌曲
+                if let (Some(album_detail), Some(selected)) =
+                    (&self.album_detail, self.track_table_state.selected())
+                {
+                    let mut player_guard = PLAYER.lock().await;
+                    player_guard
+                        .switch_playlist(album_detail.name.clone(), album_detail.songs.clone());
+                    player_guard
+                        .play_particularly_now(selected, NCM_API.lock().await)
+                        .await?;
+                }
+            }
+            Command::Collect(subscribe) => {
+                if let Some(album_detail) = &self.album_detail {
+                    let is_sub = self
+                        .album_detail_dynamic
+                        .as_ref()
+                        .map(|album_detail_dynamic| album_detail_dynamic.is_sub)
+                        .unwrap_or(false);
+                    let subscribe = subscribe.unwrap_or(!is_sub);
+                    NCM_API
+                        .lock()
+                        .await
+                        .subscribe_album(album_detail.id, subscribe)
+                        .await?;
+                    // 同步更新收藏状态与收藏数
+                    if let Some(album_detail_dynamic) = &mut self.album_detail_dynamic {
+                        if album_detail_dynamic.is_sub != subscribe {
+                            album_detail_dynamic.is_sub = subscribe;
+                            if subscribe {
+                                album_detail_dynamic.sub_count += 1;
+                            } else {
+                                album_detail_dynamic.sub_count =
+                                    album_detail_dynamic.sub_count.saturating_sub(1);
+                            }
+                        }
+                    }
+                }
+            }
+            _ => {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    fn update_view(&mut self, style: &Style) {
+        let header_style = Style::default().fg(tailwind::WHITE).bg(tailwind::RED.c300);
+
+        // 专辑信息
+        let mut album_info_lines = Vec::new();
+        if let Some(album_detail) = &self.album_detail {
+            album_info_lines.push(Line::from(format!("专辑: {}", album_detail.name)).bold());
+            album_info_lines.push(Line::from(format!("歌手: {}", album_detail.artist_name)));
+            album_info_lines.push(Line::from(format!(
+                "发行时间: {}",
+                format_date(album_detail.publish_time)
+            )));
+            if let Some(album_detail_dynamic) = &self.album_detail_dynamic {
+                album_info_lines.push(Line::from(format!(
+                    "收藏数: {}    评论数: {}{}",
+                    album_detail_dynamic.sub_count,
+                    album_detail_dynamic.comment_count,
+                    if album_detail_dynamic.is_sub {
+                        "    (已收藏)"
+                    } else {
+                        ""
+                    }
+                )));
+            }
+            album_info_lines.push(Line::from(""));
+            for description_line in album_detail.description.lines() {
+                album_info_lines.push(Line::from(description_line.to_string()));
+            }
+        }
+        self.album_info_page = Paragraph::new(Text::from(album_info_lines))
+            .wrap(Wrap { trim: false })
+            .block(Block::default().title("专辑信息").borders(Borders::ALL))
+            .style(*style);
+
+        // 曲目
+        self.track_table = Table::new(
+            self.track_table_rows.clone(),
+            [
+                Constraint::Length(3),
+                Constraint::Min(40),
+                Constraint::Min(15),
+                Constraint::Length(6),
+            ],
+        )
+        .header(
+            Row::new(vec![
+                Cell::new("#"),
+                Cell::new("曲名"),
+                Cell::new("歌手/乐手"),
+                Cell::new("时长"),
+            ])
+            .style(header_style)
+            .height(1),
+        )
+        .block(
+            Block::default()
+                .title(format!(
+                    "Album: {}\u{1F4BF}",
+                    self.album_detail
+                        .as_ref()
+                        .map(|album_detail| album_detail.name.clone())
+                        .unwrap_or_default()
+                ))
+                .title_bottom(
+                    Line::from("回车以专辑为播放列表播放  c 收藏  r 查看歌手  Esc 返回")
+                        .right_aligned(),
+                )
+                .borders(Borders::ALL),
+        )
+        .row_highlight_style(ITEM_SELECTED_STYLE)
+        .highlight_symbol(">");
+    }
+
+    fn draw(&self, frame: &mut Frame, chunk: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(35), Constraint::Percentage(65)].as_ref())
+            .split(chunk);
+
+        frame.render_widget(&self.album_info_page, chunks[0]);
+
+        let mut track_table_state = self.track_table_state.clone();
+        frame.render_stateful_widget(&self.track_table, chunks[1], &mut track_table_state);
+    }
+}
+
+/// 毫秒时间戳转换为日期 (UTC+8)
+/// 算法参考 http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn format_date(timestamp_ms: u64) -> String {
+    let days = ((timestamp_ms / 1000 + 8 * 3600) / 86400) as i64;
+
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
@@ -0,0 +1,376 @@
+use crate::config::Command;
+use crate::ui::Controller;
+use crate::{NCM_API, PLAYER};
+use anyhow::Result;
+use ncm_api::{SingerDetail, SingerInfo, SongInfo, SongList};
+use ratatui::layout::Rect;
+use ratatui::prelude::*;
+use ratatui::style::palette::tailwind;
+use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState, Tabs, Wrap};
+use ratatui::Frame;
+
+const ITEM_SELECTED_STYLE: Style = Style::new()
+    .bg(tailwind::RED.c400)
+    .add_modifier(Modifier::BOLD);
+const TAB_SELECTED_STYLE: Style = Style::new()
+    .fg(tailwind::RED.c600)
+    .add_modifier(Modifier::BOLD);
+
+/// 每页专辑数量
+const ARTIST_ALBUM_PAGE_SIZE: u16 = 30;
+
+#[derive(PartialEq, Clone, Copy)]
+enum ArtistTab {
+    HotSongs,
+    Albums,
+}
+
+impl ArtistTab {
+    fn index(&self) -> usize {
+        match self {
+            ArtistTab::HotSongs => 0,
+            ArtistTab::Albums => 1,
+        }
+    }
+
+    fn toggle(&self) -> Self {
+        match self {
+            ArtistTab::HotSongs => ArtistTab::Albums,
+            ArtistTab::Albums => ArtistTab::HotSongs,
+        }
+    }
+}
+
+pub struct ArtistScreen<'a> {
+    // model
+    singer: Option<SingerInfo>,
+    singer_detail: Option<SingerDetail>,
+    current_tab: ArtistTab,
+    album_page: u16,
+    //
+    hot_songs: Vec<SongInfo>,
+    albums: Vec<SongList>,
+    table_rows: Vec<Row<'a>>,
+    table_state: TableState,
+
+    // view
+    artist_info_page: Paragraph<'a>,
+    artist_tabs: Tabs<'a>,
+    table: Table<'a>,
+}
+
+impl<'a> ArtistScreen<'a> {
+    pub fn new(_normal_style: &Style) -> Self {
+        Self {
+            singer: None,
+            singer_detail: None,
+            current_tab: ArtistTab::HotSongs,
+            album_page: 0,
+            hot_songs: Vec::new(),
+            albums: Vec::new(),
+            table_rows: Vec::new(),
+            table_state: TableState::new(),
+            artist_info_page: Paragraph::default(),
+            artist_tabs: Tabs::default(),
+            table: Table::default(),
+        }
+    }
+}
+
+/// public
+impl<'a> ArtistScreen<'a> {
+    /// 获取歌手详情、热门单曲与专辑，切换到歌手页面前调用
+    pub async fn load(&mut self, singer: SingerInfo) -> Result<()> {
+        let ncm_api_guard = NCM_API.lock().await;
+        let hot_songs = ncm_api_guard.singer_songs(singer.id).await?;
+        let albums = ncm_api_guard
+            .singer_albums(singer.id, 0, ARTIST_ALBUM_PAGE_SIZE)
+            .await?;
+        // 歌手简介获取失败时不影响歌手页展示
+        let singer_detail = ncm_api_guard.singer_detail(singer.id).await.ok();
+        drop(ncm_api_guard);
+
+        self.singer = Some(singer);
+        self.singer_detail = singer_detail;
+        self.hot_songs = hot_songs;
+        self.albums = albums;
+        self.album_page = 0;
+        self.current_tab = ArtistTab::HotSongs;
+        self.refresh_table_rows();
+
+        Ok(())
+    }
+
+    /// 热门单曲中选中的歌曲
+    pub fn selected_song_info(&self) -> Option<SongInfo> {
+        if self.current_tab != ArtistTab::HotSongs {
+            return None;
+        }
+        self.table_state
+            .selected()
+            .and_then(|selected| self.hot_songs.get(selected))
+            .cloned()
+    }
+
+    /// 选中的专辑或选中歌曲所属专辑
+    pub fn selected_album_id(&self) -> Option<u64> {
+        let selected = self.table_state.selected()?;
+        match self.current_tab {
+            ArtistTab::HotSongs => self.hot_songs.get(selected).map(|s| s.album_id),
+            ArtistTab::Albums => self.albums.get(selected).map(|s| s.id),
+        }
+    }
+}
+
+impl<'a> Controller for ArtistScreen<'a> {
+    async fn update_model(&mut self) -> Result<bool> {
+        Ok(false)
+    }
+
+    async fn handle_event(&mut self, cmd: Command) -> Result<bool> {
+        match cmd {
+            Command::NextPanel | Command::PrevPanel => {
+                self.current_tab = self.current_tab.toggle();
+                self.refresh_table_rows();
+            }
+            Command::NextPage => {
+                // 当前页未满说明已无更多专辑
+                if self.current_tab != ArtistTab::Albums
+                    || self.albums.len() < ARTIST_ALBUM_PAGE_SIZE as usize
+                {
+                    return Ok(false);
+                }
+                self.album_page += 1;
+                self.load_albums().await?;
+            }
+            Command::PrevPage => {
+                if self.current_tab != ArtistTab::Albums || self.album_page == 0 {
+                    return Ok(false);
+                }
+                self.album_page -= 1;
+                self.load_albums().await?;
+            }
+            Command::Down => {
+                // 直接使用 select_next() 存在越界问题
+                if let (Some(selected), list_len) =
+                    (self.table_state.selected(), self.table_rows.len())
+                {
+                    if selected + 1 < list_len {
+                        self.table_state.select_next();
+                    }
+                }
+            }
+            Command::Up => self.table_state.select_previous(),
+            Command::GoToTop => self.table_state.select_first(),
+            Command::GoToBottom => {
+                // 使用 select_last() 会越界
+                if !self.table_rows.is_empty() {
+                    self.table_state.select(Some(self.table_rows.len() - 1));
+                }
+            }
+            Command::Play => {
+                self.play_selected().await?;
+            }
+            Command::AddToPlaylist => {
+                if let Some(song_info) = self.selected_song_info() {
+                    PLAYER.lock().await.add_to_playlist(song_info);
+                }
+            }
+            Command::Collect(subscribe) => {
+                if self.current_tab != ArtistTab::Albums {
+                    return Ok(false);
+                }
+                if let Some(album_id) = self.selected_album_id() {
+                    NCM_API
+                        .lock()
+                        .await
+                        .subscribe_album(album_id, subscribe.unwrap_or(true))
+                        .await?;
+                }
+            }
+            _ => {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    fn update_view(&mut self, style: &Style) {
+        let header_style = Style::default().fg(tailwind::WHITE).bg(tailwind::RED.c300);
+
+        let singer_name = self
+            .singer
+            .as_ref()
+            .map(|singer| singer.name.clone())
+            .unwrap_or_default();
+
+        // 歌手信息
+        let mut artist_info_lines = vec![Line::from(format!("歌手: {}", singer_name)).bold()];
+        if let Some(singer_detail) = &self.singer_detail {
+            artist_info_lines.push(Line::from(format!(
+                "单曲数: {}    专辑数: {}    MV数: {}",
+                singer_detail.music_size, singer_detail.album_size, singer_detail.mv_size
+            )));
+            artist_info_lines.push(Line::from(""));
+            for description_line in singer_detail.brief_desc.lines() {
+                artist_info_lines.push(Line::from(description_line.to_string()));
+            }
+        }
+        self.artist_info_page = Paragraph::new(Text::from(artist_info_lines))
+            .wrap(Wrap { trim: false })
+            .block(Block::default().title("歌手信息").borders(Borders::ALL))
+            .style(*style);
+
+        self.artist_tabs = Tabs::new(vec!["热门单曲", "专辑"])
+            .select(self.current_tab.index())
+            .highlight_style(TAB_SELECTED_STYLE)
+            .block(
+                Block::default()
+                    .title(format!("Artist: {}\u{1F3A4}", singer_name))
+                    .borders(Borders::ALL),
+            );
+
+        let (header, widths, title, footer) = match self.current_tab {
+            ArtistTab::HotSongs => (
+                vec!["曲名", "歌手/乐手", "专辑", "时长"],
+                vec![
+                    Constraint::Min(30),
+                    Constraint::Min(15),
+                    Constraint::Min(15),
+                    Constraint::Length(6),
+                ],
+                String::from("热门单曲"),
+                "回车播放热门单曲  a 加入当前播放列表  b 查看专辑  Esc 返回",
+            ),
+            ArtistTab::Albums => (
+                vec!["专辑", "歌手/乐手"],
+                vec![Constraint::Min(30), Constraint::Min(15)],
+                format!("专辑: 第 {} 页", self.album_page + 1),
+                "回车切换到该专辑  b 查看专辑  c 收藏  [ / ] 翻页  Esc 返回",
+            ),
+        };
+
+        self.table = Table::new(self.table_rows.clone(), widths)
+            .header(Row::new(header).style(header_style).height(1))
+            .block(
+                Block::default()
+                    .title(title)
+                    .title_bottom(Line::from(footer).right_aligned())
+                    .borders(Borders::ALL),
+            )
+            .row_highlight_style(ITEM_SELECTED_STYLE)
+            .highlight_symbol(">");
+    }
+
+    fn draw(&self, frame: &mut Frame, chunk: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(35), Constraint::Percentage(65)].as_ref())
+            .split(chunk);
+
+        // 左侧渲染歌手信息
+        frame.render_widget(&self.artist_info_page, chunks[0]);
+
+        // 右侧渲染热门单曲/专辑
+        let right_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(3)].as_ref())
+            .split(chunks[1]);
+        frame.render_widget(&self.artist_tabs, right_chunks[0]);
+
+        let mut table_state = self.table_state.clone();
+        frame.render_stateful_widget(&self.table, right_chunks[1], &mut table_state);
+    }
+}
+
+/// private
+impl<'a> ArtistScreen<'a> {
+    /// 按当前标签页重建表格
+    fn refresh_table_rows(&mut self) {
+        self.table_rows = match self.current_tab {
+            ArtistTab::HotSongs => self
+                .hot_songs
+                .iter()
+                .map(|song_info| {
+                    Row::from_iter(vec![
+                        Cell::new(song_info.name.clone()),
+                        Cell::new(song_info.singer.clone()),
+                        Cell::new(song_info.album.clone()),
+                        Cell::new(format!(
+                            "{:02}:{:02}",
+                            song_info.duration / 60000,
+                            song_info.duration % 60000 / 1000
+                        )),
+                    ])
+                })
+                .collect(),
+            ArtistTab::Albums => self
+                .albums
+                .iter()
+                .map(|album| {
+                    Row::from_iter(vec![
+                        Cell::new(album.name.clone()),
+                        Cell::new(album.author.clone()),
+                    ])
+                })
+                .collect(),
+        };
+
+        self.table_state.select(if self.table_rows.is_empty() {
+            None
+        } else {
+            Some(0)
+        });
+    }
+
+    /// 获取当前页的专辑
+    async fn load_albums(&mut self) -> Result<()> {
+        if let Some(singer) = &self.singer {
+            self.albums = NCM_API
+                .lock()
+                .await
+                .singer_albums(
+                    singer.id,
+                    self.album_page * ARTIST_ALBUM_PAGE_SIZE,
+                    ARTIST_ALBUM_PAGE_SIZE,
+                )
+                .await?;
+            self.refresh_table_rows();
+        }
+
+        Ok(())
+    }
+
+    /// 热门单曲：以热门单曲为当前播放列表，并播放选中的歌曲
+    /// 专辑：切换为当前播放列表
+    async fn play_selected(&mut self) -> Result<()> {
+        if let Some(selected) = self.table_state.selected() {
+            match self.current_tab {
+                ArtistTab::HotSongs => {
+                    if let Some(singer) = &self.singer {
+                        let mut player_guard = PLAYER.lock().await;
+                        player_guard.switch_playlist(
+                            format!("{} 热门单曲", singer.name),
+                            self.hot_songs.clone(),
+                        );
+                        player_guard
+                            .play_particularly_now(selected, NCM_API.lock().await)
+                            .await?;
+                    }
+                }
+                ArtistTab::Albums => {
+                    if let Some(album) = self.albums.get(selected) {
+                        let album_detail = NCM_API.lock().await.album_detail(album.id).await?;
+                        PLAYER
+                            .lock()
+                            .await
+                            .switch_playlist(album_detail.name, album_detail.songs);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,350 @@
+use crate::config::Command;
+use crate::ui::Controller;
+use crate::{NCM_API, PLAYER};
+use anyhow::{anyhow, Result};
+use ncm_api::{SongInfo, UploadProgress};
+use ratatui::layout::Rect;
+use ratatui::prelude::*;
+use ratatui::style::palette::tailwind;
+use ratatui::widgets::{Block, Borders, Cell, Gauge, Row, Table, TableState};
+use ratatui::Frame;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tokio::task;
+
+const ITEM_SELECTED_STYLE: Style = Style::new()
+    .bg(tailwind::RED.c400)
+    .add_modifier(Modifier::BOLD);
+
+/// 每页云盘歌曲数量
+const CLOUD_PAGE_SIZE: u16 = 50;
+
+/// 上传状态，由上传任务更新，页面每次 update_model 时读取
+#[derive(Clone)]
+enum UploadState {
+    Idle,
+    Uploading(String, UploadProgress), // (文件名, 进度)
+    Finished(String, bool),            // (文件名, 是否已刷新歌曲列表)
+    Failed(String, String),            // (文件名, 错误信息)
+}
+
+pub struct CloudScreen<'a> {
+    // model
+    need_reload: bool,
+    page: u16,
+    status: Option<String>,           // 加载失败或未登录时的提示
+    pending_delete: Option<SongInfo>, // 等待再次按 d 确认删除的歌曲
+    upload_state: Arc<Mutex<UploadState>>,
+    upload_label: Option<String>,
+    upload_ratio: f64,
+    //
+    songs: Vec<SongInfo>,
+    song_table_rows: Vec<Row<'a>>,
+    song_table_state: TableState,
+
+    // view
+    song_table: Table<'a>,
+    upload_bar: Gauge<'a>,
+}
+
+impl<'a> CloudScreen<'a> {
+    pub fn new(_normal_style: &Style) -> Self {
+        Self {
+            need_reload: true,
+            page: 0,
+            status: None,
+            pending_delete: None,
+            upload_state: Arc::new(Mutex::new(UploadState::Idle)),
+            upload_label: None,
+            upload_ratio: 0.0,
+            songs: Vec::new(),
+            song_table_rows: Vec::new(),
+            song_table_state: TableState::new(),
+            song_table: Table::default(),
+            upload_bar: Gauge::default(),
+        }
+    }
+}
+
+/// public
+impl<'a> CloudScreen<'a> {
+    /// 登录/登出后调用，下次进入页面时重新获取云盘歌曲
+    pub fn reload(&mut self) {
+        self.page = 0;
+        self.pending_delete = None;
+        self.need_reload = true;
+    }
+
+    pub fn selected_song_info(&self) -> Option<SongInfo> {
+        self.song_table_state
+            .selected()
+            .and_then(|selected| self.songs.get(selected))
+            .cloned()
+    }
+
+    /// 在后台上传本地文件到云盘，同一时间只能上传一个文件
+    pub async fn start_upload(&mut self, file_path: PathBuf) -> Result<()> {
+        if let UploadState::Uploading(..) = *self.upload_state.lock().unwrap() {
+            return Err(anyhow!("正在上传其他文件，请稍后再试"));
+        }
+        if !file_path.is_file() {
+            return Err(anyhow!("文件不存在: {}", file_path.display()));
+        }
+
+        let file_name = file_path
+            .file_name()
+            .map(|file_name| file_name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        *self.upload_state.lock().unwrap() =
+            UploadState::Uploading(file_name.clone(), UploadProgress::default());
+
+        // 上传耗时较长，使用 NcmApi 的副本上传，不阻塞播放和界面
+        let ncm_api = NCM_API.lock().await.clone();
+        let upload_state = Arc::clone(&self.upload_state);
+        task::spawn(async move {
+            let result = ncm_api
+                .cloud_upload(&file_path, |progress| {
+                    *upload_state.lock().unwrap() =
+                        UploadState::Uploading(file_name.clone(), progress);
+                })
+                .await;
+
+            *upload_state.lock().unwrap() = match result {
+                Ok(_) => UploadState::Finished(file_name, false),
+                Err(e) => UploadState::Failed(file_name, e.to_string()),
+            };
+        });
+
+        Ok(())
+    }
+}
+
+impl<'a> Controller for CloudScreen<'a> {
+    async fn update_model(&mut self) -> Result<bool> {
+        let upload_changed = self.update_upload_state();
+
+        if !self.need_reload {
+            return Ok(upload_changed);
+        }
+        self.need_reload = false;
+
+        self.songs = Vec::new();
+        self.song_table_rows = Vec::new();
+        self.song_table_state.select(None);
+
+        match NCM_API
+            .lock()
+            .await
+            .user_cloud(self.page * CLOUD_PAGE_SIZE, CLOUD_PAGE_SIZE)
+            .await
+        {
+            Ok(songs) => {
+                self.songs = songs;
+                self.status = None;
+            }
+            Err(e) => {
+                self.status = Some(format!("云盘获取失败: {e}"));
+            }
+        }
+
+        self.song_table_rows = self
+            .songs
+            .iter()
+            .map(|song_info| {
+                Row::from_iter(vec![
+                    Cell::new(song_info.name.clone()),
+                    Cell::new(song_info.singer.clone()),
+                    Cell::new(song_info.album.clone()),
+                    Cell::new(format!(
+                        "{:02}:{:02}",
+                        song_info.duration / 60000,
+                        song_info.duration % 60000 / 1000
+                    )),
+                ])
+            })
+            .collect();
+
+        if !self.song_table_rows.is_empty() {
+            self.song_table_state.select(Some(0));
+        }
+
+        Ok(true)
+    }
+
+    async fn handle_event(&mut self, cmd: Command) -> Result<bool> {
+        // 删除需要连续按两次 d 确认，其他操作取消待删除状态
+        let pending_delete = self.pending_delete.take();
+
+        match cmd {
+            Command::Down => {
+                // 直接使用 select_next() 存在越界问题
+                if let (Some(selected), list_len) =
+                    (self.song_table_state.selected(), self.song_table_rows.len())
+                {
+                    if selected + 1 < list_len {
+                        self.song_table_state.select_next();
+                    }
+                }
+            }
+            Command::Up => self.song_table_state.select_previous(),
+            Command::GoToTop => self.song_table_state.select_first(),
+            Command::GoToBottom => {
+                // 使用 select_last() 会越界
+                if !self.song_table_rows.is_empty() {
+                    self.song_table_state
+                        .select(Some(self.song_table_rows.len() - 1));
+                }
+            }
+            Command::NextPage => {
+                // 当前页未满说明已无更多歌曲
+                if self.songs.len() < CLOUD_PAGE_SIZE as usize {
+                    return Ok(false);
+                }
+                self.page += 1;
+                self.need_reload = true;
+            }
+            Command::PrevPage => {
+                if self.page == 0 {
+                    return Ok(false);
+                }
+                self.page -= 1;
+                self.need_reload = true;
+            }
+            Command::Play => {
+                if let Some(selected) = self.song_table_state.selected() {
+                    let mut player_guard = PLAYER.lock().await;
+                    player_guard.switch_playlist(String::from("我的云盘"), self.songs.clone());
+                    player_guard
+                        .play_particularly_now(selected, NCM_API.lock().await)
+                        .await?;
+                }
+            }
+            Command::AddToPlaylist => {
+                if let Some(song_info) = self
+                    .song_table_state
+                    .selected()
+                    .and_then(|selected| self.songs.get(selected))
+                {
+                    PLAYER.lock().await.add_to_playlist(song_info.clone());
+                }
+            }
+            Command::Delete => {
+                if let Some(song_info) = self.selected_song_info() {
+                    if pending_delete.is_some_and(|pending| pending.id == song_info.id) {
+                        NCM_API.lock().await.cloud_delete(song_info.id).await?;
+                        self.need_reload = true;
+                    } else {
+                        self.pending_delete = Some(song_info);
+                    }
+                }
+            }
+            _ => {
+                return Ok(pending_delete.is_some());
+            }
+        }
+
+        Ok(true)
+    }
+
+    fn update_view(&mut self, style: &Style) {
+        let header_style = Style::default().fg(tailwind::WHITE).bg(tailwind::RED.c300);
+
+        self.song_table = Table::new(
+            self.song_table_rows.clone(),
+            [
+                Constraint::Min(40),
+                Constraint::Min(15),
+                Constraint::Min(15),
+                Constraint::Length(6),
+            ],
+        )
+        .header(
+            Row::new(vec![
+                Cell::new("曲名"),
+                Cell::new("歌手/乐手"),
+                Cell::new("专辑"),
+                Cell::new("时长"),
+            ])
+            .style(header_style)
+            .height(1),
+        )
+        .block(
+            Block::default()
+                .title(match (&self.pending_delete, &self.status) {
+                    (Some(song_info), _) => {
+                        format!("Cloud: 再按 d 确认从云盘删除《{}》\u{2601}", song_info.name)
+                    }
+                    (None, Some(status)) => format!("Cloud: {}\u{2601}", status),
+                    (None, None) => format!("Cloud: 第 {} 页\u{2601}", self.page + 1),
+                })
+                .title_bottom(
+                    Line::from(
+                        "回车播放  a 加入当前播放列表  dd 删除  :upload <文件> 上传  [ / ] 翻页",
+                    )
+                    .right_aligned(),
+                )
+                .borders(Borders::ALL),
+        )
+        .row_highlight_style(ITEM_SELECTED_STYLE)
+        .highlight_symbol(">");
+
+        self.upload_bar = Gauge::default()
+            .block(Block::default().title("上传").borders(Borders::ALL))
+            .style(*style)
+            .gauge_style(tailwind::PINK.c300)
+            .ratio(self.upload_ratio)
+            .label(self.upload_label.clone().unwrap_or_default());
+    }
+
+    fn draw(&self, frame: &mut Frame, chunk: Rect) {
+        let mut song_table_state = self.song_table_state.clone();
+
+        if self.upload_label.is_some() {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(3), Constraint::Length(3)].as_ref())
+                .split(chunk);
+            frame.render_stateful_widget(&self.song_table, chunks[0], &mut song_table_state);
+            frame.render_widget(&self.upload_bar, chunks[1]);
+        } else {
+            frame.render_stateful_widget(&self.song_table, chunk, &mut song_table_state);
+        }
+    }
+}
+
+/// private
+impl<'a> CloudScreen<'a> {
+    /// 读取上传任务的状态，返回进度条是否需要刷新
+    /// 上传完成后重新获取云盘歌曲
+    fn update_upload_state(&mut self) -> bool {
+        let (upload_label, upload_ratio) = match &mut *self.upload_state.lock().unwrap() {
+            UploadState::Idle => (None, 0.0),
+            UploadState::Uploading(file_name, progress) => (
+                Some(format!(
+                    "正在上传 {}: {:.1}/{:.1}MB",
+                    file_name,
+                    progress.uploaded as f64 / 1024.0 / 1024.0,
+                    progress.total as f64 / 1024.0 / 1024.0
+                )),
+                progress.ratio(),
+            ),
+            UploadState::Finished(file_name, reloaded) => {
+                // 完成后只刷新一次歌曲列表
+                if !*reloaded {
+                    *reloaded = true;
+                    self.need_reload = true;
+                }
+                (Some(format!("上传完成: {}", file_name)), 1.0)
+            }
+            UploadState::Failed(file_name, e) => {
+                (Some(format!("上传失败 {}: {}", file_name, e)), 0.0)
+            }
+        };
+
+        let changed = upload_label != self.upload_label || upload_ratio != self.upload_ratio;
+        self.upload_label = upload_label;
+        self.upload_ratio = upload_ratio;
+
+        changed
+    }
+}
@@ -0,0 +1,361 @@
+use crate::config::Command;
+use crate::ui::Controller;
+use crate::{NCM_API, PLAYER};
+use anyhow::Result;
+use ncm_api::{BannersInfo, TargetType, TopList};
+use ratatui::layout::Rect;
+use ratatui::prelude::*;
+use ratatui::style::palette::tailwind;
+use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState, Tabs, Wrap};
+use ratatui::Frame;
+
+const ITEM_SELECTED_STYLE: Style = Style::new()
+    .bg(tailwind::RED.c400)
+    .add_modifier(Modifier::BOLD);
+const TAB_SELECTED_STYLE: Style = Style::new()
+    .fg(tailwind::RED.c600)
+    .add_modifier(Modifier::BOLD);
+
+#[derive(PartialEq, Clone, Copy)]
+enum DiscoverTab {
+    Toplists,
+    Banners,
+}
+
+impl DiscoverTab {
+    fn index(&self) -> usize {
+        match self {
+            DiscoverTab::Toplists => 0,
+            DiscoverTab::Banners => 1,
+        }
+    }
+
+    fn toggle(&self) -> Self {
+        match self {
+            DiscoverTab::Toplists => DiscoverTab::Banners,
+            DiscoverTab::Banners => DiscoverTab::Toplists,
+        }
+    }
+}
+
+pub struct DiscoverScreen<'a> {
+    // model
+    need_reload: bool,
+    status: Option<String>, // 加载失败时的提示
+    current_tab: DiscoverTab,
+    //
+    toplists: Vec<TopList>,
+    banners: Vec<(BannersInfo, String)>, // (轮播信息, 轮播内容名称)
+    table_rows: Vec<Row<'a>>,
+    table_state: TableState,
+
+    // view
+    discover_tabs: Tabs<'a>,
+    table: Table<'a>,
+    description_page: Paragraph<'a>,
+}
+
+impl<'a> DiscoverScreen<'a> {
+    pub fn new(_normal_style: &Style) -> Self {
+        Self {
+            need_reload: true,
+            status: None,
+            current_tab: DiscoverTab::Toplists,
+            toplists: Vec::new(),
+            banners: Vec::new(),
+            table_rows: Vec::new(),
+            table_state: TableState::new(),
+            discover_tabs: Tabs::default(),
+            table: Table::default(),
+            description_page: Paragraph::default(),
+        }
+    }
+}
+
+/// public
+impl<'a> DiscoverScreen<'a> {
+    /// 选中的专辑轮播
+    pub fn selected_album_id(&self) -> Option<u64> {
+        match self.selected_banner() {
+            Some(BannersInfo {
+                target_type: TargetType::Album,
+                target_id,
+                ..
+            }) => Some(*target_id),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> Controller for DiscoverScreen<'a> {
+    async fn update_model(&mut self) -> Result<bool> {
+        if !self.need_reload {
+            return Ok(false);
+        }
+        self.need_reload = false;
+
+        let ncm_api_guard = NCM_API.lock().await;
+        match ncm_api_guard.toplist().await {
+            Ok(toplists) => {
+                self.toplists = toplists;
+                self.status = None;
+            }
+            Err(e) => {
+                self.status = Some(format!("榜单获取失败: {e}"));
+            }
+        }
+
+        // 轮播只有内容 id，歌曲与专辑需要再获取名称
+        let banners = ncm_api_guard.banners().await.unwrap_or_default();
+        let song_ids: Vec<u64> = banners
+            .iter()
+            .filter(|banner| matches!(banner.target_type, TargetType::Song))
+            .map(|banner| banner.target_id)
+            .collect();
+        let songs = if song_ids.is_empty() {
+            Vec::new()
+        } else {
+            ncm_api_guard
+                .songs_detail(&song_ids)
+                .await
+                .unwrap_or_default()
+        };
+        self.banners = Vec::new();
+        for banner in banners {
+            let content = match banner.target_type {
+                TargetType::Song => songs
+                    .iter()
+                    .find(|song_info| song_info.id == banner.target_id)
+                    .map(|song_info| format!("{} - {}", song_info.name, song_info.singer)),
+                TargetType::Album => match ncm_api_guard.album_detail(banner.target_id).await {
+                    Ok(album_detail) => Some(format!(
+                        "{} - {}",
+                        album_detail.name, album_detail.artist_name
+                    )),
+                    Err(_) => None,
+                },
+                TargetType::Unknown => None,
+            };
+            // 不支持的轮播类型（活动、MV 等）不展示
+            if let Some(content) = content {
+                self.banners.push((banner, content));
+            }
+        }
+        drop(ncm_api_guard);
+
+        self.refresh_table_rows();
+
+        Ok(true)
+    }
+
+    async fn handle_event(&mut self, cmd: Command) -> Result<bool> {
+        match cmd {
+            Command::NextPanel | Command::PrevPanel => {
+                self.current_tab = self.current_tab.toggle();
+                self.refresh_table_rows();
+            }
+            Command::Down => {
+                // 直接使用 select_next() 存在越界问题
+                if let (Some(selected), list_len) =
+                    (self.table_state.selected(), self.table_rows.len())
+                {
+                    if selected + 1 < list_len {
+                        self.table_state.select_next();
+                    }
+                }
+            }
+            Command::Up => self.table_state.select_previous(),
+            Command::GoToTop => self.table_state.select_first(),
+            Command::GoToBottom => {
+                // 使用 select_last() 会越界
+                if !self.table_rows.is_empty() {
+                    self.table_state.select(Some(self.table_rows.len() - 1));
+                }
+            }
+            Command::Play => {
+                self.play_selected().await?;
+            }
+            _ => {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    fn update_view(&mut self, style: &Style) {
+        let header_style = Style::default().fg(tailwind::WHITE).bg(tailwind::RED.c300);
+
+        self.discover_tabs = Tabs::new(vec!["官方榜单", "轮播推荐"])
+            .select(self.current_tab.index())
+            .highlight_style(TAB_SELECTED_STYLE)
+            .block(
+                Block::default()
+                    .title(match &self.status {
+                        Some(status) => format!("Discover: {}\u{1F525}", status),
+                        None => String::from("Discover\u{1F525}"),
+                    })
+                    .borders(Borders::ALL),
+            );
+
+        let (header, widths, footer) = match self.current_tab {
+            DiscoverTab::Toplists => (
+                vec!["榜单", "更新频率"],
+                vec![Constraint::Min(30), Constraint::Min(15)],
+                "回车切换到该榜单  ← / → 切换标签页",
+            ),
+            DiscoverTab::Banners => (
+                vec!["类型", "内容"],
+                vec![Constraint::Length(12), Constraint::Min(30)],
+                "回车播放单曲 / 切换到该专辑  b 查看专辑  ← / → 切换标签页",
+            ),
+        };
+
+        self.table = Table::new(self.table_rows.clone(), widths)
+            .header(Row::new(header).style(header_style).height(1))
+            .block(
+                Block::default()
+                    .title_bottom(Line::from(footer).right_aligned())
+                    .borders(Borders::ALL),
+            )
+            .row_highlight_style(ITEM_SELECTED_STYLE)
+            .highlight_symbol(">");
+
+        // 选中榜单的简介
+        let description = match self.current_tab {
+            DiscoverTab::Toplists => self
+                .table_state
+                .selected()
+                .and_then(|selected| self.toplists.get(selected))
+                .map(|toplist| format!("{}\n\n{}", toplist.name, toplist.description))
+                .unwrap_or_default(),
+            DiscoverTab::Banners => String::new(),
+        };
+        self.description_page = Paragraph::new(description)
+            .wrap(Wrap { trim: false })
+            .block(Block::default().title("简介").borders(Borders::ALL))
+            .style(*style);
+    }
+
+    fn draw(&self, frame: &mut Frame, chunk: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(3)].as_ref())
+            .split(chunk);
+        frame.render_widget(&self.discover_tabs, chunks[0]);
+
+        let mut table_state = self.table_state.clone();
+        if self.current_tab == DiscoverTab::Toplists {
+            // 榜单页右侧显示简介
+            let body_chunks = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(60), Constraint::Percentage(40)].as_ref())
+                .split(chunks[1]);
+            frame.render_stateful_widget(&self.table, body_chunks[0], &mut table_state);
+            frame.render_widget(&self.description_page, body_chunks[1]);
+        } else {
+            frame.render_stateful_widget(&self.table, chunks[1], &mut table_state);
+        }
+    }
+}
+
+/// private
+impl<'a> DiscoverScreen<'a> {
+    /// 按当前标签页重建表格
+    fn refresh_table_rows(&mut self) {
+        self.table_rows = match self.current_tab {
+            DiscoverTab::Toplists => self
+                .toplists
+                .iter()
+                .map(|toplist| {
+                    Row::from_iter(vec![
+                        Cell::new(toplist.name.clone()),
+                        Cell::new(toplist.update.clone()),
+                    ])
+                })
+                .collect(),
+            DiscoverTab::Banners => self
+                .banners
+                .iter()
+                .map(|(banner, content)| {
+                    Row::from_iter(vec![
+                        Cell::new(banner.type_title.clone()),
+                        Cell::new(content.clone()),
+                    ])
+                })
+                .collect(),
+        };
+
+        self.table_state.select(if self.table_rows.is_empty() {
+            None
+        } else {
+            Some(0)
+        });
+    }
+
+    fn selected_banner(&self) -> Option<&BannersInfo> {
+        if self.current_tab != DiscoverTab::Banners {
+            return None;
+        }
+        self.table_state
+            .selected()
+            .and_then(|selected| self.banners.get(selected))
+            .map(|(banner, _)| banner)
+    }
+
+    /// 榜单：切换为当前播放列表
+    /// 单曲轮播：加入当前播放列表并立刻播放
+    /// 专辑轮播：切换为当前播放列表
+    async fn play_selected(&mut self) -> Result<()> {
+        match self.current_tab {
+            DiscoverTab::Toplists => {
+                if let Some(toplist) = self
+                    .table_state
+                    .selected()
+                    .and_then(|selected| self.toplists.get(selected))
+                {
+                    let songs = NCM_API.lock().await.toplist_detail(toplist.id).await?.songs;
+                    PLAYER
+                        .lock()
+                        .await
+                        .switch_playlist(toplist.name.clone(), songs);
+                }
+            }
+            DiscoverTab::Banners => match self.selected_banner() {
+                Some(BannersInfo {
+                    target_type: TargetType::Song,
+                    target_id,
+                    ..
+                }) => {
+                    let song_info = NCM_API
+                        .lock()
+                        .await
+                        .songs_detail(&[*target_id])
+                        .await?
+                        .pop();
+                    if let Some(song_info) = song_info {
+                        let mut player_guard = PLAYER.lock().await;
+                        let index = player_guard.add_to_playlist(song_info);
+                        player_guard
+                            .play_particularly_now(index, NCM_API.lock().await)
+                            .await?;
+                    }
+                }
+                Some(BannersInfo {
+                    target_type: TargetType::Album,
+                    target_id,
+                    ..
+                }) => {
+                    let album_detail = NCM_API.lock().await.album_detail(*target_id).await?;
+                    PLAYER
+                        .lock()
+                        .await
+                        .switch_playlist(album_detail.name, album_detail.songs);
+                }
+                _ => {}
+            },
+        }
+
+        Ok(())
+    }
+}
@@ -22,12 +22,28 @@ impl<'a> HelpScreen<'a> {
             Previous Panel:                         {}\n\
             Next Panel:                             {}\n\
             Go To Main Screen:                      {}\n\
+            Go To Playlists Screen:                 {}\n\
             Go To Help Screen (Here):               {}\n\
+            Go To Search Screen:                    {}\n\
+            Go To Queue Screen:                     {}\n\
+            Go To Discover Screen:                  {}\n\
+            Go To Cloud Screen:                     {}\n\
             Play Next Song:                         {}\n\
             Play Previous Song:                     {}\n\
             *Switch To Command Line Mode:           {}\n\
             Search Forward:                         {}\n\
             Search Backward:                        {}\n\
+            Next Page / Previous Page:              {}\n\
+            Add To Current Playlist:                {}\n\
+            Open Album Of Selected Song:            {}\n\
+            Open Artist Of Selected Song:           {}\n\
+            Like / Unlike Selected Song:            {}\n\
+            Collect / Uncollect Selected Item:      {}\n\
+            Add To Queue / Play Next:               {}\n\
+            Remove From Queue / Cloud:              {}\n\
+            Move Up / Down In Queue:                {}\n\
+            Trash Current Song (FM):                {}\n\
+            Delay / Advance Lyrics By 100ms:        {}\n\
             Quit:                                   {}",
             "↑ / k",
             "↓ / j",
@@ -35,12 +51,28 @@ impl<'a> HelpScreen<'a> {
             "←",
             "→",
             "1",
+            "2",
             "0 / F1",
+            "3",
+            "4",
+            "5",
+            "6",
             ">",
             "<",
             ":",
             "/",
             "?",
+            "] / [",
+            "a",
+            "b",
+            "r",
+            "f",
+            "c",
+            "e / p",
+            "d",
+            "K / J",
+            "xx",
+            "+ / -",
             "q",
         ));
         let normal_mode_help_page = Paragraph::new(normal_mode_help_text)
@@ -57,40 +89,84 @@ impl<'a> HelpScreen<'a> {
             Logout:                                 {}\n\
             Set Volume:                             {} (e.g. `vol 20` will set volume at 20%)\n\
             Mute:                                   {}\n\
+            Set Audio Quality:                      {}\n\
+            Show Translation / Romanization:        {}\n\
+            Reload Lyrics Of Current Song:          {}\n\
+            Adjust Lyric Timing:                    {} (e.g. `lyric offset +300` will delay lyrics by 300ms)\n\
+            Set Crossfade:                          {} (e.g. `crossfade 5`, only under `list repeat mode` or `shuffle mode`)\n\
+            Set Loudness Normalization:             {}\n\
             Set Play Mode:                          {}\n\
             |_ single play mode:                    {}\n\
             |_ single repeat mode:                  {}\n\
             |_ list repeat mode:                    {}\n\
             |_ shuffle mode:                        {}\n\
+            |_ personal fm:                         {}\n\
+            |_ heartbeat mode:                      {}\n\
             Play Next Song:                         {}\n\
             Play Previous Song:                     {}\n\
             Start Auto Play:                        {} (Only under `list repeat mode` or `shuffle mode`)\n\
             Jump To Current Song In Playlist:       {}\n\
+            Open Album Of Selected Song:            {}\n\
+            Open Artist Of Selected Song:           {} (e.g. `artist 2` for the second artist)\n\
+            Like / Unlike Selected Song:            {}\n\
+            Collect / Uncollect Album Or Playlist:  {}\n\
+            Go To Queue Screen:                     {}\n\
+            Add To Queue / Play Next:               {}\n\
+            Clear Queue:                            {}\n\
+            Daily Recommended Songs:                {}\n\
+            Go To Discover Screen (Toplists):       {}\n\
+            Trash Current Song (FM):                {}\n\
+            Go To Cloud Screen:                     {}\n\
+            Upload To Cloud:                        {}\n\
+            Open Local Directory As Playlist:       {}\n\
             Jump To Top:                            {}\n\
             Jump To Bottom:                         {}\n\
             Search Forward:                         {}\n\
-            Search Backward:                        {}",
+            Search Backward:                        {}\n\
+            Search Online:                          {}",
             "q / quit / exit",
-            "screen 0 / 1",
-            "screen help / main",
+            "screen 0 / 1 / 2 / 3 / 4 / 5 / 6",
+            "screen help / main / playlists / search / queue / discover / cloud",
             "h / help",
             "l / login",
             "logout",
             "vol / volume",
             "mute",
+            "quality standard / higher / exhigh / lossless / hires",
+            "lyric trans / roma / both / off",
+            "lyric reload",
+            "lyric offset +n / -n / n",
+            "crossfade <secs> / crossfade off",
+            "normalize off / track / album",
             "mode",
             "mode single",
             "mode sr / single-repeat",
             "mode lr / list-repeat",
             "mode s / shuf / shuffle",
+            "fm / mode fm",
+            "hb / heartbeat / mode heartbeat",
             "next",
             "prev / previous",
             "start",
             "where this",
+            "album",
+            "artist [n]",
+            "like / unlike",
+            "collect / uncollect",
+            "queue",
+            "queue add / queue next",
+            "queue clear",
+            "daily / recommend",
+            "discover / toplist",
+            "trash / dislike",
+            "cloud",
+            "upload <file path>",
+            "open / local <directory or file path>",
             "top",
             "bottom",
             "/ xxx",
             "? xxx",
+            "search xxx",
         ));
         let commandline_mode_help_page = Paragraph::new(commandline_mode_help_text)
             .block(Block::default().title("命令行模式").borders(Borders::ALL))
@@ -1,6 +1,6 @@
 use crate::config::Command;
 use crate::ui::Controller;
-use crate::NCM_API;
+use crate::{NCM_API, SETTINGS};
 use anyhow::Result;
 use fast_qr::QRBuilder;
 use ratatui::{
@@ -76,7 +76,9 @@ impl<'a> Controller for LoginScreen<'a> {
             self.create_login_qr().await?;
         }
         if msg.code == 803 {
-            ncm_api_guard.init_after_new_login().await?;
+            ncm_api_guard
+                .init_after_new_login(SETTINGS.startup_playlist)
+                .await?;
         }
 
         Ok(true)
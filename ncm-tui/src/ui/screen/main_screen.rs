@@ -1,8 +1,8 @@
-use crate::config::Command;
+use crate::config::{Command, LyricLayer};
 use crate::ui::Controller;
-use crate::{NCM_API, PLAYER};
+use crate::{NCM_API, PLAYER, SETTINGS};
 use anyhow::Result;
-use ncm_api::SongInfo;
+use ncm_api::{LyricLine, SongInfo};
 use ratatui::layout::Rect;
 use ratatui::prelude::*;
 use ratatui::style::palette::tailwind;
@@ -10,6 +10,7 @@ use ratatui::widgets::{
     Block, Borders, Cell, HighlightSpacing, List, ListItem, ListState, Row, Table, TableState,
 };
 use ratatui::Frame;
+use std::collections::HashSet;
 
 const PANEL_SELECTED_BORDER_STYLE: Style = Style::new().fg(tailwind::RED.c800);
 const ITEM_SELECTED_STYLE: Style = Style::new()
@@ -18,6 +19,9 @@ const ITEM_SELECTED_STYLE: Style = Style::new()
 const LYRIC_FOCUSED_STYLE: Style = Style::new()
     .fg(tailwind::RED.c600)
     .add_modifier(Modifier::BOLD);
+// 逐字高亮时整行只加粗，已唱部分由 LYRIC_FOCUSED_STYLE 着色
+const LYRIC_KARAOKE_STYLE: Style = Style::new().add_modifier(Modifier::BOLD);
+const LIKED_MARK_STYLE: Style = Style::new().fg(tailwind::RED.c500);
 
 #[derive(PartialEq)]
 pub enum FocusPanel {
@@ -32,12 +36,16 @@ pub struct MainScreen<'a> {
     current_focus_panel: FocusPanel,
     //
     playlist_name: String,
+    liked_song_ids: HashSet<u64>,
     playlist_table_rows: Vec<Row<'a>>,
     playlist_table_state: TableState,
     //
     song_info: Option<SongInfo>,
+    song_lyrics: Vec<LyricLine>,
+    song_lyric_word_offset: Option<(usize, usize)>, // (逐字高亮的歌词行, 已唱过的字符数)
     song_lyric_list_items: Vec<ListItem<'a>>,
     song_lyric_list_state: ListState,
+    lyric_layer: LyricLayer,
 
     // view
     playlist_table: Table<'a>,
@@ -54,11 +62,15 @@ impl<'a> MainScreen<'a> {
         Self {
             current_focus_panel: FocusPanel::PlaylistOutside,
             playlist_name: String::new(),
+            liked_song_ids: HashSet::new(),
             playlist_table_rows: Vec::new(),
             playlist_table_state: TableState::new(),
             song_info: None,
+            song_lyrics: Vec::new(),
+            song_lyric_word_offset: None,
             song_lyric_list_items,
             song_lyric_list_state: ListState::default(),
+            lyric_layer: SETTINGS.lyric_layer,
             playlist_table: Table::default(),
             song_lyric_list: List::default(),
         }
@@ -72,7 +84,18 @@ impl<'a> Controller for MainScreen<'a> {
         let player_guard = PLAYER.lock().await;
 
         // playlist
-        if self.playlist_name != *player_guard.current_playlist_name_ref() {
+        // 切换歌单、向当前歌单加入歌曲或喜欢的歌曲变化后均需重建 rows
+        let playlist_switched = self.playlist_name != *player_guard.current_playlist_name_ref();
+        let ncm_api_guard = NCM_API.lock().await;
+        let liked_changed = self.liked_song_ids != *ncm_api_guard.user_liked_song_ids();
+        if liked_changed {
+            self.liked_song_ids = ncm_api_guard.user_liked_song_ids().clone();
+        }
+        drop(ncm_api_guard);
+        if playlist_switched
+            || liked_changed
+            || self.playlist_table_rows.len() != player_guard.current_playlist_len()
+        {
             //
             self.playlist_name = player_guard.current_playlist_name_ref().clone();
             //
@@ -81,6 +104,11 @@ impl<'a> Controller for MainScreen<'a> {
                 .iter()
                 .map(|song_info| {
                     Row::from_iter(vec![
+                        if self.liked_song_ids.contains(&song_info.id) {
+                            Cell::new("\u{2665}").style(LIKED_MARK_STYLE)
+                        } else {
+                            Cell::new("")
+                        },
                         Cell::new(song_info.name.clone()),
                         Cell::new(song_info.singer.clone()),
                         Cell::new(song_info.album.clone()),
@@ -93,8 +121,10 @@ impl<'a> Controller for MainScreen<'a> {
                 })
                 .collect();
 
-            // 更新 playlist_table 的 selected，防止悬空
-            self.playlist_table_state.select(None);
+            // 切换歌单时更新 playlist_table 的 selected，防止悬空
+            if playlist_switched {
+                self.playlist_table_state.select(None);
+            }
 
             result = Ok(true);
         }
@@ -118,29 +148,41 @@ impl<'a> Controller for MainScreen<'a> {
                     result = Ok(true);
                 }
             }
+
+            // 逐字高亮当前歌词行，用户选择歌词行时不高亮
+            let word_offset = if self.current_focus_panel != FocusPanel::LyricInside {
+                player_guard
+                    .current_song_lyric_index()
+                    .zip(player_guard.current_song_lyric_word_offset())
+            } else {
+                None
+            };
+            if self.song_lyric_word_offset != word_offset {
+                if let Some((index, _)) = self.song_lyric_word_offset {
+                    self.update_lyric_list_item(index, None);
+                }
+                if let Some((index, sung_chars)) = word_offset {
+                    self.update_lyric_list_item(index, Some(sung_chars));
+                }
+                self.song_lyric_word_offset = word_offset;
+
+                result = Ok(true);
+            }
         } else {
             // 切换到新歌
             self.song_info = player_guard.current_song_info_ref().clone();
+            self.song_lyric_word_offset = None;
             // 更新歌词 ListItem
             if let Some(lyrics) = player_guard.current_song_lyrics() {
                 // 有歌词
                 self.song_lyric_list_items = lyrics
                     .iter()
-                    .map(|lyric| {
-                        if lyric.1 != None {
-                            // 有翻译
-                            ListItem::new(Text::from(vec![
-                                Line::from(lyric.0.clone()).centered(),
-                                Line::from(lyric.1.clone().unwrap()).centered(),
-                            ]))
-                        } else {
-                            // 无翻译
-                            ListItem::new(Text::from(Line::from(lyric.0.clone()).centered()))
-                        }
-                    })
+                    .map(|lyric| Self::lyric_list_item(lyric, None, self.lyric_layer))
                     .collect();
+                self.song_lyrics = lyrics;
             } else {
                 // 无歌词（纯音乐或网络异常）
+                self.song_lyrics = Vec::new();
                 self.song_lyric_list_items = Vec::new();
                 self.song_lyric_list_items.push(ListItem::new(Text::from(
                     Line::from("无歌词，请欣赏").centered(),
@@ -359,6 +401,38 @@ impl<'a> Controller for MainScreen<'a> {
     }
 }
 
+/// public
+impl<'a> MainScreen<'a> {
+    /// 歌单中选中的歌曲
+    pub async fn selected_song_info(&self) -> Option<SongInfo> {
+        let selected = self.playlist_table_state.selected()?;
+        PLAYER
+            .lock()
+            .await
+            .current_playlist()
+            .get(selected)
+            .cloned()
+    }
+
+    /// 歌词重新获取后，下次 update_model 时重新生成歌词 ListItem
+    pub fn reload_song_lyrics(&mut self) {
+        self.song_info = None;
+    }
+
+    /// 切换歌词下方显示的翻译、音译
+    pub fn set_lyric_layer(&mut self, lyric_layer: LyricLayer) {
+        self.lyric_layer = lyric_layer;
+        if !self.song_lyrics.is_empty() {
+            self.song_lyric_list_items = self
+                .song_lyrics
+                .iter()
+                .map(|lyric| Self::lyric_list_item(lyric, None, lyric_layer))
+                .collect();
+            self.song_lyric_word_offset = None;
+        }
+    }
+}
+
 /// private
 impl<'a> MainScreen<'a> {
     #[inline]
@@ -368,6 +442,7 @@ impl<'a> MainScreen<'a> {
         let mut playlist_table = Table::new(
             self.playlist_table_rows.clone(),
             [
+                Constraint::Length(1),
                 Constraint::Min(40),
                 Constraint::Min(15),
                 Constraint::Min(15),
@@ -376,6 +451,7 @@ impl<'a> MainScreen<'a> {
         )
         .header(
             Row::new(vec![
+                Cell::new(""),
                 Cell::from(Text::from("曲名")),
                 Cell::new("歌手/乐手"),
                 Cell::new("专辑"),
@@ -444,6 +520,10 @@ impl<'a> MainScreen<'a> {
         // highlight
         song_lyric_list = if self.current_focus_panel == FocusPanel::LyricInside {
             song_lyric_list.highlight_style(ITEM_SELECTED_STYLE)
+        } else if self.song_lyric_word_offset.is_some() {
+            song_lyric_list
+                .highlight_style(LYRIC_KARAOKE_STYLE)
+                .highlight_spacing(HighlightSpacing::WhenSelected)
         } else {
             song_lyric_list
                 .highlight_style(LYRIC_FOCUSED_STYLE)
@@ -453,6 +533,57 @@ impl<'a> MainScreen<'a> {
         self.song_lyric_list = song_lyric_list;
     }
 
+    /// 歌词 ListItem，音译和翻译按 lyric_layer 依次显示在原文下方
+    /// sung_chars 不为 None 时逐字高亮已唱过的部分
+    fn lyric_list_item(
+        lyric: &LyricLine,
+        sung_chars: Option<usize>,
+        lyric_layer: LyricLayer,
+    ) -> ListItem<'a> {
+        let lyric_line = match sung_chars {
+            Some(sung_chars) => {
+                let split = lyric
+                    .text
+                    .char_indices()
+                    .nth(sung_chars)
+                    .map_or(lyric.text.len(), |(index, _)| index);
+                Line::from(vec![
+                    Span::styled(lyric.text[..split].to_string(), LYRIC_FOCUSED_STYLE),
+                    Span::raw(lyric.text[split..].to_string()),
+                ])
+            }
+            None => Line::from(lyric.text.clone()),
+        };
+
+        let mut lines = vec![lyric_line.centered()];
+        if let Some(romanization) = lyric
+            .romanization
+            .as_ref()
+            .filter(|_| lyric_layer.show_romanization())
+        {
+            lines.push(Line::from(romanization.clone()).centered());
+        }
+        if let Some(translation) = lyric
+            .translation
+            .as_ref()
+            .filter(|_| lyric_layer.show_translation())
+        {
+            lines.push(Line::from(translation.clone()).centered());
+        }
+
+        ListItem::new(Text::from(lines))
+    }
+
+    /// 重新生成一行歌词的 ListItem
+    fn update_lyric_list_item(&mut self, index: usize, sung_chars: Option<usize>) {
+        if let (Some(lyric), Some(item)) = (
+            self.song_lyrics.get(index),
+            self.song_lyric_list_items.get_mut(index),
+        ) {
+            *item = Self::lyric_list_item(lyric, sung_chars, self.lyric_layer);
+        }
+    }
+
     /// 修正 offset 以使歌词居中
     #[inline]
     fn correct_offset_to_make_lyric_centered(
@@ -462,7 +593,7 @@ impl<'a> MainScreen<'a> {
     ) {
         if self.song_lyric_list_items.len() > 1 {
             let current_index = lyric_list_state.selected().unwrap_or(0);
-            // 一句歌词所占行数（带翻译的歌词会占2行）
+            // 一句歌词所占行数（带翻译、音译的歌词会占2~3行）
             let lyric_line_count = self
                 .song_lyric_list_items
                 .get(current_index)
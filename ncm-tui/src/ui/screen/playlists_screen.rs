@@ -0,0 +1,237 @@
+use crate::config::Command;
+use crate::ui::Controller;
+use crate::{NCM_API, PLAYER};
+use anyhow::{anyhow, Result};
+use ncm_api::SongList;
+use ratatui::layout::Rect;
+use ratatui::prelude::*;
+use ratatui::style::palette::tailwind;
+use ratatui::widgets::{Block, Borders, Cell, Row, Table, TableState};
+use ratatui::Frame;
+
+const ITEM_SELECTED_STYLE: Style = Style::new()
+    .bg(tailwind::RED.c400)
+    .add_modifier(Modifier::BOLD);
+
+/// 每页歌单数量
+const PLAYLISTS_PAGE_SIZE: u16 = 30;
+
+pub struct PlaylistsScreen<'a> {
+    // model
+    need_reload: bool,
+    page: u16,
+    status: Option<String>, // 加载失败或未登录时的提示
+    //
+    songlists: Vec<SongList>,
+    songlist_table_rows: Vec<Row<'a>>,
+    songlist_table_state: TableState,
+
+    // view
+    songlist_table: Table<'a>,
+}
+
+impl<'a> PlaylistsScreen<'a> {
+    pub fn new(_normal_style: &Style) -> Self {
+        Self {
+            need_reload: true,
+            page: 0,
+            status: None,
+            songlists: Vec::new(),
+            songlist_table_rows: Vec::new(),
+            songlist_table_state: TableState::new(),
+            songlist_table: Table::default(),
+        }
+    }
+}
+
+/// public
+impl<'a> PlaylistsScreen<'a> {
+    /// 登录/登出后调用，下次进入页面时重新获取歌单
+    pub fn reload(&mut self) {
+        self.page = 0;
+        self.need_reload = true;
+    }
+}
+
+impl<'a> Controller for PlaylistsScreen<'a> {
+    async fn update_model(&mut self) -> Result<bool> {
+        if !self.need_reload {
+            return Ok(false);
+        }
+        self.need_reload = false;
+
+        self.songlists = Vec::new();
+        self.songlist_table_rows = Vec::new();
+        self.songlist_table_state.select(None);
+
+        let ncm_api_guard = NCM_API.lock().await;
+        match ncm_api_guard.login_info() {
+            Some(login_info) => {
+                match ncm_api_guard
+                    .user_song_list(
+                        login_info.uid,
+                        self.page * PLAYLISTS_PAGE_SIZE,
+                        PLAYLISTS_PAGE_SIZE,
+                    )
+                    .await
+                {
+                    Ok(songlists) => {
+                        self.songlists = songlists;
+                        self.status = None;
+                    }
+                    Err(e) => {
+                        self.status = Some(format!("歌单获取失败: {e}"));
+                    }
+                }
+            }
+            None => {
+                self.status = Some(String::from("请先登录"));
+            }
+        }
+        drop(ncm_api_guard);
+
+        self.songlist_table_rows = self
+            .songlists
+            .iter()
+            .map(|songlist| {
+                Row::from_iter(vec![
+                    Cell::new(songlist.name.clone()),
+                    Cell::new(songlist.author.clone()),
+                    Cell::new(if songlist.subscribed {
+                        "收藏"
+                    } else {
+                        "创建"
+                    }),
+                ])
+            })
+            .collect();
+
+        if !self.songlist_table_rows.is_empty() {
+            self.songlist_table_state.select(Some(0));
+        }
+
+        Ok(true)
+    }
+
+    async fn handle_event(&mut self, cmd: Command) -> Result<bool> {
+        match cmd {
+            Command::Down => {
+                // 直接使用 select_next() 存在越界问题
+                if let (Some(selected), list_len) = (
+                    self.songlist_table_state.selected(),
+                    self.songlist_table_rows.len(),
+                ) {
+                    if selected + 1 < list_len {
+                        self.songlist_table_state.select_next();
+                    }
+                }
+            }
+            Command::Up => self.songlist_table_state.select_previous(),
+            Command::GoToTop => self.songlist_table_state.select_first(),
+            Command::GoToBottom => {
+                // 使用 select_last() 会越界
+                if !self.songlist_table_rows.is_empty() {
+                    self.songlist_table_state
+                        .select(Some(self.songlist_table_rows.len() - 1));
+                }
+            }
+            Command::NextPage => {
+                // 当前页未满说明已无更多歌单
+                if self.songlists.len() < PLAYLISTS_PAGE_SIZE as usize {
+                    return Ok(false);
+                }
+                self.page += 1;
+                self.need_reload = true;
+            }
+            Command::PrevPage => {
+                if self.page == 0 {
+                    return Ok(false);
+                }
+                self.page -= 1;
+                self.need_reload = true;
+            }
+            Command::Play => {
+                if let Some(songlist) = self
+                    .songlist_table_state
+                    .selected()
+                    .and_then(|selected| self.songlists.get(selected))
+                {
+                    let songs = NCM_API
+                        .lock()
+                        .await
+                        .song_list_detail(songlist.id)
+                        .await?
+                        .songs;
+                    PLAYER
+                        .lock()
+                        .await
+                        .switch_playlist(songlist.name.clone(), songs);
+                }
+            }
+            Command::Collect(subscribe) => {
+                if let Some(songlist) = self
+                    .songlist_table_state
+                    .selected()
+                    .and_then(|selected| self.songlists.get(selected))
+                {
+                    // 未收藏的歌单即用户自己创建的歌单
+                    if !songlist.subscribed {
+                        return Err(anyhow!("不能收藏或取消收藏自己创建的歌单"));
+                    }
+                    NCM_API
+                        .lock()
+                        .await
+                        .subscribe_playlist(songlist.id, subscribe.unwrap_or(false))
+                        .await?;
+                    // 取消收藏后歌单从列表中移除，重新获取当前页
+                    self.need_reload = true;
+                }
+            }
+            _ => {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    fn update_view(&mut self, _style: &Style) {
+        let header_style = Style::default().fg(tailwind::WHITE).bg(tailwind::RED.c300);
+
+        self.songlist_table = Table::new(
+            self.songlist_table_rows.clone(),
+            [
+                Constraint::Min(40),
+                Constraint::Min(15),
+                Constraint::Length(6),
+            ],
+        )
+        .header(
+            Row::new(vec![
+                Cell::new("歌单"),
+                Cell::new("创建者"),
+                Cell::new("类型"),
+            ])
+            .style(header_style)
+            .height(1),
+        )
+        .block(
+            Block::default()
+                .title(match &self.status {
+                    Some(status) => format!("Playlists: {}\u{1F4DA}", status),
+                    None => format!("Playlists: 第 {} 页\u{1F4DA}", self.page + 1),
+                })
+                .title_bottom(
+                    Line::from("回车切换到该歌单  c 取消收藏  [ / ] 翻页").right_aligned(),
+                )
+                .borders(Borders::ALL),
+        )
+        .row_highlight_style(ITEM_SELECTED_STYLE)
+        .highlight_symbol(">");
+    }
+
+    fn draw(&self, frame: &mut Frame, chunk: Rect) {
+        let mut songlist_table_state = self.songlist_table_state.clone();
+        frame.render_stateful_widget(&self.songlist_table, chunk, &mut songlist_table_state);
+    }
+}
@@ -0,0 +1,186 @@
+use crate::config::Command;
+use crate::ui::Controller;
+use crate::{NCM_API, PLAYER};
+use anyhow::Result;
+use ncm_api::SongInfo;
+use ratatui::layout::Rect;
+use ratatui::prelude::*;
+use ratatui::style::palette::tailwind;
+use ratatui::widgets::{Block, Borders, Cell, Row, Table, TableState};
+use ratatui::Frame;
+
+const ITEM_SELECTED_STYLE: Style = Style::new()
+    .bg(tailwind::RED.c400)
+    .add_modifier(Modifier::BOLD);
+
+pub struct QueueScreen<'a> {
+    // model
+    queue: Vec<SongInfo>,
+    queue_table_rows: Vec<Row<'a>>,
+    queue_table_state: TableState,
+
+    // view
+    queue_table: Table<'a>,
+}
+
+impl<'a> QueueScreen<'a> {
+    pub fn new(_normal_style: &Style) -> Self {
+        Self {
+            queue: Vec::new(),
+            queue_table_rows: Vec::new(),
+            queue_table_state: TableState::new(),
+            queue_table: Table::default(),
+        }
+    }
+}
+
+impl<'a> Controller for QueueScreen<'a> {
+    async fn update_model(&mut self) -> Result<bool> {
+        // 队列可能在其他页面被修改（加入、播放出队等），变化时重建 rows
+        let queue = PLAYER.lock().await.play_queue_ref().songs();
+        if queue == self.queue {
+            return Ok(false);
+        }
+        self.queue = queue;
+
+        self.queue_table_rows = self
+            .queue
+            .iter()
+            .enumerate()
+            .map(|(index, song_info)| {
+                Row::from_iter(vec![
+                    Cell::new(format!("{:02}", index + 1)),
+                    Cell::new(song_info.name.clone()),
+                    Cell::new(song_info.singer.clone()),
+                    Cell::new(song_info.album.clone()),
+                    Cell::new(format!(
+                        "{:02}:{:02}",
+                        song_info.duration / 60000,
+                        song_info.duration % 60000 / 1000
+                    )),
+                ])
+            })
+            .collect();
+
+        // 修正 selected，防止悬空
+        let selected = match self.queue_table_state.selected() {
+            _ if self.queue_table_rows.is_empty() => None,
+            Some(selected) => Some(selected.min(self.queue_table_rows.len() - 1)),
+            None => Some(0),
+        };
+        self.queue_table_state.select(selected);
+
+        Ok(true)
+    }
+
+    async fn handle_event(&mut self, cmd: Command) -> Result<bool> {
+        match cmd {
+            Command::Down => {
+                // 直接使用 select_next() 存在越界问题
+                if let (Some(selected), list_len) = (
+                    self.queue_table_state.selected(),
+                    self.queue_table_rows.len(),
+                ) {
+                    if selected + 1 < list_len {
+                        self.queue_table_state.select_next();
+                    }
+                }
+            }
+            Command::Up => self.queue_table_state.select_previous(),
+            Command::GoToTop => self.queue_table_state.select_first(),
+            Command::GoToBottom => {
+                // 使用 select_last() 会越界
+                if !self.queue_table_rows.is_empty() {
+                    self.queue_table_state
+                        .select(Some(self.queue_table_rows.len() - 1));
+                }
+            }
+            Command::Play => {
+                if let Some(selected) = self.queue_table_state.selected() {
+                    PLAYER
+                        .lock()
+                        .await
+                        .play_from_queue_now(selected, NCM_API.lock().await)
+                        .await?;
+                }
+            }
+            Command::Delete => {
+                if let Some(selected) = self.queue_table_state.selected() {
+                    PLAYER.lock().await.play_queue_mut().remove(selected);
+                }
+            }
+            Command::MoveUp => {
+                if let Some(selected) = self.queue_table_state.selected() {
+                    if selected > 0
+                        && PLAYER
+                            .lock()
+                            .await
+                            .play_queue_mut()
+                            .move_to(selected, selected - 1)
+                    {
+                        self.queue_table_state.select(Some(selected - 1));
+                    }
+                }
+            }
+            Command::MoveDown => {
+                if let Some(selected) = self.queue_table_state.selected() {
+                    if PLAYER
+                        .lock()
+                        .await
+                        .play_queue_mut()
+                        .move_to(selected, selected + 1)
+                    {
+                        self.queue_table_state.select(Some(selected + 1));
+                    }
+                }
+            }
+            _ => {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    fn update_view(&mut self, _style: &Style) {
+        let header_style = Style::default().fg(tailwind::WHITE).bg(tailwind::RED.c300);
+
+        self.queue_table = Table::new(
+            self.queue_table_rows.clone(),
+            [
+                Constraint::Length(3),
+                Constraint::Min(40),
+                Constraint::Min(15),
+                Constraint::Min(15),
+                Constraint::Length(6),
+            ],
+        )
+        .header(
+            Row::new(vec![
+                Cell::new("#"),
+                Cell::new("曲名"),
+                Cell::new("歌手/乐手"),
+                Cell::new("专辑"),
+                Cell::new("时长"),
+            ])
+            .style(header_style)
+            .height(1),
+        )
+        .block(
+            Block::default()
+                .title(format!("Queue: {} 首待播放\u{1F4CB}", self.queue.len()))
+                .title_bottom(
+                    Line::from("回车立即播放  d 移出队列  K / J 上移 / 下移  :queue clear 清空")
+                        .right_aligned(),
+                )
+                .borders(Borders::ALL),
+        )
+        .row_highlight_style(ITEM_SELECTED_STYLE)
+        .highlight_symbol(">");
+    }
+
+    fn draw(&self, frame: &mut Frame, chunk: Rect) {
+        let mut queue_table_state = self.queue_table_state.clone();
+        frame.render_stateful_widget(&self.queue_table, chunk, &mut queue_table_state);
+    }
+}
@@ -0,0 +1,437 @@
+use crate::config::Command;
+use crate::ui::Controller;
+use crate::{NCM_API, PLAYER};
+use anyhow::{anyhow, Result};
+use ncm_api::{SingerInfo, SongInfo, SongList};
+use ratatui::layout::Rect;
+use ratatui::prelude::*;
+use ratatui::style::palette::tailwind;
+use ratatui::widgets::{Block, Borders, Cell, Row, Table, TableState, Tabs};
+use ratatui::Frame;
+
+const ITEM_SELECTED_STYLE: Style = Style::new()
+    .bg(tailwind::RED.c400)
+    .add_modifier(Modifier::BOLD);
+const TAB_SELECTED_STYLE: Style = Style::new()
+    .fg(tailwind::RED.c600)
+    .add_modifier(Modifier::BOLD);
+
+/// 每页搜索结果数量
+const SEARCH_PAGE_SIZE: u16 = 30;
+
+#[derive(PartialEq, Clone, Copy)]
+enum SearchType {
+    Song,
+    Album,
+    Singer,
+    Songlist,
+}
+
+impl SearchType {
+    const ALL: [SearchType; 4] = [
+        SearchType::Song,
+        SearchType::Album,
+        SearchType::Singer,
+        SearchType::Songlist,
+    ];
+
+    fn index(&self) -> usize {
+        Self::ALL.iter().position(|t| t == self).unwrap_or(0)
+    }
+
+    fn title(&self) -> &'static str {
+        match self {
+            SearchType::Song => "单曲",
+            SearchType::Album => "专辑",
+            SearchType::Singer => "歌手",
+            SearchType::Songlist => "歌单",
+        }
+    }
+
+    fn next(&self) -> Self {
+        Self::ALL[(self.index() + 1) % Self::ALL.len()]
+    }
+
+    fn prev(&self) -> Self {
+        Self::ALL[(self.index() + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+}
+
+pub struct SearchScreen<'a> {
+    // model
+    search_keywords: Vec<String>,
+    search_type: SearchType,
+    search_page: u16,
+    //
+    song_results: Vec<SongInfo>,
+    songlist_results: Vec<SongList>, // 专辑与歌单共用
+    singer_results: Vec<SingerInfo>,
+    result_table_rows: Vec<Row<'a>>,
+    result_table_state: TableState,
+
+    // view
+    search_type_tabs: Tabs<'a>,
+    result_table: Table<'a>,
+}
+
+impl<'a> SearchScreen<'a> {
+    pub fn new(_normal_style: &Style) -> Self {
+        Self {
+            search_keywords: Vec::new(),
+            search_type: SearchType::Song,
+            search_page: 0,
+            song_results: Vec::new(),
+            songlist_results: Vec::new(),
+            singer_results: Vec::new(),
+            result_table_rows: Vec::new(),
+            result_table_state: TableState::new(),
+            search_type_tabs: Tabs::default(),
+            result_table: Table::default(),
+        }
+    }
+}
+
+impl<'a> Controller for SearchScreen<'a> {
+    async fn update_model(&mut self) -> Result<bool> {
+        Ok(false)
+    }
+
+    async fn handle_event(&mut self, cmd: Command) -> Result<bool> {
+        match cmd {
+            Command::SearchOnline(keywords) => {
+                self.search_keywords = keywords;
+                self.search_page = 0;
+                self.search().await?;
+            }
+            Command::NextPanel | Command::PrevPanel => {
+                self.search_type = match cmd {
+                    Command::NextPanel => self.search_type.next(),
+                    _ => self.search_type.prev(),
+                };
+                self.search_page = 0;
+                self.search().await?;
+            }
+            Command::NextPage => {
+                // 当前页未满说明已无更多结果，下一页的 offset 超出范围时同样停止
+                let next_page = self.search_page + 1;
+                if self.result_table_rows.len() < SEARCH_PAGE_SIZE as usize
+                    || next_page.checked_mul(SEARCH_PAGE_SIZE).is_none()
+                {
+                    return Ok(false);
+                }
+                self.search_page = next_page;
+                self.search().await?;
+                // 当前页恰好是最后一页时下一页为空，回到当前页
+                if self.result_table_rows.is_empty() {
+                    self.search_page -= 1;
+                    self.search().await?;
+                }
+            }
+            Command::PrevPage => {
+                if self.search_page == 0 {
+                    return Ok(false);
+                }
+                self.search_page -= 1;
+                self.search().await?;
+            }
+            Command::Down => {
+                // 直接使用 select_next() 存在越界问题
+                if let (Some(selected), list_len) = (
+                    self.result_table_state.selected(),
+                    self.result_table_rows.len(),
+                ) {
+                    if selected + 1 < list_len {
+                        self.result_table_state.select_next();
+                    }
+                }
+            }
+            Command::Up => self.result_table_state.select_previous(),
+            Command::GoToTop => self.result_table_state.select_first(),
+            Command::GoToBottom => {
+                // 使用 select_last() 会越界
+                if !self.result_table_rows.is_empty() {
+                    self.result_table_state
+                        .select(Some(self.result_table_rows.len() - 1));
+                }
+            }
+            Command::Play => {
+                self.play_selected().await?;
+            }
+            Command::AddToPlaylist => {
+                self.add_selected_to_playlist().await;
+            }
+            Command::Collect(subscribe) => {
+                self.collect_selected(subscribe.unwrap_or(true)).await?;
+            }
+            _ => {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    fn update_view(&mut self, _style: &Style) {
+        let header_style = Style::default().fg(tailwind::WHITE).bg(tailwind::RED.c300);
+
+        self.search_type_tabs = Tabs::new(SearchType::ALL.iter().map(|t| t.title()))
+            .select(self.search_type.index())
+            .highlight_style(TAB_SELECTED_STYLE)
+            .block(
+                Block::default()
+                    .title(if self.search_keywords.is_empty() {
+                        String::from("Search: 输入`:search xxx`搜索\u{1F50D}")
+                    } else {
+                        format!("Search: {}\u{1F50D}", self.search_keywords.join(" "))
+                    })
+                    .borders(Borders::ALL),
+            );
+
+        let (header, widths) = match self.search_type {
+            SearchType::Song => (
+                vec!["曲名", "歌手/乐手", "专辑", "时长"],
+                vec![
+                    Constraint::Min(40),
+                    Constraint::Min(15),
+                    Constraint::Min(15),
+                    Constraint::Length(6),
+                ],
+            ),
+            SearchType::Album => (
+                vec!["专辑", "歌手/乐手"],
+                vec![Constraint::Min(40), Constraint::Min(15)],
+            ),
+            SearchType::Singer => (vec!["歌手/乐手"], vec![Constraint::Min(40)]),
+            SearchType::Songlist => (
+                vec!["歌单", "创建者"],
+                vec![Constraint::Min(40), Constraint::Min(15)],
+            ),
+        };
+
+        self.result_table = Table::new(self.result_table_rows.clone(), widths)
+            .header(Row::new(header).style(header_style).height(1))
+            .block(
+                Block::default()
+                    .title(format!("第 {} 页", self.search_page + 1))
+                    .title_bottom(
+                        Line::from("[ / ] 翻页  a 加入当前播放列表  b 查看专辑  r 查看歌手")
+                            .right_aligned(),
+                    )
+                    .borders(Borders::ALL),
+            )
+            .row_highlight_style(ITEM_SELECTED_STYLE)
+            .highlight_symbol(">");
+    }
+
+    fn draw(&self, frame: &mut Frame, chunk: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(3)].as_ref())
+            .split(chunk);
+
+        // 上方渲染搜索类型
+        frame.render_widget(&self.search_type_tabs, chunks[0]);
+
+        // 下方渲染搜索结果
+        let mut result_table_state = self.result_table_state.clone();
+        frame.render_stateful_widget(&self.result_table, chunks[1], &mut result_table_state);
+    }
+}
+
+/// public
+impl<'a> SearchScreen<'a> {
+    /// 选中的单曲所属专辑或选中的专辑
+    pub fn selected_album_id(&self) -> Option<u64> {
+        let selected = self.result_table_state.selected()?;
+        match self.search_type {
+            SearchType::Song => self.song_results.get(selected).map(|s| s.album_id),
+            SearchType::Album => self.songlist_results.get(selected).map(|s| s.id),
+            _ => None,
+        }
+    }
+
+    /// 选中的单曲
+    pub fn selected_song_info(&self) -> Option<SongInfo> {
+        if self.search_type != SearchType::Song {
+            return None;
+        }
+        self.result_table_state
+            .selected()
+            .and_then(|selected| self.song_results.get(selected))
+            .cloned()
+    }
+
+    /// 选中的单曲的歌手或选中的歌手
+    pub fn selected_singers(&self) -> Vec<SingerInfo> {
+        match (self.search_type, self.result_table_state.selected()) {
+            (SearchType::Song, Some(selected)) => self
+                .song_results
+                .get(selected)
+                .map(|s| s.singers.clone())
+                .unwrap_or_default(),
+            (SearchType::Singer, Some(selected)) => self
+                .singer_results
+                .get(selected)
+                .cloned()
+                .into_iter()
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// private
+impl<'a> SearchScreen<'a> {
+    /// 按当前的关键词、类型和页码搜索，并更新结果
+    async fn search(&mut self) -> Result<()> {
+        // 先清空旧结果，防止搜索失败时表头与结果不对应
+        self.result_table_rows = Vec::new();
+        self.result_table_state.select(None);
+
+        if self.search_keywords.is_empty() {
+            return Ok(());
+        }
+
+        let keywords = self.search_keywords.join(" ");
+        let offset = self
+            .search_page
+            .checked_mul(SEARCH_PAGE_SIZE)
+            .ok_or_else(|| anyhow!("搜索页码超出范围"))?;
+        let ncm_api_guard = NCM_API.lock().await;
+
+        self.result_table_rows = match self.search_type {
+            SearchType::Song => {
+                self.song_results = ncm_api_guard
+                    .search_song(&keywords, offset, SEARCH_PAGE_SIZE)
+                    .await?;
+                self.song_results
+                    .iter()
+                    .map(|song_info| {
+                        Row::from_iter(vec![
+                            Cell::new(song_info.name.clone()),
+                            Cell::new(song_info.singer.clone()),
+                            Cell::new(song_info.album.clone()),
+                            Cell::new(format!(
+                                "{:02}:{:02}",
+                                song_info.duration / 60000,
+                                song_info.duration % 60000 / 1000
+                            )),
+                        ])
+                    })
+                    .collect()
+            }
+            SearchType::Album | SearchType::Songlist => {
+                self.songlist_results = if self.search_type == SearchType::Album {
+                    ncm_api_guard
+                        .search_album(&keywords, offset, SEARCH_PAGE_SIZE)
+                        .await?
+                } else {
+                    ncm_api_guard
+                        .search_songlist(&keywords, offset, SEARCH_PAGE_SIZE)
+                        .await?
+                };
+                self.songlist_results
+                    .iter()
+                    .map(|songlist| {
+                        Row::from_iter(vec![
+                            Cell::new(songlist.name.clone()),
+                            Cell::new(songlist.author.clone()),
+                        ])
+                    })
+                    .collect()
+            }
+            SearchType::Singer => {
+                self.singer_results = ncm_api_guard
+                    .search_singer(&keywords, offset, SEARCH_PAGE_SIZE)
+                    .await?;
+                self.singer_results
+                    .iter()
+                    .map(|singer_info| Row::from_iter(vec![Cell::new(singer_info.name.clone())]))
+                    .collect()
+            }
+        };
+
+        if !self.result_table_rows.is_empty() {
+            self.result_table_state.select(Some(0));
+        }
+
+        Ok(())
+    }
+
+    /// 单曲：加入当前播放列表并立刻播放
+    /// 歌单：切换为当前播放列表
+    async fn play_selected(&mut self) -> Result<()> {
+        if let Some(selected) = self.result_table_state.selected() {
+            match self.search_type {
+                SearchType::Song => {
+                    if let Some(song_info) = self.song_results.get(selected) {
+                        let mut player_guard = PLAYER.lock().await;
+                        let index = player_guard.add_to_playlist(song_info.clone());
+                        player_guard
+                            .play_particularly_now(index, NCM_API.lock().await)
+                            .await?;
+                    }
+                }
+                SearchType::Songlist => {
+                    if let Some(songlist) = self.songlist_results.get(selected) {
+                        let songs = NCM_API
+                            .lock()
+                            .await
+                            .song_list_detail(songlist.id)
+                            .await?
+                            .songs;
+                        PLAYER
+                            .lock()
+                            .await
+                            .switch_playlist(songlist.name.clone(), songs);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 收藏/取消收藏选中的专辑或歌单
+    async fn collect_selected(&self, subscribe: bool) -> Result<()> {
+        if let Some(selected) = self.result_table_state.selected() {
+            match self.search_type {
+                SearchType::Album => {
+                    if let Some(album) = self.songlist_results.get(selected) {
+                        NCM_API
+                            .lock()
+                            .await
+                            .subscribe_album(album.id, subscribe)
+                            .await?;
+                    }
+                }
+                SearchType::Songlist => {
+                    if let Some(songlist) = self.songlist_results.get(selected) {
+                        NCM_API
+                            .lock()
+                            .await
+                            .subscribe_playlist(songlist.id, subscribe)
+                            .await?;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 将选中的单曲加入当前播放列表
+    async fn add_selected_to_playlist(&mut self) {
+        if self.search_type == SearchType::Song {
+            if let Some(song_info) = self
+                .result_table_state
+                .selected()
+                .and_then(|selected| self.song_results.get(selected))
+            {
+                PLAYER.lock().await.add_to_playlist(song_info.clone());
+            }
+        }
+    }
+}
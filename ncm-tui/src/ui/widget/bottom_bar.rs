@@ -1,7 +1,9 @@
 use crate::config::Command;
 use crate::ui::Controller;
-use crate::PLAYER;
+use crate::{NCM_API, PLAYER};
 use anyhow::Result;
+use gstreamer::ClockTime;
+use ncm_play::{PlaybackState, PlayerEvent, PlayerEventReceiver};
 use ratatui::layout::{Layout, Rect};
 use ratatui::prelude::{Constraint, Direction, Style};
 use ratatui::style::palette::tailwind;
@@ -17,8 +19,14 @@ pub struct BottomBar<'a> {
     playback_label: String,
     song_name: Option<String>,
     singer_name: Option<String>,
+    song_liked: bool,
+    song_quality: Option<String>, // 实际播放的音质
     //
     volume: f64,
+    //
+    player_events: PlayerEventReceiver,
+    duration: Option<ClockTime>,
+    buffering_percent: Option<i32>, // 缓冲中时显示缓冲进度
 
     // view
     control_bar: Paragraph<'a>,
@@ -27,14 +35,19 @@ pub struct BottomBar<'a> {
 }
 
 impl<'a> BottomBar<'a> {
-    pub fn new(_normal_style: &Style) -> Self {
+    pub fn new(_normal_style: &Style, player_events: PlayerEventReceiver) -> Self {
         Self {
             info_bar_text: Text::default(),
             playback_ratio: 0.0,
             playback_label: String::new(),
             song_name: None,
             singer_name: None,
+            song_liked: false,
+            song_quality: None,
             volume: 0.0,
+            player_events,
+            duration: None,
+            buffering_percent: None,
             control_bar: Paragraph::default(),
             playback_bar: Gauge::default(),
             volume_bar: Gauge::default(),
@@ -44,6 +57,8 @@ impl<'a> BottomBar<'a> {
 
 impl<'a> Controller for BottomBar<'a> {
     async fn update_model(&mut self) -> Result<bool> {
+        self.handle_player_events();
+
         let player_guard = PLAYER.lock().await;
 
         // control_bar
@@ -61,13 +76,14 @@ impl<'a> Controller for BottomBar<'a> {
         );
 
         // playback_bar
-        if let (Some(player_position), Some(player_duration)) =
-            (player_guard.position(), player_guard.duration())
-        {
-            self.playback_ratio = if player_position.mseconds() as f64 / player_duration.mseconds() as f64 <= 1.0 {
-                player_position.mseconds() as f64 / player_duration.mseconds() as f64
+        if let (Some(player_position), Some(player_duration)) = (
+            player_guard.position(),
+            self.duration.or(player_guard.duration()),
+        ) {
+            self.playback_ratio = if player_duration.mseconds() > 0 {
+                (player_position.mseconds() as f64 / player_duration.mseconds() as f64).min(1.0)
             } else {
-                1.0
+                0.0
             };
             self.playback_label = format!(
                 "{:02}:{:02}/{:02}:{:02}",
@@ -80,10 +96,25 @@ impl<'a> Controller for BottomBar<'a> {
             self.playback_ratio = 0.0;
             self.playback_label = String::from("--:--/--:--");
         };
+        if let Some(percent) = self.buffering_percent {
+            self.playback_label = format!("缓冲中 {}%", percent);
+        }
         if let Some(song_info) = player_guard.current_song_info_ref().clone() {
             self.song_name = Some(song_info.name.clone());
             self.singer_name = Some(song_info.singer.clone());
+            self.song_liked = NCM_API.lock().await.is_liked_song(song_info.id);
         }
+        self.song_quality = player_guard
+            .current_song_url_ref()
+            .as_ref()
+            .map(|song_url| {
+                format!(
+                    "{} {}kbps {:.1}MB",
+                    song_url.codec.to_uppercase(),
+                    song_url.rate / 1000,
+                    song_url.size as f64 / 1024.0 / 1024.0
+                )
+            });
 
         // volume_bar
         self.volume = player_guard.volume();
@@ -108,9 +139,19 @@ impl<'a> Controller for BottomBar<'a> {
                     (self.song_name.clone(), self.singer_name.clone())
                 {
                     block = block
-                        .title_top(Line::from(format!("{}", song_name)).centered())
+                        .title_top(
+                            Line::from(if self.song_liked {
+                                format!("\u{2665} {}", song_name)
+                            } else {
+                                song_name
+                            })
+                            .centered(),
+                        )
                         .title_bottom(Line::from(format!("{}", artist_name)).centered());
                 }
+                if let Some(song_quality) = self.song_quality.clone() {
+                    block = block.title_bottom(Line::from(song_quality).right_aligned());
+                }
                 block
             })
             .gauge_style(tailwind::PINK.c300)
@@ -151,3 +192,27 @@ impl<'a> Controller for BottomBar<'a> {
         frame.render_widget(&self.volume_bar, bottom_bar_chunks[2]);
     }
 }
+
+/// private
+impl<'a> BottomBar<'a> {
+    /// 根据播放器事件更新时长和缓冲进度
+    fn handle_player_events(&mut self) {
+        while let Ok(event) = self.player_events.try_recv() {
+            match event {
+                PlayerEvent::DurationChanged(duration) => self.duration = duration,
+                PlayerEvent::Buffering(percent) if percent < 100 => {
+                    self.buffering_percent = Some(percent)
+                }
+                PlayerEvent::Buffering(_)
+                | PlayerEvent::StateChanged(PlaybackState::Playing | PlaybackState::Paused) => {
+                    self.buffering_percent = None
+                }
+                PlayerEvent::StateChanged(PlaybackState::Stopped) => {
+                    self.duration = None;
+                    self.buffering_percent = None;
+                }
+                _ => {}
+            }
+        }
+    }
+}